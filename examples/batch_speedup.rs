@@ -0,0 +1,38 @@
+//! Example: Compare serial and parallel batch repair over a directory of inputs
+//!
+//! Run with: cargo run --release --example batch_speedup -- examples/data [jobs] [min-speedup]
+//!
+//! Exits with an error if the two runs disagree on any input, or if the speedup
+//! falls below `min-speedup` when one is given.
+
+use anyrepair::analytics::benchmark_corpus;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let dir = args.next().unwrap_or_else(|| "examples/data".to_string());
+    let jobs = match args.next() {
+        Some(jobs) => jobs.parse()?,
+        None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let min_speedup: Option<f64> = args.next().map(|s| s.parse()).transpose()?;
+
+    let benchmark = benchmark_corpus(&dir, jobs)?;
+
+    println!("=== Corpus: {} ===\n", dir);
+    println!("Inputs:    {}", benchmark.inputs);
+    println!("Jobs:      {}", benchmark.jobs);
+    println!("Serial:    {:?}", benchmark.serial);
+    println!("Parallel:  {:?}", benchmark.parallel);
+    println!("Speedup:   {:.2}x", benchmark.speedup());
+    println!("Identical: {}", benchmark.identical);
+
+    if !benchmark.identical {
+        return Err("parallel results differ from serial results".into());
+    }
+    if let Some(min) = min_speedup
+        && benchmark.speedup() < min
+    {
+        return Err(format!("speedup {:.2}x is below {:.2}x", benchmark.speedup(), min).into());
+    }
+    Ok(())
+}
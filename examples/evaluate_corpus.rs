@@ -0,0 +1,31 @@
+//! Example: Measure repair quality over a directory of inputs
+//!
+//! Run with: cargo run --example evaluate_corpus -- examples/data
+
+use anyrepair::analytics::evaluate_corpus;
+
+fn main() -> anyrepair::Result<()> {
+    let dir = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/data".to_string());
+    let report = evaluate_corpus(&dir)?;
+
+    println!("=== Corpus: {} ===\n", dir);
+    println!("Inputs:          {}", report.inputs);
+    println!("Failures:        {}", report.failures);
+    println!("Valid before:    {}", report.valid_before);
+    println!(
+        "Valid after:     {} ({:.1}%)",
+        report.valid_after,
+        report.valid_fraction() * 100.0
+    );
+    println!("Confidence gain: {:+.3}\n", report.average_confidence_gain);
+
+    println!("Strategies applied:");
+    let mut counts: Vec<_> = report.strategy_counts.iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    for (strategy, count) in counts {
+        println!("  {:>4}  {}", count, strategy);
+    }
+    Ok(())
+}
@@ -0,0 +1,628 @@
+//! Repair metrics for long-running servers and strategy tuning
+//!
+//! [`AnalyticsTracker`] keeps lock-free counters for totals and failures, a
+//! bounded window of latency samples and per-minute counts for the last hour,
+//! and exports a Prometheus text snapshot or a JSON time series.
+//! [`evaluate_corpus`] repairs every file under a directory and summarizes how
+//! well the strategies did, for comparing changes against a fixed set of inputs.
+//! [`benchmark_batch`] times the same inputs repaired serially and in parallel.
+
+use crate::error::Result;
+use crate::json_util::json_string;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Number of most recent latency samples kept for percentile estimates
+const LATENCY_WINDOW: usize = 1024;
+
+/// Width of a time-series bucket
+const BUCKET_SECONDS: u64 = 60;
+
+/// Number of most recent time-series buckets kept (one hour of minutes)
+const BUCKET_WINDOW: usize = 60;
+
+/// Fixed-point scale used to accumulate confidence scores atomically
+const CONFIDENCE_SCALE: f64 = 1_000_000.0;
+
+/// Thread-safe repair metrics, shareable across request handlers
+#[derive(Debug, Default)]
+pub struct AnalyticsTracker {
+    total: AtomicU64,
+    failures: AtomicU64,
+    confidence_sum: AtomicU64,
+    confidence_count: AtomicU64,
+    per_format: Mutex<BTreeMap<String, u64>>,
+    latencies_us: Mutex<VecDeque<u64>>,
+    buckets: Mutex<VecDeque<TimeBucket>>,
+}
+
+/// Repairs of one format within a [`TimeBucket`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BucketCounts {
+    /// Repairs attempted, including failures
+    pub repairs: u64,
+    /// Repairs that returned an error
+    pub failures: u64,
+}
+
+/// Repair counts for one minute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeBucket {
+    /// Start of the minute, in seconds since the Unix epoch
+    pub start: u64,
+    /// Counts per format name
+    pub per_format: BTreeMap<String, BucketCounts>,
+}
+
+/// Point-in-time snapshot of an [`AnalyticsTracker`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metrics {
+    /// Repairs attempted, including failures
+    pub total_repairs: u64,
+    /// Repairs that returned an error
+    pub failures: u64,
+    /// Repairs attempted per format name
+    pub per_format: BTreeMap<String, u64>,
+    /// Mean confidence of successful repairs (0.0 if none)
+    pub average_confidence: f64,
+    /// Median latency over the recent sample window
+    pub p50_latency: Duration,
+    /// 95th percentile latency over the recent sample window
+    pub p95_latency: Duration,
+}
+
+impl AnalyticsTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a successful repair with its confidence score
+    pub fn record_success(&self, format: &str, confidence: f64, latency: Duration) {
+        self.record(format, latency, false);
+        let scaled = (confidence.clamp(0.0, 1.0) * CONFIDENCE_SCALE).round() as u64;
+        self.confidence_sum.fetch_add(scaled, Ordering::Relaxed);
+        self.confidence_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a repair that returned an error
+    pub fn record_failure(&self, format: &str, latency: Duration) {
+        self.record(format, latency, true);
+        self.failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record(&self, format: &str, latency: Duration, failed: bool) {
+        self.count_in_bucket(format, failed, SystemTime::now());
+        self.total.fetch_add(1, Ordering::Relaxed);
+        *lock(&self.per_format)
+            .entry(format.to_string())
+            .or_insert(0) += 1;
+
+        let mut latencies = lock(&self.latencies_us);
+        if latencies.len() == LATENCY_WINDOW {
+            latencies.pop_front();
+        }
+        latencies.push_back(latency.as_micros().min(u64::MAX as u128) as u64);
+    }
+
+    /// Add a repair at time `at` to its minute's bucket, dropping buckets that
+    /// fall out of the window
+    fn count_in_bucket(&self, format: &str, failed: bool, at: SystemTime) {
+        let seconds = at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let start = seconds - seconds % BUCKET_SECONDS;
+
+        let mut buckets = lock(&self.buckets);
+        let newest = buckets.back().map_or(start, |last| last.start.max(start));
+        let oldest = newest.saturating_sub((BUCKET_WINDOW as u64 - 1) * BUCKET_SECONDS);
+        while buckets.front().is_some_and(|first| first.start < oldest) {
+            buckets.pop_front();
+        }
+        if start < oldest {
+            return;
+        }
+
+        // Buckets stay sorted by start, so late arrivals land in their own minute
+        let index = buckets.partition_point(|bucket| bucket.start < start);
+        if buckets
+            .get(index)
+            .is_none_or(|bucket| bucket.start != start)
+        {
+            buckets.insert(
+                index,
+                TimeBucket {
+                    start,
+                    per_format: BTreeMap::new(),
+                },
+            );
+        }
+        let counts = buckets[index]
+            .per_format
+            .entry(format.to_string())
+            .or_default();
+        counts.repairs += 1;
+        counts.failures += u64::from(failed);
+    }
+
+    /// Per-minute repair counts for the last hour of activity, oldest first.
+    /// Minutes without repairs have no bucket.
+    pub fn timeseries(&self) -> Vec<TimeBucket> {
+        lock(&self.buckets).iter().cloned().collect()
+    }
+
+    /// Export [`timeseries`](Self::timeseries) as JSON for dashboards:
+    /// `{"bucket_seconds": 60, "buckets": [{"start": 1700000040, "formats":
+    /// {"json": {"repairs": 2, "failures": 1}}}]}`
+    pub fn to_json_timeseries(&self) -> String {
+        let buckets: Vec<String> = self
+            .timeseries()
+            .iter()
+            .map(|bucket| {
+                let formats: Vec<String> = bucket
+                    .per_format
+                    .iter()
+                    .map(|(format, counts)| {
+                        format!(
+                            "{}: {{\"repairs\": {}, \"failures\": {}}}",
+                            json_string(format),
+                            counts.repairs,
+                            counts.failures
+                        )
+                    })
+                    .collect();
+                format!(
+                    "{{\"start\": {}, \"formats\": {{{}}}}}",
+                    bucket.start,
+                    formats.join(", ")
+                )
+            })
+            .collect();
+        format!(
+            "{{\"bucket_seconds\": {}, \"buckets\": [{}]}}",
+            BUCKET_SECONDS,
+            buckets.join(", ")
+        )
+    }
+
+    /// Take a snapshot of the current counters
+    pub fn metrics(&self) -> Metrics {
+        let count = self.confidence_count.load(Ordering::Relaxed);
+        let average_confidence = if count == 0 {
+            0.0
+        } else {
+            self.confidence_sum.load(Ordering::Relaxed) as f64 / CONFIDENCE_SCALE / count as f64
+        };
+
+        let mut sorted: Vec<u64> = lock(&self.latencies_us).iter().copied().collect();
+        sorted.sort_unstable();
+
+        Metrics {
+            total_repairs: self.total.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            per_format: lock(&self.per_format).clone(),
+            average_confidence,
+            p50_latency: percentile(&sorted, 0.50),
+            p95_latency: percentile(&sorted, 0.95),
+        }
+    }
+
+    /// Export the current counters in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        self.metrics().to_prometheus()
+    }
+}
+
+impl Metrics {
+    /// Render this snapshot in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# HELP anyrepair_repairs_total Repairs attempted");
+        let _ = writeln!(out, "# TYPE anyrepair_repairs_total counter");
+        let _ = writeln!(out, "anyrepair_repairs_total {}", self.total_repairs);
+        let _ = writeln!(
+            out,
+            "# HELP anyrepair_repair_failures_total Repairs that returned an error"
+        );
+        let _ = writeln!(out, "# TYPE anyrepair_repair_failures_total counter");
+        let _ = writeln!(out, "anyrepair_repair_failures_total {}", self.failures);
+        let _ = writeln!(
+            out,
+            "# HELP anyrepair_format_repairs_total Repairs attempted per format"
+        );
+        let _ = writeln!(out, "# TYPE anyrepair_format_repairs_total counter");
+        for (format, count) in &self.per_format {
+            let _ = writeln!(
+                out,
+                "anyrepair_format_repairs_total{{format=\"{}\"}} {}",
+                format.replace('\\', "\\\\").replace('"', "\\\""),
+                count
+            );
+        }
+        let _ = writeln!(
+            out,
+            "# HELP anyrepair_confidence_average Mean confidence of successful repairs"
+        );
+        let _ = writeln!(out, "# TYPE anyrepair_confidence_average gauge");
+        let _ = writeln!(
+            out,
+            "anyrepair_confidence_average {}",
+            self.average_confidence
+        );
+        let _ = writeln!(
+            out,
+            "# HELP anyrepair_latency_seconds Repair latency over recent requests"
+        );
+        let _ = writeln!(out, "# TYPE anyrepair_latency_seconds summary");
+        let _ = writeln!(
+            out,
+            "anyrepair_latency_seconds{{quantile=\"0.5\"}} {}",
+            self.p50_latency.as_secs_f64()
+        );
+        let _ = writeln!(
+            out,
+            "anyrepair_latency_seconds{{quantile=\"0.95\"}} {}",
+            self.p95_latency.as_secs_f64()
+        );
+        out
+    }
+}
+
+/// Repair quality over a directory of inputs, from [`evaluate_corpus`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CorpusReport {
+    /// Files found under the directory
+    pub inputs: usize,
+    /// Files that could not be read, whose format could not be determined or whose
+    /// repair returned an error
+    pub failures: usize,
+    /// Files that were already valid before repair
+    pub valid_before: usize,
+    /// Files whose repaired output is valid
+    pub valid_after: usize,
+    /// Mean of `confidence_after - confidence_before` over successful repairs
+    pub average_confidence_gain: f64,
+    /// Number of files each strategy changed, by strategy name
+    pub strategy_counts: BTreeMap<String, usize>,
+}
+
+impl CorpusReport {
+    /// Fraction of the inputs (0.0–1.0) whose repaired output is valid
+    pub fn valid_fraction(&self) -> f64 {
+        if self.inputs == 0 {
+            0.0
+        } else {
+            self.valid_after as f64 / self.inputs as f64
+        }
+    }
+}
+
+/// Repair every file under `dir` (recursively) and summarize the results.
+///
+/// Each file is repaired as the format its extension names, falling back to
+/// content detection. A file that cannot be read as UTF-8 text counts as a
+/// failure; returns `RepairError::Io` if the directory cannot be read.
+pub fn evaluate_corpus(dir: impl AsRef<Path>) -> Result<CorpusReport> {
+    let mut files = Vec::new();
+    collect_files(dir.as_ref(), &mut files)?;
+    files.sort();
+
+    let mut report = CorpusReport {
+        inputs: files.len(),
+        ..CorpusReport::default()
+    };
+    let mut gain_sum = 0.0;
+    let mut repaired = 0;
+    for path in files {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            report.failures += 1;
+            continue;
+        };
+        let Some(format) = crate::format_detection::format_from_path(&path)
+            .or_else(|| crate::detect_format(&content))
+        else {
+            report.failures += 1;
+            continue;
+        };
+        let Ok(repair) = crate::repair_report(&content, format) else {
+            report.failures += 1;
+            continue;
+        };
+
+        let validator = crate::create_validator(format)?;
+        report.valid_before += usize::from(validator.is_valid(&content));
+        report.valid_after += usize::from(validator.is_valid(&repair.repaired));
+        gain_sum += repair.confidence_after - repair.confidence_before;
+        repaired += 1;
+        for strategy in repair.applied_strategies {
+            *report.strategy_counts.entry(strategy).or_insert(0) += 1;
+        }
+    }
+    if repaired > 0 {
+        report.average_confidence_gain = gain_sum / repaired as f64;
+    }
+    Ok(report)
+}
+
+/// Timing of the same inputs repaired serially and with [`crate::repair_batch`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchBenchmark {
+    /// Number of inputs repaired in each run
+    pub inputs: usize,
+    /// Worker threads used for the parallel run
+    pub jobs: usize,
+    /// Wall time of the serial run
+    pub serial: Duration,
+    /// Wall time of the parallel run
+    pub parallel: Duration,
+    /// Whether both runs produced the same result (or error) for every input
+    pub identical: bool,
+}
+
+impl BatchBenchmark {
+    /// Serial time divided by parallel time (1.0 if the parallel run took no
+    /// measurable time)
+    pub fn speedup(&self) -> f64 {
+        if self.parallel.is_zero() {
+            1.0
+        } else {
+            self.serial.as_secs_f64() / self.parallel.as_secs_f64()
+        }
+    }
+}
+
+/// Repair `inputs` serially, then on `jobs` threads, and report both wall times
+/// and whether the results agree
+pub fn benchmark_batch(inputs: &[&str], jobs: usize) -> BatchBenchmark {
+    let outcome = |results: Vec<Result<String>>| -> Vec<std::result::Result<String, String>> {
+        results
+            .into_iter()
+            .map(|result| result.map_err(|e| e.to_string()))
+            .collect()
+    };
+
+    let start = Instant::now();
+    let serial = outcome(crate::repair_batch(inputs, 1));
+    let serial_time = start.elapsed();
+
+    let start = Instant::now();
+    let parallel = outcome(crate::repair_batch(inputs, jobs));
+    let parallel_time = start.elapsed();
+
+    BatchBenchmark {
+        inputs: inputs.len(),
+        jobs,
+        serial: serial_time,
+        parallel: parallel_time,
+        identical: serial == parallel,
+    }
+}
+
+/// Run [`benchmark_batch`] over every file under `dir` (recursively).
+/// Returns `RepairError::Io` if the directory or a file cannot be read.
+pub fn benchmark_corpus(dir: impl AsRef<Path>, jobs: usize) -> Result<BatchBenchmark> {
+    let mut files = Vec::new();
+    collect_files(dir.as_ref(), &mut files)?;
+    files.sort();
+    let contents = files
+        .iter()
+        .map(std::fs::read_to_string)
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let inputs: Vec<&str> = contents.iter().map(String::as_str).collect();
+    Ok(benchmark_batch(&inputs, jobs))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Nearest-rank percentile of sorted microsecond samples
+fn percentile(sorted: &[u64], q: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((q * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len());
+    Duration::from_micros(sorted[rank - 1])
+}
+
+/// Lock a mutex, recovering the data if another thread panicked while holding it
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<AnalyticsTracker>();
+    }
+
+    #[test]
+    fn test_concurrent_counters() {
+        let tracker = AnalyticsTracker::new();
+        std::thread::scope(|s| {
+            for t in 0..8 {
+                let tracker = &tracker;
+                s.spawn(move || {
+                    let format = if t % 2 == 0 { "json" } else { "yaml" };
+                    for _ in 0..100 {
+                        tracker.record_success(format, 0.5, Duration::from_micros(10));
+                    }
+                    tracker.record_failure(format, Duration::from_micros(10));
+                });
+            }
+        });
+
+        let metrics = tracker.metrics();
+        assert_eq!(metrics.total_repairs, 808);
+        assert_eq!(metrics.failures, 8);
+        assert_eq!(metrics.per_format["json"], 404);
+        assert_eq!(metrics.per_format["yaml"], 404);
+        assert!((metrics.average_confidence - 0.5).abs() < 1e-9);
+        let bucketed: u64 = tracker
+            .timeseries()
+            .iter()
+            .flat_map(|bucket| bucket.per_format.values())
+            .map(|counts| counts.repairs)
+            .sum();
+        assert_eq!(bucketed, 808);
+    }
+
+    #[test]
+    fn test_parallel_batch_matches_serial() {
+        let inputs = [
+            r#"{"a": 1, "b": [1, 2,],}"#,
+            "name: John\nage 30",
+            "<root><item>value</item>",
+            "[server\nport = 8080",
+            "name,age\nJohn,30,\nJane,25",
+            "#Title\n**bold",
+            "",
+            "{'quote': 'He said \"hi\"'}",
+        ];
+        let serial = crate::repair_batch(&inputs, 1);
+        let parallel = crate::repair_batch(&inputs, 4);
+        assert_eq!(serial.len(), inputs.len());
+        for ((input, serial), parallel) in inputs.iter().zip(&serial).zip(&parallel) {
+            assert_eq!(serial.as_ref().ok(), parallel.as_ref().ok(), "{}", input);
+            assert_eq!(serial.as_ref().ok(), crate::repair(input).as_ref().ok());
+        }
+
+        let benchmark = benchmark_batch(&inputs, 4);
+        assert_eq!(benchmark.inputs, inputs.len());
+        assert!(benchmark.identical);
+        assert!(benchmark.speedup() > 0.0);
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let tracker = AnalyticsTracker::new();
+        for ms in 1..=100 {
+            tracker.record_success("json", 1.0, Duration::from_millis(ms));
+        }
+        let metrics = tracker.metrics();
+        assert_eq!(metrics.p50_latency, Duration::from_millis(50));
+        assert_eq!(metrics.p95_latency, Duration::from_millis(95));
+    }
+
+    #[test]
+    fn test_prometheus_export() {
+        let tracker = AnalyticsTracker::new();
+        tracker.record_success("json", 0.9, Duration::from_millis(2));
+        tracker.record_failure("xml", Duration::from_millis(4));
+
+        let text = tracker.to_prometheus();
+        assert!(text.contains("anyrepair_repairs_total 2\n"));
+        assert!(text.contains("anyrepair_repair_failures_total 1\n"));
+        assert!(text.contains("anyrepair_format_repairs_total{format=\"json\"} 1\n"));
+        assert!(text.contains("anyrepair_confidence_average 0.9\n"));
+        assert!(text.contains("# TYPE anyrepair_latency_seconds summary"));
+    }
+
+    #[test]
+    fn test_timeseries_buckets() {
+        let tracker = AnalyticsTracker::new();
+        let at = |seconds: u64| UNIX_EPOCH + Duration::from_secs(seconds);
+        let base = 1_700_000_040; // the start of a minute
+        tracker.count_in_bucket("json", false, at(base));
+        tracker.count_in_bucket("json", true, at(base + 59));
+        tracker.count_in_bucket("yaml", false, at(base + 30));
+        tracker.count_in_bucket("json", false, at(base + 125));
+        // Late arrivals still land in their own minute
+        tracker.count_in_bucket("yaml", true, at(base + 10));
+        tracker.count_in_bucket("toml", false, at(base + 60));
+
+        let buckets = tracker.timeseries();
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets[0].start, base);
+        assert_eq!(
+            buckets[0].per_format["json"],
+            BucketCounts {
+                repairs: 2,
+                failures: 1
+            }
+        );
+        assert_eq!(
+            buckets[0].per_format["yaml"],
+            BucketCounts {
+                repairs: 2,
+                failures: 1
+            }
+        );
+        assert_eq!(buckets[1].start, base + 60);
+        assert_eq!(buckets[2].start, base + 120);
+        assert_eq!(
+            tracker.to_json_timeseries(),
+            format!(
+                "{{\"bucket_seconds\": 60, \"buckets\": [\
+                 {{\"start\": {}, \"formats\": {{\"json\": {{\"repairs\": 2, \"failures\": 1}}, \
+                 \"yaml\": {{\"repairs\": 2, \"failures\": 1}}}}}}, \
+                 {{\"start\": {}, \"formats\": {{\"toml\": {{\"repairs\": 1, \"failures\": 0}}}}}}, \
+                 {{\"start\": {}, \"formats\": {{\"json\": {{\"repairs\": 1, \"failures\": 0}}}}}}]}}",
+                base,
+                base + 60,
+                base + 120
+            )
+        );
+        assert!(crate::json_util::is_valid_json(
+            &tracker.to_json_timeseries()
+        ));
+
+        // Only the last hour is kept, and repairs older than that are dropped
+        tracker.count_in_bucket("json", false, at(base + 7200));
+        let buckets = tracker.timeseries();
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].start, base + 7200);
+        tracker.count_in_bucket("json", false, at(base));
+        assert_eq!(tracker.timeseries(), buckets);
+    }
+
+    #[test]
+    fn test_evaluate_corpus() {
+        let dir = std::env::temp_dir().join(format!("anyrepair-corpus-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        for (name, content) in [
+            ("valid.json", r#"{"a": 1}"#),
+            ("trailing.json", r#"{"a": [1, 2,],}"#),
+            ("nested/colons.yaml", "name John\nage: 30"),
+            ("deep.json", &"[".repeat(600)),
+        ] {
+            std::fs::write(dir.join(name), content).unwrap();
+        }
+        std::fs::write(dir.join("latin1.json"), b"{\"caf\xe9\": 1}").unwrap();
+
+        let report = evaluate_corpus(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(report.inputs, 5);
+        assert_eq!(report.failures, 2);
+        assert_eq!(report.valid_before, 1);
+        assert_eq!(report.valid_after, 3);
+        assert_eq!(report.valid_fraction(), 0.6);
+        assert!(report.average_confidence_gain > 0.0);
+        assert_eq!(report.strategy_counts["FixTrailingCommas"], 1);
+        assert_eq!(report.strategy_counts["FixIndentationStrategy"], 1);
+
+        assert!(evaluate_corpus(Path::new("/nonexistent/anyrepair")).is_err());
+    }
+
+    #[test]
+    fn test_empty_metrics() {
+        let metrics = AnalyticsTracker::new().metrics();
+        assert_eq!(metrics.total_repairs, 0);
+        assert_eq!(metrics.average_confidence, 0.0);
+        assert_eq!(metrics.p95_latency, Duration::ZERO);
+    }
+}
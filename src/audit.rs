@@ -0,0 +1,198 @@
+//! Audit trail of repairs
+//!
+//! [`AuditLogger`] keeps a record of what each repair received and produced so
+//! changes to data can be reviewed later. With redaction enabled, secrets are
+//! masked before an entry is stored, so raw values never reach the log.
+
+use crate::error::Result;
+use crate::json_util::json_string;
+use crate::report::RepairReport;
+use regex::{Captures, Regex};
+use std::io::{self, Write};
+use std::sync::{Mutex, OnceLock};
+
+/// Replacement for redacted text
+const REDACTED: &str = "***";
+
+/// One audited repair
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    /// Format the content was repaired as
+    pub format: String,
+    /// Input, after redaction
+    pub original: String,
+    /// Output, after redaction
+    pub repaired: String,
+    /// Names of the strategies that changed the content
+    pub applied_strategies: Vec<String>,
+}
+
+/// Thread-safe in-memory audit log, shareable across request handlers
+#[derive(Debug, Default)]
+pub struct AuditLogger {
+    entries: Mutex<Vec<AuditEntry>>,
+    redactions: Vec<Regex>,
+    redact_secrets: bool,
+}
+
+impl AuditLogger {
+    /// Create a logger that stores content as-is
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a logger that replaces every match of `patterns`, and the values of
+    /// keys such as `password`, `api_key` or `token`, with `***` before storing
+    pub fn with_redaction(patterns: Vec<Regex>) -> Self {
+        Self {
+            redactions: patterns,
+            redact_secrets: true,
+            ..Self::default()
+        }
+    }
+
+    /// Repair `content` as `format`, record the result and return the repaired content
+    pub fn repair(&self, content: &str, format: &str) -> Result<String> {
+        let report = crate::repair_report(content, format)?;
+        self.record(format, content, &report);
+        Ok(report.repaired)
+    }
+
+    /// Record a repair of `original` described by `report`
+    pub fn record(&self, format: &str, original: &str, report: &RepairReport) {
+        let entry = AuditEntry {
+            format: format.to_string(),
+            original: self.redact(original),
+            repaired: self.redact(&report.repaired),
+            applied_strategies: report.applied_strategies.clone(),
+        };
+        lock(&self.entries).push(entry);
+    }
+
+    /// Snapshot of the recorded entries, oldest first
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        lock(&self.entries).clone()
+    }
+
+    /// Write the entries as JSON Lines, one object per repair
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        for entry in lock(&self.entries).iter() {
+            let strategies: Vec<String> = entry
+                .applied_strategies
+                .iter()
+                .map(|name| json_string(name))
+                .collect();
+            writeln!(
+                writer,
+                r#"{{"format": {}, "applied_strategies": [{}], "original": {}, "repaired": {}}}"#,
+                json_string(&entry.format),
+                strategies.join(", "),
+                json_string(&entry.original),
+                json_string(&entry.repaired),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn redact(&self, content: &str) -> String {
+        let mut redacted = content.to_string();
+        if self.redact_secrets {
+            redacted = secret_value_regex()
+                .replace_all(&redacted, |caps: &Captures| {
+                    let value = &caps[2];
+                    let quote = value
+                        .chars()
+                        .next()
+                        .filter(|c| matches!(c, '"' | '\''))
+                        .map(String::from)
+                        .unwrap_or_default();
+                    format!("{}{quote}{REDACTED}{quote}", &caps[1])
+                })
+                .into_owned();
+        }
+        for pattern in &self.redactions {
+            redacted = pattern.replace_all(&redacted, REDACTED).into_owned();
+        }
+        redacted
+    }
+}
+
+/// `key = value` / `key: value` / `"key": "value"` where the key names a secret
+/// (a quoted value honours `\` escapes and, if unterminated, runs to the end of the line)
+fn secret_value_regex() -> &'static Regex {
+    static SECRET_VALUE: OnceLock<Regex> = OnceLock::new();
+    SECRET_VALUE.get_or_init(|| {
+        Regex::new(
+            r#"(?i)(\b[\w.-]*(?:password|passwd|secret|api[_-]?key|token|credentials?|private[_-]?key)[\w.-]*["']?\s*[:=]\s*)("(?:\\.|[^"\\\n])*"?|'(?:\\.|[^'\\\n])*'?|[^\s,;}\]]+)"#,
+        )
+        .expect("secret redaction regex is valid")
+    })
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_secret_keys() {
+        let logger = AuditLogger::with_redaction(Vec::new());
+        let repaired = logger
+            .repair("[db]\nhost = localhost\npassword = secret\n", "ini")
+            .unwrap();
+        assert!(repaired.contains("password = secret"));
+
+        let entry = &logger.entries()[0];
+        assert_eq!(entry.format, "ini");
+        assert!(entry.repaired.contains("host = localhost"));
+        assert!(entry.repaired.contains("password = ***"));
+        assert!(!entry.repaired.contains("secret"));
+        assert!(!entry.original.contains("secret"));
+    }
+
+    #[test]
+    fn test_redacts_escaped_and_unterminated_values() {
+        let logger = AuditLogger::with_redaction(Vec::new());
+        assert_eq!(
+            logger.redact(r#"{"api_key": "abc\"def ghi", "user": "ann"}"#),
+            r#"{"api_key": "***", "user": "ann"}"#
+        );
+        assert_eq!(
+            logger.redact("{\"token\": \"abc def, ghi\n\"user\": \"ann\"}"),
+            "{\"token\": \"***\"\n\"user\": \"ann\"}"
+        );
+        assert_eq!(logger.redact("password: 'a\\'b c"), "password: '***'");
+    }
+
+    #[test]
+    fn test_custom_patterns_and_json_values() {
+        let logger = AuditLogger::with_redaction(vec![Regex::new(r"\d{4}-\d{4}").unwrap()]);
+        logger
+            .repair(
+                r#"{"api_key": "sk-1", "card": "1234-5678", "user": "ann",}"#,
+                "json",
+            )
+            .unwrap();
+
+        let mut out = Vec::new();
+        logger.write_to(&mut out).unwrap();
+        let line = String::from_utf8(out).unwrap();
+        assert!(line.contains(r#"\"api_key\": \"***\""#), "{line}");
+        assert!(line.contains(r#"\"card\": \"***\""#), "{line}");
+        assert!(line.contains(r#"\"user\": \"ann\""#), "{line}");
+        assert!(
+            line.contains(r#""applied_strategies": ["FixTrailingCommas"]"#),
+            "{line}"
+        );
+    }
+
+    #[test]
+    fn test_plain_logger_keeps_content() {
+        let logger = AuditLogger::new();
+        logger.repair("password = secret", "ini").unwrap();
+        assert!(logger.entries()[0].original.contains("secret"));
+    }
+}
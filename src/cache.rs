@@ -0,0 +1,156 @@
+//! Memoization of repair results for servers that see the same inputs repeatedly
+//!
+//! [`RepairCache`] is a capacity-bounded LRU keyed by `(format, input)`,
+//! where a repairer with options may extend `format` with a fingerprint of them.
+//! It is shared between repairers through an `Arc` and is safe to use from
+//! multiple threads.
+
+use crate::report::RepairReport;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Keyed on the whole input, so distinct inputs never share an entry
+type CacheKey = (String, String);
+
+/// Thread-safe LRU cache of [`RepairReport`]s
+#[derive(Debug)]
+pub struct RepairCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    /// Cached reports with the tick of their last use
+    entries: HashMap<CacheKey, (RepairReport, u64)>,
+    /// Keys ordered by last use, oldest first
+    recency: BTreeMap<u64, CacheKey>,
+    tick: u64,
+}
+
+impl RepairCache {
+    /// Create a cache holding at most `capacity` reports (0 disables caching)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(LruState::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Look up the report for `input` repaired as `format`, marking it recently used
+    pub fn get(&self, format: &str, input: &str) -> Option<RepairReport> {
+        let key = (format.to_string(), input.to_string());
+        let mut state = self.lock();
+        let state = &mut *state;
+        state.tick += 1;
+
+        match state.entries.get_mut(&key) {
+            Some((report, last_used)) => {
+                state.recency.remove(last_used);
+                *last_used = state.tick;
+                state.recency.insert(state.tick, key);
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(report.clone())
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Store the report for `input` repaired as `format`, evicting the least recently used entry if full
+    pub fn insert(&self, format: &str, input: &str, report: RepairReport) {
+        if self.capacity == 0 {
+            return;
+        }
+        let key = (format.to_string(), input.to_string());
+        let mut state = self.lock();
+        state.tick += 1;
+        let tick = state.tick;
+
+        if let Some((_, last_used)) = state.entries.insert(key.clone(), (report, tick)) {
+            state.recency.remove(&last_used);
+        } else if state.entries.len() > self.capacity
+            && let Some((_, oldest)) = state.recency.pop_first()
+        {
+            state.entries.remove(&oldest);
+        }
+        state.recency.insert(tick, key);
+    }
+
+    /// Number of lookups answered from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups that found nothing
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Number of cached reports
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// True if nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(repaired: &str) -> RepairReport {
+        RepairReport {
+            repaired: repaired.to_string(),
+            applied_strategies: Vec::new(),
+            confidence_before: 0.0,
+            confidence_after: 1.0,
+            removed: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let cache = RepairCache::new(2);
+        cache.insert("json", "a", report("A"));
+        cache.insert("json", "b", report("B"));
+        // Touch "a" so "b" becomes the least recently used
+        assert!(cache.get("json", "a").is_some());
+        cache.insert("json", "c", report("C"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("json", "b").is_none());
+        assert_eq!(cache.get("json", "a").unwrap().repaired, "A");
+        assert_eq!(cache.get("json", "c").unwrap().repaired, "C");
+    }
+
+    #[test]
+    fn test_format_is_part_of_key() {
+        let cache = RepairCache::new(4);
+        cache.insert("json", "x", report("X"));
+        assert!(cache.get("yaml", "x").is_none());
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn test_zero_capacity_stores_nothing() {
+        let cache = RepairCache::new(0);
+        cache.insert("json", "x", report("X"));
+        assert!(cache.is_empty());
+    }
+}
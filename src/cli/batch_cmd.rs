@@ -2,64 +2,57 @@
 
 use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+/// A file to repair and where to write the result
+struct BatchJob {
+    input: PathBuf,
+    output: PathBuf,
+}
+
+/// Number of worker threads used when `--jobs` is not given
+fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
 
 pub fn handle_batch(
     input_dir: &str,
     output_dir: &str,
     pattern: Option<&str>,
     recursive: bool,
+    jobs: Option<usize>,
+    continue_on_error: bool,
     verbose: bool,
 ) -> io::Result<()> {
     let pattern = pattern.unwrap_or("*");
+    let jobs = jobs.unwrap_or_else(default_jobs).max(1);
 
     if verbose {
         eprintln!("Processing batch files from: {}", input_dir);
         eprintln!("Pattern: {}", pattern);
         eprintln!("Recursive: {}", recursive);
+        eprintln!("Jobs: {}", jobs);
     }
 
     // Create output directory if it doesn't exist
     fs::create_dir_all(output_dir)?;
 
-    let mut count = 0;
-
-    if recursive {
-        // Recursive processing
-        process_directory_recursive(input_dir, output_dir, pattern, verbose, &mut count)?;
-    } else {
-        // Single-level processing
-        let entries = fs::read_dir(input_dir)?;
-
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.is_file() {
-                let file_name = path.file_name()
-                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, 
-                        format!("Invalid file path: {}", path.display())))?
-                    .to_string_lossy();
-
-                // Simple pattern matching
-                if pattern == "*" || file_name.contains(pattern) {
-                    if verbose {
-                        eprintln!("Processing: {}", file_name);
-                    }
-
-                    let content = fs::read_to_string(&path)
-                        .map_err(|e| io::Error::other(format!("Failed to read {}: {}", path.display(), e)))?;
-                    let repaired = anyrepair::repair(&content)
-                        .map_err(|e| io::Error::other(format!("Failed to repair {}: {}", path.display(), e)))?;
+    let mut batch = Vec::new();
+    collect_jobs(
+        Path::new(input_dir),
+        Path::new(output_dir),
+        pattern,
+        recursive,
+        &mut batch,
+    )?;
 
-                    let output_path = Path::new(output_dir).join(&*file_name);
-                    fs::write(&output_path, repaired)
-                        .map_err(|e| io::Error::other(format!("Failed to write {}: {}", output_path.display(), e)))?;
+    let (count, mut failures) = run_jobs(&batch, jobs, continue_on_error, verbose);
 
-                    count += 1;
-                }
-            }
-        }
+    if !continue_on_error && !failures.is_empty() {
+        return Err(failures.swap_remove(0));
     }
 
     if verbose {
@@ -68,79 +61,131 @@ pub fn handle_batch(
 
     println!("Processed {} files", count);
 
+    if !failures.is_empty() {
+        eprintln!("Failed to process {} file(s):", failures.len());
+        for error in &failures {
+            eprintln!("  {}", error);
+        }
+        return Err(io::Error::other(format!(
+            "{} of {} files failed",
+            failures.len(),
+            batch.len()
+        )));
+    }
+
     Ok(())
 }
 
-fn process_directory_recursive(
-    input_dir: &str,
-    output_dir: &str,
+/// Gather the files under `input_dir` matching `pattern`, mirroring the
+/// directory structure under `output_dir` when `recursive`
+fn collect_jobs(
+    input_dir: &Path,
+    output_dir: &Path,
     pattern: &str,
-    verbose: bool,
-    count: &mut usize,
+    recursive: bool,
+    batch: &mut Vec<BatchJob>,
 ) -> io::Result<()> {
-    let entries = fs::read_dir(input_dir)?;
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
+    let mut entries = fs::read_dir(input_dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for path in entries {
+        let name = path.file_name().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid file path: {}", path.display()),
+            )
+        })?;
 
         if path.is_file() {
-            let file_name = path.file_name()
-                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, 
-                    format!("Invalid file path: {}", path.display())))?
-                .to_string_lossy();
-
-            if pattern == "*" || file_name.contains(pattern) {
-                if verbose {
-                    eprintln!("Processing: {}", path.display());
-                }
+            // Simple pattern matching
+            if pattern == "*" || name.to_string_lossy().contains(pattern) {
+                batch.push(BatchJob {
+                    output: output_dir.join(name),
+                    input: path,
+                });
+            }
+        } else if recursive && path.is_dir() {
+            collect_jobs(&path, &output_dir.join(name), pattern, recursive, batch)?;
+        }
+    }
 
-                let content = fs::read_to_string(&path)
-                    .map_err(|e| io::Error::other(format!("Failed to read {}: {}", path.display(), e)))?;
-                let repaired = anyrepair::repair(&content)
-                    .map_err(|e| io::Error::other(format!("Failed to repair {}: {}", path.display(), e)))?;
-
-                // Preserve directory structure in output
-                let relative_path = path
-                    .strip_prefix(input_dir)
-                    .map_err(|e| io::Error::other(format!("Failed to compute relative path for {}: {}", path.display(), e)))?;
-                let output_path = Path::new(output_dir).join(relative_path);
-
-                // Create parent directories if needed
-                if let Some(parent) = output_path.parent() {
-                    fs::create_dir_all(parent)
-                        .map_err(|e| io::Error::other(format!("Failed to create directory {}: {}", parent.display(), e)))?;
+    Ok(())
+}
+
+/// Repair `batch` on `jobs` worker threads.
+///
+/// Returns the number of files written and the failures in file order. Unless
+/// `continue_on_error` is set, workers stop picking up files after the first failure.
+fn run_jobs(
+    batch: &[BatchJob],
+    jobs: usize,
+    continue_on_error: bool,
+    verbose: bool,
+) -> (usize, Vec<io::Error>) {
+    let next = AtomicUsize::new(0);
+    let count = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let failures = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(batch.len()) {
+            scope.spawn(|| {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(job) = batch.get(index) else {
+                        break;
+                    };
+                    match process_file(job, verbose) {
+                        Ok(()) => {
+                            count.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(error) => {
+                            if !continue_on_error {
+                                stop.store(true, Ordering::Relaxed);
+                            }
+                            failures
+                                .lock()
+                                .unwrap_or_else(|e| e.into_inner())
+                                .push((index, error));
+                        }
+                    }
                 }
+            });
+        }
+    });
+
+    let mut failures = failures.into_inner().unwrap_or_else(|e| e.into_inner());
+    failures.sort_by_key(|(index, _)| *index);
+    (
+        count.into_inner(),
+        failures.into_iter().map(|(_, error)| error).collect(),
+    )
+}
 
-                fs::write(&output_path, repaired)
-                    .map_err(|e| io::Error::other(format!("Failed to write {}: {}", output_path.display(), e)))?;
+fn process_file(job: &BatchJob, verbose: bool) -> io::Result<()> {
+    let BatchJob { input, output } = job;
+    if verbose {
+        eprintln!("Processing: {}", input.display());
+    }
 
-                *count += 1;
-            }
-        } else if path.is_dir() {
-            // Recursively process subdirectories
-            let dir_name = path.file_name()
-                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, 
-                    format!("Invalid directory path: {}", path.display())))?
-                .to_string_lossy();
-            let new_output_dir = Path::new(output_dir).join(&*dir_name);
-            
-            let path_str = path.to_str()
-                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, 
-                    format!("Non-UTF8 path: {}", path.display())))?;
-            let output_str = new_output_dir.to_str()
-                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, 
-                    format!("Non-UTF8 output path: {}", new_output_dir.display())))?;
-            
-            process_directory_recursive(
-                path_str,
-                output_str,
-                pattern,
-                verbose,
-                count,
-            )?;
-        }
+    let content = fs::read_to_string(input)
+        .map_err(|e| io::Error::other(format!("Failed to read {}: {}", input.display(), e)))?;
+    let repaired = anyrepair::repair(&content)
+        .map_err(|e| io::Error::other(format!("Failed to repair {}: {}", input.display(), e)))?;
+
+    // Create parent directories if needed
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            io::Error::other(format!(
+                "Failed to create directory {}: {}",
+                parent.display(),
+                e
+            ))
+        })?;
     }
 
-    Ok(())
+    fs::write(output, repaired)
+        .map_err(|e| io::Error::other(format!("Failed to write {}: {}", output.display(), e)))
 }
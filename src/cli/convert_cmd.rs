@@ -0,0 +1,73 @@
+//! Convert command handler
+
+use std::io;
+
+/// Formats the conversion helpers can read and write
+const CONVERTIBLE_FORMATS: &[&str] = &["json", "yaml", "toml", "csv"];
+
+pub fn handle_convert(
+    from: &str,
+    to: &str,
+    input: Option<&str>,
+    output: Option<&str>,
+    verbose: bool,
+) -> io::Result<()> {
+    let content = super::read_input(input)?;
+
+    let from = if anyrepair::normalize_format(from) == "auto" {
+        let detected = anyrepair::detect_format(&content).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Could not detect the input format",
+            )
+        })?;
+        if verbose {
+            eprintln!("Detected format: {}", detected);
+        }
+        detected
+    } else {
+        anyrepair::normalize_format(from)
+    };
+    let to = anyrepair::normalize_format(to);
+    for format in [from, to] {
+        if !CONVERTIBLE_FORMATS.contains(&format) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Cannot convert {} (supported: {})",
+                    format,
+                    CONVERTIBLE_FORMATS.join(", ")
+                ),
+            ));
+        }
+    }
+
+    if verbose {
+        eprintln!("Converting {} to {}...", from, to);
+    }
+    let mut converted = convert(&content, from, to).map_err(|e| io::Error::other(e.to_string()))?;
+    if content.ends_with('\n') && !converted.ends_with('\n') {
+        converted.push('\n');
+    }
+    super::write_output(&converted, output)
+}
+
+/// Repair `content` as `from` and convert it to `to`, going through JSON
+/// when neither side is JSON
+fn convert(content: &str, from: &str, to: &str) -> anyrepair::Result<String> {
+    if from == to {
+        return anyrepair::repair_with_format(content, from);
+    }
+    let json = match from {
+        "yaml" => anyrepair::yaml_to_json(content)?,
+        "toml" => anyrepair::toml_to_json(content)?,
+        "csv" => anyrepair::csv_to_json(content)?,
+        _ => content.to_string(),
+    };
+    match to {
+        "yaml" => anyrepair::json_to_yaml(&json),
+        "toml" => anyrepair::json_to_toml(&json),
+        "csv" => anyrepair::json_to_csv(&json),
+        _ => anyrepair::jsonrepair(&json),
+    }
+}
@@ -7,9 +7,20 @@ pub mod validate_cmd;
 pub mod batch_cmd;
 pub mod stream_cmd;
 pub mod completions_cmd;
+pub mod rules_cmd;
+pub mod convert_cmd;
 
 use std::fs;
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
+
+/// Pick the format to repair with: `--format` wins, `--stdin-format` applies only to piped input
+pub fn effective_format(
+    format: Option<String>,
+    stdin_format: Option<String>,
+    input_path: Option<&str>,
+) -> Option<String> {
+    format.or(stdin_format.filter(|_| input_path.is_none()))
+}
 
 /// Read content from file or stdin
 pub fn read_input(file_path: Option<&str>) -> io::Result<String> {
@@ -28,8 +39,12 @@ pub fn write_output(content: &str, file_path: Option<&str>) -> io::Result<()> {
     match file_path {
         Some(path) => fs::write(path, content),
         None => {
-            print!("{}", content);
-            Ok(())
+            let mut stdout = io::stdout().lock();
+            match stdout.write_all(content.as_bytes()).and_then(|_| stdout.flush()) {
+                // The reader went away (e.g. `| head`); nothing left to write to
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+                result => result,
+            }
         }
     }
 }
@@ -1,6 +1,7 @@
 //! Repair command handler
 
 use std::io::{self, IsTerminal};
+use std::time::{Duration, Instant};
 
 /// ANSI color codes for terminal output.
 const RED: &str = "\x1b[31m";
@@ -21,6 +22,7 @@ fn should_use_color(color: &str) -> bool {
 /// Unified repair handler for all formats.
 /// When format is Some, uses that format directly via the registry.
 /// When format is None, uses auto-detection.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_repair(
     input: Option<&str>,
     output: Option<&str>,
@@ -32,9 +34,12 @@ pub fn handle_repair(
     json_output: bool,
     min_confidence: Option<f64>,
     explain: bool,
+    report: bool,
+    schema: Option<&str>,
     color: &str,
 ) -> io::Result<()> {
     let content = super::read_input(input)?;
+    let started = Instant::now();
 
     let (repaired, confidence, detected_format, explanations) = if let Some(fmt) = format {
         if verbose {
@@ -85,25 +90,47 @@ pub fn handle_repair(
     };
 
     if verbose {
-        eprintln!("Repair completed");
+        let elapsed = started.elapsed();
+        let report = anyrepair::repair_report(&content, detected_format)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        eprintln!("{}", format_summary(&report, detected_format, content != repaired, elapsed));
     }
 
-    if let Some(threshold) = min_confidence {
-        if confidence < threshold {
-            eprintln!(
-                "Confidence {:.2}% is below threshold {:.2}%",
-                confidence * 100.0,
-                threshold * 100.0
-            );
+    if let Some(threshold) = min_confidence
+        && confidence < threshold
+    {
+        eprintln!(
+            "Confidence {:.2}% is below threshold {:.2}%",
+            confidence * 100.0,
+            threshold * 100.0
+        );
+        return Err(io::Error::other(format!(
+            "Confidence {:.2}% below minimum threshold {:.2}%",
+            confidence * 100.0,
+            threshold * 100.0
+        )));
+    }
+
+    if let Some(schema_path) = schema {
+        if anyrepair::normalize_format(detected_format) != "json" {
             return Err(io::Error::new(
-                io::ErrorKind::Other,
-                format!(
-                    "Confidence {:.2}% below minimum threshold {:.2}%",
-                    confidence * 100.0,
-                    threshold * 100.0
-                ),
+                io::ErrorKind::InvalidInput,
+                format!("--schema requires JSON input, got {}", detected_format),
             ));
         }
+        let schema = std::fs::read_to_string(schema_path)?;
+        let violations = anyrepair::validate_schema(&repaired, &schema)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        if !violations.is_empty() {
+            eprintln!("Repaired output does not match {}:", schema_path);
+            for violation in &violations {
+                eprintln!("  - {}", violation);
+            }
+            return Err(io::Error::other(format!(
+                "{} schema violation(s)",
+                violations.len()
+            )));
+        }
     }
 
     let had_changes = content != repaired;
@@ -125,6 +152,19 @@ pub fn handle_repair(
         }
     }
 
+    if report {
+        let report = anyrepair::repair_report(&content, detected_format)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        eprint!("{}", format_report(&report, detected_format));
+        if report.degraded() {
+            if use_color {
+                eprintln!("{RED}Warning: repair lowered confidence{RESET}");
+            } else {
+                eprintln!("Warning: repair lowered confidence");
+            }
+        }
+    }
+
     if json_output {
         let json = build_json_result(
             detected_format,
@@ -175,6 +215,48 @@ fn repair_format(content: &str, format: &str) -> io::Result<(String, f64)> {
     Ok((repaired, confidence))
 }
 
+/// Render the one-line `--verbose` summary of a repair.
+fn format_summary(
+    report: &anyrepair::RepairReport,
+    format: &str,
+    changed: bool,
+    elapsed: Duration,
+) -> String {
+    let count = report.applied_strategies.len();
+    format!(
+        "Repair completed: format {}, changed {}, confidence {:.2}%, {} {} applied, {:?}",
+        format,
+        if changed { "yes" } else { "no" },
+        report.confidence_after * 100.0,
+        count,
+        if count == 1 { "strategy" } else { "strategies" },
+        elapsed
+    )
+}
+
+/// Render the human-readable `--report` summary.
+fn format_report(report: &anyrepair::RepairReport, format: &str) -> String {
+    let strategies = if report.applied_strategies.is_empty() {
+        "none".to_string()
+    } else {
+        report.applied_strategies.join(", ")
+    };
+    let mut text = format!(
+        "Repair report ({}):\n  Confidence before: {:.2}%\n  Confidence after:  {:.2}%\n  Applied strategies: {}\n",
+        format,
+        report.confidence_before * 100.0,
+        report.confidence_after * 100.0,
+        strategies
+    );
+    for span in &report.removed {
+        text.push_str(&format!(
+            "  Removed {} at bytes {}..{}: {:?}\n",
+            span.reason, span.byte_range.start, span.byte_range.end, span.text
+        ));
+    }
+    text
+}
+
 /// Build a machine-readable JSON result string for CI usage.
 fn build_json_result(
     format: &str,
@@ -333,6 +415,8 @@ mod tests {
             false,
             None,
             false,
+            false,
+            None,
             "never",
         );
         assert!(result.is_ok());
@@ -359,6 +443,8 @@ mod tests {
             false,
             None,
             false,
+            false,
+            None,
             "never",
         );
         assert!(result.is_ok());
@@ -383,6 +469,8 @@ mod tests {
             false,
             None,
             false,
+            false,
+            None,
             "never",
         );
         assert!(result.is_ok());
@@ -407,6 +495,8 @@ mod tests {
             true,
             None,
             false,
+            false,
+            None,
             "never",
         );
         assert!(result.is_ok());
@@ -431,6 +521,8 @@ mod tests {
             false,
             Some(0.0),
             false,
+            false,
+            None,
             "never",
         );
         assert!(result.is_ok());
@@ -455,12 +547,43 @@ mod tests {
             false,
             Some(2.0),
             false,
+            false,
+            None,
             "never",
         );
         assert!(result.is_err());
         let _ = std::fs::remove_file(&tmp);
     }
 
+    #[test]
+    fn test_format_report() {
+        let report = anyrepair::repair_report(r#"{"key": "value",}"#, "json").unwrap();
+        let text = format_report(&report, "json");
+        assert!(text.starts_with("Repair report (json):"));
+        assert!(text.contains("Confidence after:  100.00%"));
+        assert!(text.contains("FixTrailingCommas"));
+        assert!(!text.contains("Removed"));
+
+        let report = anyrepair::repair_report("[1, 2] ***", "json").unwrap();
+        let text = format_report(&report, "json");
+        assert!(text.contains("  Removed trailing content at bytes 7..10: \"***\"\n"));
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let report = anyrepair::repair_report(r#"{"key": "value",}"#, "json").unwrap();
+        let line = format_summary(&report, "json", true, Duration::from_millis(3));
+        assert_eq!(
+            line,
+            "Repair completed: format json, changed yes, confidence 100.00%, 1 strategy applied, 3ms"
+        );
+
+        let report = anyrepair::repair_report("a: 1", "yaml").unwrap();
+        let line = format_summary(&report, "yaml", false, Duration::from_millis(3));
+        assert!(line.contains("changed no"));
+        assert!(line.contains("0 strategies applied"));
+    }
+
     #[test]
     fn test_build_json_result_with_output() {
         let json = build_json_result("json", 0.95, true, 20, 18, Some("out.json"), "{}");
@@ -500,6 +623,8 @@ mod tests {
             false,
             None,
             true,
+            false,
+            None,
             "never",
         );
         assert!(result.is_ok());
@@ -524,6 +649,8 @@ mod tests {
             false,
             None,
             true,
+            false,
+            None,
             "never",
         );
         assert!(result.is_ok());
@@ -547,4 +674,39 @@ mod tests {
             anyrepair::repair_with_explanations(r#"{"key": "value"}"#, "json").unwrap();
         assert!(names.is_empty(), "no strategies should be applied to valid content");
     }
+
+    #[test]
+    fn test_schema_violation_fails_repair() {
+        let dir = std::env::temp_dir();
+        let schema = dir.join("anyrepair_schema_unit.schema.json");
+        std::fs::write(&schema, r#"{"type": "object", "required": ["name"]}"#).unwrap();
+        let schema_path = schema.to_str().unwrap();
+
+        let run = |input: &str, file: &str| {
+            let tmp = dir.join(file);
+            std::fs::write(&tmp, input).unwrap();
+            let result = handle_repair(
+                tmp.to_str(),
+                None,
+                false,
+                false,
+                Some("json"),
+                false,
+                true,
+                false,
+                None,
+                false,
+                false,
+                Some(schema_path),
+                "never",
+            );
+            let _ = std::fs::remove_file(&tmp);
+            result
+        };
+
+        assert!(run(r#"{"name": "a",}"#, "anyrepair_schema_ok.json").is_ok());
+        let err = run(r#"{"age": 1,}"#, "anyrepair_schema_bad.json").unwrap_err();
+        assert_eq!(err.to_string(), "1 schema violation(s)");
+        let _ = std::fs::remove_file(&schema);
+    }
 }
@@ -0,0 +1,67 @@
+//! Rules command handler: list the repair strategies of each format
+
+use anyrepair::StrategyInfo;
+use std::io;
+
+/// Without `name`, list the strategies of `format` (or every format) in run
+/// order; with `name`, show the details of that strategy.
+pub fn handle_rules(name: Option<&str>, format: Option<&str>) -> io::Result<()> {
+    let formats = match format {
+        Some(format) => vec![format],
+        None => anyrepair::SUPPORTED_FORMATS.to_vec(),
+    };
+
+    let mut found = false;
+    for format in formats {
+        let strategies = anyrepair::list_strategies(format)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+        let format = anyrepair::normalize_format(format);
+        match name {
+            None => {
+                println!("{}:", format);
+                for strategy in &strategies {
+                    println!(
+                        "  {:>3}  {:<32} {}",
+                        strategy.priority, strategy.name, strategy.description
+                    );
+                }
+            }
+            Some(name) => {
+                for strategy in strategies
+                    .iter()
+                    .filter(|s| s.name.eq_ignore_ascii_case(name))
+                {
+                    print_strategy(format, strategy);
+                    found = true;
+                }
+            }
+        }
+    }
+
+    match name {
+        Some(name) if !found => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Unknown strategy: {}", name),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn print_strategy(format: &str, strategy: &StrategyInfo) {
+    println!("{}", strategy.name);
+    println!("  Format:      {}", format);
+    println!("  Priority:    {}", strategy.priority);
+    println!("  Description: {}", strategy.description);
+    if let Some((input, output)) = &strategy.example {
+        println!("  Example input:");
+        print_indented(input);
+        println!("  Example output:");
+        print_indented(output);
+    }
+}
+
+fn print_indented(text: &str) {
+    for line in text.lines() {
+        println!("    {}", line);
+    }
+}
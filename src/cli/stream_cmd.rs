@@ -31,7 +31,18 @@ pub fn handle_stream(
     let buf_reader = BufReader::new(reader);
     let processor = StreamingRepair::with_buffer_size(buffer_size);
     
-    match processor.process(buf_reader, &mut writer, format) {
+    let result = if anyrepair::normalize_format(format) == "auto" {
+        let (detected, buf_reader) = StreamingRepair::detect_format(buf_reader)
+            .map_err(|e| io::Error::other(format!("Streaming repair failed: {}", e)))?;
+        if verbose {
+            eprintln!("Detected format: {}", detected.unwrap_or("unknown"));
+        }
+        processor.process(buf_reader, &mut writer, detected.unwrap_or("auto"))
+    } else {
+        processor.process(buf_reader, &mut writer, format)
+    };
+    
+    match result {
         Ok(bytes) => {
             if verbose {
                 eprintln!("Processed {} bytes", bytes);
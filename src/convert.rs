@@ -0,0 +1,1682 @@
+//! Conversion between JSON and YAML, CSV or TOML
+//!
+//! Every conversion repairs its input first (YAML and TOML only if they do not
+//! parse as is), then converts through a small in-crate document model (no
+//! serde). The YAML side covers the block and flow styles produced by config
+//! files and LLMs: mappings, sequences, plain and quoted scalars, and `|`/`>`
+//! block scalars. Anchors, aliases and tags are
+//! rejected with `RepairError::Conversion`, and multi-document streams are not
+//! supported. YAML `.inf` becomes `1e999`, which JSON parsers read as infinity.
+//! CSV maps to a JSON array of objects keyed by the header row. TOML
+//! date-times, which JSON lacks, become ISO 8601 strings.
+
+use crate::csv::{format_csv_line, parse_csv_fields};
+use crate::error::{RepairError, Result};
+use crate::json_util::{MAX_PARSE_DEPTH, is_valid_json, json_string};
+use crate::repairer_base::DEFAULT_MAX_DEPTH;
+use crate::traits::Repair;
+
+/// Repair `s` as JSON and convert it to block-style YAML
+pub fn json_to_yaml(s: &str) -> Result<String> {
+    let repaired = crate::jsonrepair(s)?;
+    let value = JsonParser::new(&repaired).parse()?;
+    let mut out = String::new();
+    write_yaml(&value, 0, &mut out);
+    Ok(out.trim_end().to_string())
+}
+
+/// Convert YAML `s` to JSON, repairing it first if it does not parse as is
+pub fn yaml_to_json(s: &str) -> Result<String> {
+    let value = match YamlParser::new(s).parse() {
+        Ok(value) => value,
+        // Repair would only hide the unsupported syntax
+        Err(RepairError::Conversion(message)) if message.starts_with(UNSUPPORTED_YAML) => {
+            return Err(RepairError::Conversion(message));
+        }
+        Err(_) => {
+            let repaired = crate::yaml::YamlRepairer::new().repair(s)?;
+            YamlParser::new(&repaired).parse()?
+        }
+    };
+    let mut out = String::new();
+    write_json(&value, &mut out);
+    Ok(out)
+}
+
+/// Repair `s` as CSV and convert it to a JSON array with one object per row.
+///
+/// The first row supplies the keys unless it contains numbers, in which case it
+/// is treated as data. Blank or missing header cells are named `column_N`.
+/// Numbers and `true`/`false` become JSON numbers and booleans, and cells
+/// missing from short rows become `null`.
+pub fn csv_to_json(s: &str) -> Result<String> {
+    let repaired = crate::csv::CsvRepairer::new().repair(s)?;
+    let mut rows = crate::csv::csv_records(&repaired, None)
+        .into_iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            parse_csv_fields(line.trim())
+                .map_err(|_| conversion_error(format!("unterminated quote in CSV row: {}", line)))
+        });
+
+    let first = match rows.next() {
+        Some(first) => first?,
+        None => return Ok("[]".to_string()),
+    };
+    // A first row containing numbers is data, not a header
+    let (mut headers, mut records) = if first
+        .iter()
+        .any(|cell| matches!(csv_cell_value(cell.clone()), Value::Number(_)))
+    {
+        (Vec::new(), vec![first])
+    } else {
+        (first, Vec::new())
+    };
+    for row in rows {
+        records.push(row?);
+    }
+    let width = records.iter().map(Vec::len).max().unwrap_or(0);
+    for i in headers.len()..width {
+        headers.push(format!("column_{}", i + 1));
+    }
+    for (i, header) in headers.iter_mut().enumerate() {
+        if header.trim().is_empty() {
+            *header = format!("column_{}", i + 1);
+        }
+    }
+
+    let objects = records
+        .into_iter()
+        .map(|row| {
+            let mut cells = row.into_iter();
+            Value::Object(
+                headers
+                    .iter()
+                    .map(|key| {
+                        (
+                            key.clone(),
+                            cells.next().map_or(Value::Null, csv_cell_value),
+                        )
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+
+    let mut out = String::new();
+    write_json(&Value::Array(objects), &mut out);
+    Ok(out)
+}
+
+/// Repair `s` as JSON and flatten an array of objects (or a single object) to CSV.
+///
+/// The header is the union of all keys in first-seen order. `null` and missing
+/// keys become empty cells; nested arrays and objects are written as JSON text.
+/// Returns `RepairError::Conversion` if the JSON is not an object or an array of objects.
+pub fn json_to_csv(s: &str) -> Result<String> {
+    let repaired = crate::jsonrepair(s)?;
+    let rows = match JsonParser::new(&repaired).parse()? {
+        Value::Array(items) => items,
+        object @ Value::Object(_) => vec![object],
+        _ => {
+            return Err(conversion_error(
+                "expected a JSON object or array of objects",
+            ));
+        }
+    };
+
+    let mut headers: Vec<String> = Vec::new();
+    let mut objects = Vec::with_capacity(rows.len());
+    for row in rows {
+        let Value::Object(entries) = row else {
+            return Err(conversion_error(
+                "expected every array element to be an object",
+            ));
+        };
+        for (key, _) in &entries {
+            if !headers.contains(key) {
+                headers.push(key.clone());
+            }
+        }
+        objects.push(entries);
+    }
+
+    let mut lines = vec![format_csv_line(&headers)];
+    for entries in objects {
+        let cells: Vec<String> = headers
+            .iter()
+            .map(|key| {
+                entries
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map_or_else(String::new, |(_, value)| csv_cell_text(value))
+            })
+            .collect();
+        lines.push(format_csv_line(&cells));
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Convert TOML `s` to a JSON object, repairing it first if it does not parse as is.
+///
+/// Tables and arrays of tables become nested objects and arrays. Date-times
+/// and `inf`/`nan` have no JSON equivalent and are kept as strings.
+pub fn toml_to_json(s: &str) -> Result<String> {
+    let value = match TomlParser::new(s).parse() {
+        Ok(value) => value,
+        Err(_) => {
+            let repaired = crate::toml::TomlRepairer::new().repair(s)?;
+            TomlParser::new(&repaired).parse()?
+        }
+    };
+    let mut out = String::new();
+    write_json(&value, &mut out);
+    Ok(out)
+}
+
+/// Repair `s` as a JSON array of rows (`[[1, 2], [3, 4,]]`) and pad every row
+/// with `null` to the length of the longest, giving a rectangular table.
+/// Returns `RepairError::Conversion` if the JSON is not an array of arrays.
+pub fn repair_table_json(s: &str) -> Result<Vec<Vec<Value>>> {
+    let repaired = crate::jsonrepair(s)?;
+    let Value::Array(rows) = parse_json(&repaired)? else {
+        return Err(conversion_error("expected an array of rows"));
+    };
+    let mut table = rows
+        .into_iter()
+        .map(|row| match row {
+            Value::Array(cells) => Ok(cells),
+            _ => Err(conversion_error("expected every row to be an array")),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let width = table.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut table {
+        row.resize(width, Value::Null);
+    }
+    Ok(table)
+}
+
+/// Repair `s` as JSON and convert it to TOML.
+///
+/// Nested objects become `[tables]` and arrays of objects become
+/// `[[arrays of tables]]`. TOML has no null, so `null` members are omitted.
+/// Returns `RepairError::Conversion` if the JSON is not an object or an array
+/// contains `null`.
+pub fn json_to_toml(s: &str) -> Result<String> {
+    let repaired = crate::jsonrepair(s)?;
+    let Value::Object(entries) = JsonParser::new(&repaired).parse()? else {
+        return Err(conversion_error("TOML documents must be JSON objects"));
+    };
+    let mut out = String::new();
+    write_toml_table(&entries, &mut Vec::new(), &mut out)?;
+    Ok(out.trim().to_string())
+}
+
+fn csv_cell_value(cell: String) -> Value {
+    match parse_scalar(&cell) {
+        Ok(value @ (Value::Number(_) | Value::Bool(_))) if cell == cell.trim() => value,
+        _ => Value::String(cell),
+    }
+}
+
+fn csv_cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => {
+            let mut out = String::new();
+            write_json(other, &mut out);
+            out
+        }
+    }
+}
+
+/// Document model shared by all formats. Object keys keep their input order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// Number in its JSON spelling
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+/// Parse `s`, which must already be valid JSON, into a [`Value`]
+pub(crate) fn parse_json(s: &str) -> Result<Value> {
+    JsonParser::new(s).parse()
+}
+
+/// Parse `s`, which must already be valid TOML, into a [`Value`]
+pub(crate) fn parse_toml(s: &str) -> Result<Value> {
+    TomlParser::new(s).parse()
+}
+
+fn conversion_error(message: impl Into<String>) -> RepairError {
+    RepairError::Conversion(message.into())
+}
+
+/// Deepest nesting the recursive YAML and TOML parsers below will descend
+/// into; the repairers that run first reject deeper input by default anyway
+const MAX_RECURSION_DEPTH: usize = DEFAULT_MAX_DEPTH;
+
+/// Fail with `RepairError::MaxDepthExceeded` once `depth` passes `limit`,
+/// rather than build values too deep to walk or let input overflow the stack
+fn check_depth(depth: usize, limit: usize) -> Result<()> {
+    if depth > limit {
+        return Err(RepairError::MaxDepthExceeded { depth, limit });
+    }
+    Ok(())
+}
+
+// ============================================================================
+// JSON
+// ============================================================================
+
+/// Parser for (already repaired) JSON. Open arrays and objects are kept on an
+/// explicit stack, so nesting depth is bounded by [`MAX_PARSE_DEPTH`] rather
+/// than by the thread's stack size.
+struct JsonParser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+/// An array or object whose members are still being parsed
+enum Open {
+    Array(Vec<Value>),
+    /// Members so far and the key of the member being parsed
+    Object(Vec<(String, Value)>, String),
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn parse(mut self) -> Result<Value> {
+        let value = self.value()?;
+        self.skip_ws();
+        if self.pos != self.src.len() {
+            return Err(conversion_error(format!(
+                "trailing characters at byte {}",
+                self.pos
+            )));
+        }
+        Ok(value)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.rest().starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(conversion_error(format!(
+                "expected '{}' at byte {}",
+                c, self.pos
+            )))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        let mut stack: Vec<Open> = Vec::new();
+        loop {
+            check_depth(stack.len() + 1, MAX_PARSE_DEPTH)?;
+            let mut value = if self.eat('[') {
+                if !self.eat(']') {
+                    stack.push(Open::Array(Vec::new()));
+                    continue;
+                }
+                Value::Array(Vec::new())
+            } else if self.eat('{') {
+                if !self.eat('}') {
+                    stack.push(Open::Object(Vec::new(), self.key()?));
+                    continue;
+                }
+                Value::Object(Vec::new())
+            } else {
+                self.scalar()?
+            };
+
+            // Hand the finished value to its parent, closing every collection
+            // that ends right after it
+            loop {
+                let Some(open) = stack.last_mut() else {
+                    return Ok(value);
+                };
+                let closed = match open {
+                    Open::Array(items) => {
+                        items.push(value);
+                        self.eat(']')
+                    }
+                    Open::Object(entries, key) => {
+                        entries.push((std::mem::take(key), value));
+                        self.eat('}')
+                    }
+                };
+                if !closed {
+                    self.expect(',')?;
+                    if let Open::Object(_, key) = open {
+                        *key = self.key()?;
+                    }
+                    break;
+                }
+                value = match stack.pop() {
+                    Some(Open::Array(items)) => Value::Array(items),
+                    Some(Open::Object(entries, _)) => Value::Object(entries),
+                    None => unreachable!("stack checked non-empty above"),
+                };
+            }
+        }
+    }
+
+    fn scalar(&mut self) -> Result<Value> {
+        self.skip_ws();
+        let rest = self.rest();
+        for (word, value) in [
+            ("null", Value::Null),
+            ("true", Value::Bool(true)),
+            ("false", Value::Bool(false)),
+        ] {
+            if rest.starts_with(word) {
+                self.pos += word.len();
+                return Ok(value);
+            }
+        }
+
+        match rest.chars().next() {
+            Some('"') => self.string().map(Value::String),
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let len = rest
+                    .find(|c: char| {
+                        !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+                    })
+                    .unwrap_or(rest.len());
+                self.pos += len;
+                Ok(Value::Number(rest[..len].to_string()))
+            }
+            _ => Err(conversion_error(format!(
+                "unexpected token at byte {}",
+                self.pos
+            ))),
+        }
+    }
+
+    /// Parse an object key and the `:` after it
+    fn key(&mut self) -> Result<String> {
+        self.skip_ws();
+        let key = self.string()?;
+        self.expect(':')?;
+        Ok(key)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let (value, len) = unescape_json_string(self.rest())
+            .ok_or_else(|| conversion_error(format!("invalid string at byte {}", self.pos)))?;
+        self.pos += len;
+        Ok(value)
+    }
+}
+
+/// Decode the double-quoted string at the start of `s`.
+/// Returns the decoded text and the number of bytes consumed, quotes included.
+fn unescape_json_string(s: &str) -> Option<(String, usize)> {
+    let mut chars = s.char_indices();
+    if chars.next()?.1 != '"' {
+        return None;
+    }
+    let mut out = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((out, i + 1)),
+            '\\' => {
+                let (_, escaped) = chars.next()?;
+                match escaped {
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'r' => out.push('\r'),
+                    'b' => out.push('\u{8}'),
+                    'f' => out.push('\u{c}'),
+                    'u' => {
+                        let hex: String = (0..4)
+                            .filter_map(|_| chars.next().map(|(_, c)| c))
+                            .collect();
+                        let unit = u32::from_str_radix(&hex, 16).ok()?;
+                        out.push(char::from_u32(unit).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    other => out.push(other),
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    None
+}
+
+pub(crate) fn write_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(n),
+        Value::String(s) => out.push_str(&json_string(s)),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_json(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(entries) => {
+            out.push('{');
+            for (i, (key, item)) in entries.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&json_string(key));
+                out.push_str(": ");
+                write_json(item, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+// ============================================================================
+// YAML
+// ============================================================================
+
+/// Line-based parser for block-style YAML with inline flow collections
+struct YamlParser {
+    /// `(indent, text)` of every line, text with the indentation removed
+    lines: Vec<(usize, String)>,
+    pos: usize,
+    depth: usize,
+}
+
+impl YamlParser {
+    fn new(src: &str) -> Self {
+        let lines = src
+            .lines()
+            .map(|line| {
+                let text = line.trim_start();
+                (line.len() - text.len(), text.trim_end().to_string())
+            })
+            .collect();
+        Self {
+            lines,
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    fn parse(mut self) -> Result<Value> {
+        self.skip_ignorable();
+        if self.pos >= self.lines.len() {
+            return Ok(Value::Null);
+        }
+        let indent = self.lines[self.pos].0;
+        let value = self.node(indent)?;
+        self.skip_ignorable();
+        match self.lines.get(self.pos) {
+            Some((_, text)) => Err(conversion_error(format!(
+                "unexpected content at line {}: {}",
+                self.pos + 1,
+                text
+            ))),
+            None => Ok(value),
+        }
+    }
+
+    /// Skip blank lines, comments and document markers
+    fn skip_ignorable(&mut self) {
+        while let Some((_, text)) = self.lines.get(self.pos) {
+            if text.is_empty() || text.starts_with('#') || text == "---" || text == "..." {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Indentation of the next meaningful line, if any
+    fn peek_indent(&mut self) -> Option<usize> {
+        self.skip_ignorable();
+        self.lines.get(self.pos).map(|(indent, _)| *indent)
+    }
+
+    fn is_sequence_item(text: &str) -> bool {
+        text == "-" || text.starts_with("- ")
+    }
+
+    fn node(&mut self, indent: usize) -> Result<Value> {
+        self.depth += 1;
+        let value =
+            check_depth(self.depth, MAX_RECURSION_DEPTH).and_then(|()| self.node_at_depth(indent));
+        self.depth -= 1;
+        value
+    }
+
+    fn node_at_depth(&mut self, indent: usize) -> Result<Value> {
+        let text = &self.lines[self.pos].1;
+        if Self::is_sequence_item(text) {
+            self.sequence(indent)
+        } else if split_mapping_entry(text).is_some() {
+            self.mapping(indent)
+        } else {
+            let value = parse_inline(strip_comment(text), self.depth)?;
+            self.pos += 1;
+            Ok(value)
+        }
+    }
+
+    fn sequence(&mut self, indent: usize) -> Result<Value> {
+        let mut items = Vec::new();
+        while self.peek_indent() == Some(indent) && Self::is_sequence_item(&self.lines[self.pos].1)
+        {
+            let rest = self.lines[self.pos].1[1..].trim_start().to_string();
+            if rest.is_empty() {
+                self.pos += 1;
+                items.push(self.nested(indent)?);
+            } else {
+                // Re-read the item body as a line of its own, indented past the dash
+                let offset = self.lines[self.pos].1.len() - rest.len();
+                self.lines[self.pos] = (indent + offset, rest);
+                items.push(self.node(indent + offset)?);
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn mapping(&mut self, indent: usize) -> Result<Value> {
+        let mut entries = Vec::new();
+        while self.peek_indent() == Some(indent) {
+            let text = self.lines[self.pos].1.clone();
+            let Some((key, value)) = split_mapping_entry(&text) else {
+                break;
+            };
+            let key = match parse_scalar(key)? {
+                Value::String(s) => s,
+                Value::Null => "null".to_string(),
+                other => {
+                    let mut s = String::new();
+                    write_json(&other, &mut s);
+                    s
+                }
+            };
+            let value = strip_comment(value);
+            self.pos += 1;
+
+            let value = if value.is_empty() {
+                // A sequence may sit at the same indentation as its key
+                match self.peek_indent() {
+                    Some(i) if i == indent && Self::is_sequence_item(&self.lines[self.pos].1) => {
+                        self.sequence(indent)?
+                    }
+                    _ => self.nested(indent)?,
+                }
+            } else if value.starts_with('|') || value.starts_with('>') {
+                self.block_scalar(indent, value)
+            } else {
+                parse_inline(value, self.depth)?
+            };
+            entries.push((key, value));
+        }
+        Ok(Value::Object(entries))
+    }
+
+    /// The node indented under a parent at `indent`, or null if there is none
+    fn nested(&mut self, indent: usize) -> Result<Value> {
+        match self.peek_indent() {
+            Some(child) if child > indent => self.node(child),
+            _ => Ok(Value::Null),
+        }
+    }
+
+    /// Collect a `|` (literal) or `>` (folded) block scalar under a key at `indent`
+    fn block_scalar(&mut self, indent: usize, header: &str) -> Value {
+        let mut body: Vec<&str> = Vec::new();
+        let mut block_indent = None;
+        while let Some((line_indent, text)) = self.lines.get(self.pos) {
+            if !text.is_empty() && *line_indent <= indent {
+                break;
+            }
+            if !text.is_empty() {
+                block_indent.get_or_insert(*line_indent);
+            }
+            body.push(text);
+            self.pos += 1;
+        }
+        let extra = |i: usize| {
+            let (line_indent, _) = self.lines[i];
+            " ".repeat(line_indent.saturating_sub(block_indent.unwrap_or(line_indent)))
+        };
+        let start = self.pos - body.len();
+        let lines: Vec<String> = body
+            .iter()
+            .enumerate()
+            .map(|(i, text)| format!("{}{}", extra(start + i), text))
+            .collect();
+        let lines: Vec<&str> = {
+            let end = lines
+                .iter()
+                .rposition(|l| !l.is_empty())
+                .map_or(0, |i| i + 1);
+            lines[..end].iter().map(String::as_str).collect()
+        };
+
+        let mut text = if header.starts_with('>') {
+            lines
+                .split(|l| l.is_empty())
+                .map(|paragraph| paragraph.join(" "))
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else {
+            lines.join("\n")
+        };
+        if !header.contains('-') && !text.is_empty() {
+            text.push('\n');
+        }
+        Value::String(text)
+    }
+}
+
+/// Split `key: value` at the first `:` outside quotes that ends the line or is
+/// followed by whitespace
+fn split_mapping_entry(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with(['[', '{', '#']) {
+        return None;
+    }
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') if i == 0 => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, ':') => {
+                let after = &text[i + 1..];
+                if after.is_empty() || after.starts_with([' ', '\t']) {
+                    return Some((text[..i].trim_end(), after.trim_start()));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Drop a trailing ` # comment` that is outside quotes
+fn strip_comment(text: &str) -> &str {
+    let mut quote = None;
+    let mut prev = ' ';
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '#') if prev.is_whitespace() => return text[..i].trim_end(),
+            _ => {}
+        }
+        prev = c;
+    }
+    text
+}
+
+/// Parse a value written on one line: a flow collection or a scalar
+fn parse_inline(text: &str, depth: usize) -> Result<Value> {
+    if text.starts_with(['[', '{']) {
+        let mut flow = FlowParser {
+            src: text,
+            pos: 0,
+            depth,
+        };
+        let value = flow.value()?;
+        if !flow.src[flow.pos..].trim().is_empty() {
+            return Err(conversion_error(format!(
+                "unexpected content after {}",
+                text
+            )));
+        }
+        Ok(value)
+    } else {
+        parse_scalar(text)
+    }
+}
+
+/// Start of the error for YAML node properties the converter cannot represent
+const UNSUPPORTED_YAML: &str = "YAML anchors, aliases and tags are not supported";
+
+/// Interpret a scalar: quoted string, null, boolean, number or plain string.
+/// Anchors (`&a`), aliases (`*a`) and tags (`!t`) are rejected.
+fn parse_scalar(text: &str) -> Result<Value> {
+    let text = text.trim();
+    if text.starts_with('"') {
+        return match unescape_json_string(text) {
+            Some((s, len)) if len == text.len() => Ok(Value::String(s)),
+            _ => Err(conversion_error(format!("invalid quoted string: {}", text))),
+        };
+    }
+    if let Some(inner) = text.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')) {
+        return Ok(Value::String(inner.replace("''", "'")));
+    }
+    if text.starts_with(['&', '*', '!']) {
+        return Err(conversion_error(format!("{}: {}", UNSUPPORTED_YAML, text)));
+    }
+    Ok(match text {
+        "" | "~" | "null" | "Null" | "NULL" => Value::Null,
+        "true" | "True" | "TRUE" => Value::Bool(true),
+        "false" | "False" | "FALSE" => Value::Bool(false),
+        _ => yaml_number(text).map_or_else(|| Value::String(text.to_string()), Value::Number),
+    })
+}
+
+/// JSON form of a YAML number: `+1`, `.5`, `1.`, `1e3`, `0x1F`, `0o17` and `.inf`.
+/// Integers with a leading zero (`012`) stay strings, as YAML versions disagree on them.
+fn yaml_number(text: &str) -> Option<String> {
+    let (sign, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text.strip_prefix('+').unwrap_or(text)),
+    };
+    if matches!(unsigned, ".inf" | ".Inf" | ".INF") {
+        return Some(format!("{}1e999", sign));
+    }
+    if sign.is_empty() {
+        let radix = match text.get(..2) {
+            Some("0x") => Some(16),
+            Some("0o") => Some(8),
+            _ => None,
+        };
+        if let Some(radix) = radix {
+            return u64::from_str_radix(&text[2..], radix)
+                .ok()
+                .map(|n| n.to_string());
+        }
+    }
+
+    let (mantissa, exponent) =
+        unsigned.split_at(unsigned.find(['e', 'E']).unwrap_or(unsigned.len()));
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if int.is_empty() && frac.is_empty() || int.len() > 1 && int.starts_with('0') {
+        return None;
+    }
+    let int = if int.is_empty() { "0" } else { int };
+    let frac = if frac.is_empty() {
+        String::new()
+    } else {
+        format!(".{}", frac)
+    };
+    let number = format!("{}{}{}{}", sign, int, frac, exponent);
+    is_valid_json(&number).then_some(number)
+}
+
+/// Parser for YAML flow collections (`[a, b]`, `{k: v}`)
+struct FlowParser<'a> {
+    src: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl FlowParser<'_> {
+    fn skip_ws(&mut self) {
+        let rest = &self.src[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.src[self.pos..].starts_with(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        self.depth += 1;
+        let value =
+            check_depth(self.depth, MAX_RECURSION_DEPTH).and_then(|()| self.value_at_depth());
+        self.depth -= 1;
+        value
+    }
+
+    fn value_at_depth(&mut self) -> Result<Value> {
+        self.skip_ws();
+        if self.eat('[') {
+            let mut items = Vec::new();
+            while !self.eat(']') {
+                items.push(self.value()?);
+                if !self.eat(',') && !self.src[self.pos..].trim_start().starts_with(']') {
+                    return Err(conversion_error(format!(
+                        "unterminated flow sequence: {}",
+                        self.src
+                    )));
+                }
+            }
+            return Ok(Value::Array(items));
+        }
+        if self.eat('{') {
+            let mut entries = Vec::new();
+            while !self.eat('}') {
+                let key = match self.scalar(&[':', ',', '}'])? {
+                    Value::String(s) => s,
+                    other => {
+                        let mut s = String::new();
+                        write_json(&other, &mut s);
+                        s
+                    }
+                };
+                let value = if self.eat(':') {
+                    self.value()?
+                } else {
+                    Value::Null
+                };
+                entries.push((key, value));
+                if !self.eat(',') && !self.src[self.pos..].trim_start().starts_with('}') {
+                    return Err(conversion_error(format!(
+                        "unterminated flow mapping: {}",
+                        self.src
+                    )));
+                }
+            }
+            return Ok(Value::Object(entries));
+        }
+        self.scalar(&[',', ']', '}'])
+    }
+
+    /// Read a scalar ending at one of `terminators` (or a closing quote)
+    fn scalar(&mut self, terminators: &[char]) -> Result<Value> {
+        self.skip_ws();
+        let rest = &self.src[self.pos..];
+        let len = match rest.chars().next() {
+            Some(q @ ('"' | '\'')) => {
+                let mut escaped = false;
+                rest.char_indices()
+                    .skip(1)
+                    .find(|&(_, c)| {
+                        let close = c == q && !escaped;
+                        escaped = q == '"' && c == '\\' && !escaped;
+                        close
+                    })
+                    .map(|(i, _)| i + 1)
+                    .ok_or_else(|| conversion_error(format!("unterminated string: {}", rest)))?
+            }
+            _ => rest.find(terminators).unwrap_or(rest.len()),
+        };
+        self.pos += len;
+        parse_scalar(&rest[..len])
+    }
+}
+
+/// Whether a string must be quoted to survive as a YAML plain scalar
+fn needs_quotes(s: &str) -> bool {
+    s.is_empty()
+        || s != s.trim()
+        || !matches!(parse_scalar(s), Ok(Value::String(ref parsed)) if parsed == s)
+        || s.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%',
+            '@', '`',
+        ])
+        || s.contains(": ")
+        || s.contains(" #")
+        || s.ends_with(':')
+        || s.contains(|c: char| c.is_control())
+}
+
+fn yaml_scalar(s: &str) -> String {
+    if needs_quotes(s) {
+        json_string(s)
+    } else {
+        s.to_string()
+    }
+}
+
+/// Append `value` as YAML. Collections start on a new line indented by `indent`;
+/// the caller has already written any `key:` or `- ` prefix.
+fn write_yaml(value: &Value, indent: usize, out: &mut String) {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Array(items) if !items.is_empty() => {
+            for item in items {
+                out.push_str(&pad);
+                out.push('-');
+                write_yaml_child(item, indent, out);
+            }
+        }
+        Value::Object(entries) if !entries.is_empty() => {
+            for (key, item) in entries {
+                out.push_str(&pad);
+                out.push_str(&yaml_scalar(key));
+                out.push(':');
+                write_yaml_child(item, indent, out);
+            }
+        }
+        _ => {
+            out.push_str(&pad);
+            write_yaml_inline(value, out);
+            out.push('\n');
+        }
+    }
+}
+
+/// Write the value following a `key:` or `-` prefix at `indent`
+fn write_yaml_child(value: &Value, indent: usize, out: &mut String) {
+    match value {
+        Value::Array(items) if !items.is_empty() => {
+            out.push('\n');
+            write_yaml(value, indent + 2, out);
+        }
+        Value::Object(entries) if !entries.is_empty() => {
+            out.push('\n');
+            write_yaml(value, indent + 2, out);
+        }
+        _ => {
+            out.push(' ');
+            write_yaml_inline(value, out);
+            out.push('\n');
+        }
+    }
+}
+
+/// Write a scalar or empty collection on the current line
+fn write_yaml_inline(value: &Value, out: &mut String) {
+    match value {
+        Value::String(s) => out.push_str(&yaml_scalar(s)),
+        Value::Array(_) => out.push_str("[]"),
+        Value::Object(_) => out.push_str("{}"),
+        other => write_json(other, out),
+    }
+}
+
+// ============================================================================
+// TOML
+// ============================================================================
+
+/// Parser for TOML documents, including multi-line arrays and strings
+struct TomlParser<'a> {
+    src: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> TomlParser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> RepairError {
+        let line = self.src[..self.pos].matches('\n').count() + 1;
+        conversion_error(format!("{} on line {}", message, line))
+    }
+
+    /// Skip spaces, tabs and a trailing comment; with `multiline`, also line breaks
+    fn skip_ws(&mut self, multiline: bool) {
+        loop {
+            let rest = self.rest();
+            let trimmed = if multiline {
+                rest.trim_start()
+            } else {
+                rest.trim_start_matches([' ', '\t'])
+            };
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with('#') {
+                self.pos += trimmed.find(['\r', '\n']).unwrap_or(trimmed.len());
+            }
+            if !multiline || !self.rest().starts_with(char::is_whitespace) {
+                return;
+            }
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse(mut self) -> Result<Value> {
+        let mut root = Value::Object(Vec::new());
+        let mut table: Vec<String> = Vec::new();
+
+        loop {
+            self.skip_ws(true);
+            if self.rest().is_empty() {
+                return Ok(root);
+            }
+
+            if self.eat("[[") {
+                table = self.key_path("]]")?;
+                let (last, parents) = table.split_last().expect("key paths are non-empty");
+                match member(table_at(&mut root, parents)?, last, || {
+                    Value::Array(Vec::new())
+                }) {
+                    Value::Array(items) => items.push(Value::Object(Vec::new())),
+                    _ => return Err(self.error(&format!("'{}' is not an array of tables", last))),
+                }
+            } else if self.eat("[") {
+                table = self.key_path("]")?;
+                table_at(&mut root, &table)?;
+            } else {
+                let path = self.key_path("=")?;
+                let value = self.value()?;
+                insert(table_at(&mut root, &table)?, &path, value)?;
+            }
+
+            self.skip_ws(false);
+            if !(self.rest().is_empty() || self.eat("\n") || self.eat("\r\n")) {
+                return Err(self.error("expected end of line"));
+            }
+        }
+    }
+
+    /// Read a dotted key (`a."b c".d`) terminated by `end`
+    fn key_path(&mut self, end: &str) -> Result<Vec<String>> {
+        let mut path = Vec::new();
+        loop {
+            self.skip_ws(false);
+            let key = if self.rest().starts_with(['"', '\'']) {
+                self.string()?
+            } else {
+                let rest = self.rest();
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                    .unwrap_or(rest.len());
+                if len == 0 {
+                    return Err(self.error("expected a key"));
+                }
+                self.pos += len;
+                rest[..len].to_string()
+            };
+            path.push(key);
+            self.skip_ws(false);
+            if self.eat(end) {
+                return Ok(path);
+            }
+            if !self.eat(".") {
+                return Err(self.error(&format!("expected '.' or '{}' after key", end)));
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        self.depth += 1;
+        let value =
+            check_depth(self.depth, MAX_RECURSION_DEPTH).and_then(|()| self.value_at_depth());
+        self.depth -= 1;
+        value
+    }
+
+    fn value_at_depth(&mut self) -> Result<Value> {
+        self.skip_ws(false);
+        match self.rest().chars().next() {
+            Some('"' | '\'') => self.string().map(Value::String),
+            Some('[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_ws(true);
+                    if self.eat("]") {
+                        return Ok(Value::Array(items));
+                    }
+                    items.push(self.value()?);
+                    self.skip_ws(true);
+                    if !self.eat(",") && !self.rest().starts_with(']') {
+                        return Err(self.error("expected ',' or ']' in array"));
+                    }
+                }
+            }
+            Some('{') => {
+                self.pos += 1;
+                let mut table = Value::Object(Vec::new());
+                loop {
+                    self.skip_ws(false);
+                    if self.eat("}") {
+                        return Ok(table);
+                    }
+                    let path = self.key_path("=")?;
+                    let value = self.value()?;
+                    insert(&mut table, &path, value)?;
+                    self.skip_ws(false);
+                    if !self.eat(",") && !self.rest().starts_with('}') {
+                        return Err(self.error("expected ',' or '}' in inline table"));
+                    }
+                }
+            }
+            _ => {
+                let rest = self.rest();
+                let len = rest
+                    .find([',', ']', '}', '#', '\r', '\n'])
+                    .unwrap_or(rest.len());
+                let token = rest[..len].trim_end();
+                if token.is_empty() {
+                    return Err(self.error("expected a value"));
+                }
+                self.pos += token.len();
+                Ok(toml_bare_value(token))
+            }
+        }
+    }
+
+    /// Read a basic (`"`), literal (`'`) or multi-line (`"""`, `'''`) string
+    fn string(&mut self) -> Result<String> {
+        let rest = self.rest();
+        for delimiter in ["\"\"\"", "'''"] {
+            if let Some(body) = rest.strip_prefix(delimiter) {
+                let end = body
+                    .find(delimiter)
+                    .ok_or_else(|| self.error("unterminated multi-line string"))?;
+                self.pos += 2 * delimiter.len() + end;
+                // A line break right after the opening delimiter is not part of the string
+                let text = &body[..end];
+                let text = text
+                    .strip_prefix("\r\n")
+                    .or_else(|| text.strip_prefix('\n'))
+                    .unwrap_or(text);
+                return Ok(if delimiter == "'''" {
+                    text.to_string()
+                } else {
+                    unescape_toml_basic(text)
+                });
+            }
+        }
+
+        let quote = rest.chars().next().unwrap_or('"');
+        let body = &rest[1..];
+        let mut escaped = false;
+        let end = body
+            .char_indices()
+            .find(|&(_, c)| {
+                let close = c == quote && !escaped;
+                escaped = quote == '"' && c == '\\' && !escaped;
+                close || c == '\n'
+            })
+            .filter(|&(_, c)| c == quote)
+            .map(|(i, _)| i)
+            .ok_or_else(|| self.error("unterminated string"))?;
+        self.pos += end + 2;
+        Ok(if quote == '\'' {
+            body[..end].to_string()
+        } else {
+            unescape_toml_basic(&body[..end])
+        })
+    }
+}
+
+/// Decode the escapes in the body of a basic string. A backslash at the end of
+/// a line (multi-line strings only) drops the line break and leading whitespace.
+fn unescape_toml_basic(body: &str) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('b') => out.push('\u{8}'),
+            Some('f') => out.push('\u{c}'),
+            Some(u @ ('u' | 'U')) => {
+                let width = if u == 'u' { 4 } else { 8 };
+                let hex: String = (0..width).filter_map(|_| chars.next()).collect();
+                let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                out.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            Some(c) if c.is_whitespace() => {
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Interpret an unquoted TOML value: boolean, number, or (as text) a date-time,
+/// `inf`/`nan` or anything unrecognized
+fn toml_bare_value(token: &str) -> Value {
+    match token {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+
+    let digits = token.replace('_', "");
+    let negative = digits.starts_with('-');
+    let unsigned = digits.trim_start_matches(['+', '-']);
+    for (prefix, radix) in [("0x", 16), ("0o", 8), ("0b", 2)] {
+        if let Some(n) = unsigned
+            .strip_prefix(prefix)
+            .and_then(|d| i64::from_str_radix(d, radix).ok())
+        {
+            return Value::Number(if negative { -n } else { n }.to_string());
+        }
+    }
+    let number = digits.strip_prefix('+').unwrap_or(&digits);
+    if number.starts_with(|c: char| c == '-' || c.is_ascii_digit()) && is_valid_json(number) {
+        return Value::Number(number.to_string());
+    }
+
+    Value::String(token.to_string())
+}
+
+/// The table at `path` under `root`, creating missing tables on the way.
+/// An array of tables along the path resolves to its last element.
+fn table_at<'v>(root: &'v mut Value, path: &[String]) -> Result<&'v mut Value> {
+    let mut current = root;
+    for key in path {
+        let next = member(current, key, || Value::Object(Vec::new()));
+        if matches!(next, Value::Object(_)) {
+            current = next;
+            continue;
+        }
+        current = match next {
+            Value::Array(items) => match items.last_mut() {
+                Some(last @ Value::Object(_)) => last,
+                _ => return Err(conversion_error(format!("'{}' is not a table", key))),
+            },
+            _ => return Err(conversion_error(format!("'{}' is not a table", key))),
+        };
+    }
+    Ok(current)
+}
+
+/// The member `key` of the table `table`, inserted with `default()` if missing
+fn member<'v>(table: &'v mut Value, key: &str, default: impl FnOnce() -> Value) -> &'v mut Value {
+    let Value::Object(entries) = table else {
+        unreachable!("table_at only returns objects");
+    };
+    let index = match entries.iter().position(|(k, _)| k == key) {
+        Some(index) => index,
+        None => {
+            entries.push((key.to_string(), default()));
+            entries.len() - 1
+        }
+    };
+    &mut entries[index].1
+}
+
+/// Set the dotted key `path` in `table`
+fn insert(table: &mut Value, path: &[String], value: Value) -> Result<()> {
+    let (last, parents) = path.split_last().expect("key paths are non-empty");
+    let target = member(table_at(table, parents)?, last, || Value::Null);
+    if *target != Value::Null {
+        return Err(conversion_error(format!("duplicate key '{}'", last)));
+    }
+    *target = value;
+    Ok(())
+}
+
+fn is_table_array(value: &Value) -> bool {
+    matches!(value, Value::Array(items)
+        if !items.is_empty() && items.iter().all(|item| matches!(item, Value::Object(_))))
+}
+
+fn toml_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        key.to_string()
+    } else {
+        json_string(key)
+    }
+}
+
+/// Write the members of a table: plain keys first, then sub-tables and arrays of tables
+fn write_toml_table(
+    entries: &[(String, Value)],
+    path: &mut Vec<String>,
+    out: &mut String,
+) -> Result<()> {
+    for (key, value) in entries {
+        if matches!(value, Value::Null | Value::Object(_)) || is_table_array(value) {
+            continue;
+        }
+        out.push_str(&toml_key(key));
+        out.push_str(" = ");
+        write_toml_inline(value, out)?;
+        out.push('\n');
+    }
+
+    for (key, value) in entries {
+        path.push(toml_key(key));
+        match value {
+            Value::Object(members) => {
+                out.push_str(&format!("\n[{}]\n", path.join(".")));
+                write_toml_table(members, path, out)?;
+            }
+            Value::Array(items) if is_table_array(value) => {
+                for item in items {
+                    if let Value::Object(members) = item {
+                        out.push_str(&format!("\n[[{}]]\n", path.join(".")));
+                        write_toml_table(members, path, out)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        path.pop();
+    }
+    Ok(())
+}
+
+fn write_toml_inline(value: &Value, out: &mut String) -> Result<()> {
+    match value {
+        Value::Null => {
+            return Err(conversion_error(
+                "TOML cannot represent null inside an array",
+            ));
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_toml_inline(item, out)?;
+            }
+            out.push(']');
+        }
+        Value::Object(entries) => {
+            let members: Vec<_> = entries.iter().filter(|(_, v)| *v != Value::Null).collect();
+            if members.is_empty() {
+                out.push_str("{}");
+                return Ok(());
+            }
+            out.push_str("{ ");
+            for (i, (key, item)) in members.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&toml_key(key));
+                out.push_str(" = ");
+                write_toml_inline(item, out)?;
+            }
+            out.push_str(" }");
+        }
+        other => write_json(other, out),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_to_yaml_round_trip() {
+        let input = r#"{"name": "John", "tags": ["a", "b",], "nested": {"x": 1.5, "y": null, "ok": true}, "empty": [],}"#;
+        let yaml = json_to_yaml(input).unwrap();
+        assert_eq!(
+            yaml,
+            "name: John\ntags:\n  - a\n  - b\nnested:\n  x: 1.5\n  y: null\n  ok: true\nempty: []"
+        );
+
+        let json = yaml_to_json(&yaml).unwrap();
+        assert_eq!(
+            JsonParser::new(&json).parse().unwrap(),
+            JsonParser::new(&crate::jsonrepair(input).unwrap())
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_yaml_to_json() {
+        let yaml = "# people\nserver:\n  host: localhost  # dev\n  ports: [80, 443]\nusers:\n- name: Ann\n  admin: yes\n- name: 'O''Brien'\n  admin: false\nnote: |\n  line one\n  line two\nversion: \"1.0\"";
+        assert_eq!(
+            yaml_to_json(yaml).unwrap(),
+            r#"{"server": {"host": "localhost", "ports": [80, 443]}, "users": [{"name": "Ann", "admin": "yes"}, {"name": "O'Brien", "admin": false}], "note": "line one\nline two\n", "version": "1.0"}"#
+        );
+    }
+
+    #[test]
+    fn test_valid_yaml_is_not_repaired_first() {
+        assert_eq!(
+            yaml_to_json("s: 'it''s'\no: 012").unwrap(),
+            r#"{"s": "it's", "o": "012"}"#
+        );
+    }
+
+    #[test]
+    fn test_yaml_numbers() {
+        assert_eq!(
+            yaml_to_json("a: .inf\nb: -.5\nc: +1\nd: 0x1F\ne: 1.\nf: -.INF\ng: 1e3").unwrap(),
+            r#"{"a": 1e999, "b": -0.5, "c": 1, "d": 31, "e": 1, "f": -1e999, "g": 1e3}"#
+        );
+        assert_eq!(yaml_to_json("v: 1.2.3").unwrap(), r#"{"v": "1.2.3"}"#);
+    }
+
+    #[test]
+    fn test_yaml_anchors_and_aliases_rejected() {
+        for yaml in ["a: &x 1\nb: *x", "- *x", "a: !!str 1", "a: [*x]"] {
+            assert!(
+                matches!(yaml_to_json(yaml), Err(RepairError::Conversion(_))),
+                "{}",
+                yaml
+            );
+        }
+    }
+
+    #[test]
+    fn test_strings_that_look_like_other_types_are_quoted() {
+        let yaml = json_to_yaml(r#"{"a": "true", "b": "42", "c": "x: y", "d": "", "e": "- item"}"#)
+            .unwrap();
+        assert_eq!(
+            yaml,
+            "a: \"true\"\nb: \"42\"\nc: \"x: y\"\nd: \"\"\ne: \"- item\""
+        );
+        assert_eq!(
+            yaml_to_json(&yaml).unwrap(),
+            r#"{"a": "true", "b": "42", "c": "x: y", "d": "", "e": "- item"}"#
+        );
+    }
+
+    #[test]
+    fn test_csv_json_round_trip() {
+        let csv = "name,age,city\nAnn,30,Paris\nBob,25,\"New York\"\nCara,41,Oslo";
+        let json = csv_to_json(csv).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"name": "Ann", "age": 30, "city": "Paris"}, {"name": "Bob", "age": 25, "city": "New York"}, {"name": "Cara", "age": 41, "city": "Oslo"}]"#
+        );
+        assert_eq!(json_to_csv(&json).unwrap(), csv);
+    }
+
+    #[test]
+    fn test_csv_missing_headers_and_cells() {
+        // Headerless data gets generic column names from the CSV repairer
+        assert_eq!(
+            csv_to_json("1,Ann\n2,Bob").unwrap(),
+            r#"[{"column_1": 1, "column_2": "Ann"}, {"column_1": 2, "column_2": "Bob"}]"#
+        );
+        // A blank header cell is named after its position; short rows get nulls
+        assert_eq!(
+            csv_to_json("\"id\",\"\"\n\"a\",\"b\"\n\"c\"").unwrap(),
+            r#"[{"id": "a", "column_2": "b"}, {"id": "c", "column_2": null}]"#
+        );
+    }
+
+    #[test]
+    fn test_json_to_csv_flattens_nested_values() {
+        let csv = json_to_csv(r#"[{"a": 1, "tags": ["x", "y"]}, {"b": null, "a": "hi, there",}]"#)
+            .unwrap();
+        assert_eq!(
+            csv,
+            "a,tags,b\n1,\"[\"\"x\"\", \"\"y\"\"]\",\n\"hi, there\",,"
+        );
+        assert!(json_to_csv("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn test_toml_package_to_json() {
+        let toml = r#"# manifest
+[package]
+name = "anyrepair"
+version = "0.2.9"
+authors = [
+  "a",
+  "b", # lead
+]
+edition = 2_024
+published = 1979-05-27T07:32:00Z
+
+[package.metadata]
+flags = { fast = true, level = 0x1F }
+
+[[bin]]
+name = 'cli'
+
+[[bin]]
+name = "mcp"
+"#;
+        let json = toml_to_json(toml).unwrap();
+        assert!(json.contains(r#""name": "anyrepair""#));
+        assert_eq!(
+            json,
+            r#"{"package": {"name": "anyrepair", "version": "0.2.9", "authors": ["a", "b"], "edition": 2024, "published": "1979-05-27T07:32:00Z", "metadata": {"flags": {"fast": true, "level": 31}}}, "bin": [{"name": "cli"}, {"name": "mcp"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_valid_toml_is_not_repaired_first() {
+        assert_eq!(
+            toml_to_json("name = \"x\"\nport = 8_080").unwrap(),
+            r#"{"name": "x", "port": 8080}"#
+        );
+        assert_eq!(toml_to_json("h = 0xff").unwrap(), r#"{"h": 255}"#);
+        assert_eq!(toml_to_json("n = 1_000").unwrap(), r#"{"n": 1000}"#);
+        assert_eq!(
+            toml_to_json("at = 1979-05-27T07:32:00Z\nday = 1979-05-27").unwrap(),
+            r#"{"at": "1979-05-27T07:32:00Z", "day": "1979-05-27"}"#
+        );
+    }
+
+    #[test]
+    fn test_json_toml_round_trip() {
+        let json = r#"{"title": "x y", "n": 1.5, "skip": null, "owner": {"name": "Tom", "tags": ["a", "b"]}, "servers": [{"ip": "alpha"}, {"ip": "beta", "port": 80}],}"#;
+        let toml = json_to_toml(json).unwrap();
+        assert_eq!(
+            toml,
+            "title = \"x y\"\nn = 1.5\n\n[owner]\nname = \"Tom\"\ntags = [\"a\", \"b\"]\n\n[[servers]]\nip = \"alpha\"\n\n[[servers]]\nip = \"beta\"\nport = 80"
+        );
+        assert_eq!(
+            toml_to_json(&toml).unwrap(),
+            r#"{"title": "x y", "n": 1.5, "owner": {"name": "Tom", "tags": ["a", "b"]}, "servers": [{"ip": "alpha"}, {"ip": "beta", "port": 80}]}"#
+        );
+        assert!(json_to_toml("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn test_toml_strings() {
+        let toml = "a = \"\"\"\nline \\\"one\\\"\nline two\"\"\"\nb = '''\nraw \\n'''\nc = \"tab\\there\"\nd = 'C:\\path'";
+        assert_eq!(
+            toml_to_json(toml).unwrap(),
+            r#"{"a": "line \"one\"\nline two", "b": "raw \\n", "c": "tab\there", "d": "C:\\path"}"#
+        );
+    }
+
+    #[test]
+    fn test_nested_sequences() {
+        let yaml = json_to_yaml(r#"[[1, 2], {"k": [true]}, "s"]"#).unwrap();
+        assert_eq!(yaml, "-\n  - 1\n  - 2\n-\n  k:\n    - true\n- s");
+        assert_eq!(
+            yaml_to_json(&yaml).unwrap(),
+            r#"[[1, 2], {"k": [true]}, "s"]"#
+        );
+    }
+
+    #[test]
+    fn test_table_json_rows_padded() {
+        let table = repair_table_json("[[1, 2, 3], [4,], [\"a\", null, true, false],]").unwrap();
+        let number = |n: &str| Value::Number(n.to_string());
+        assert_eq!(
+            table,
+            vec![
+                vec![number("1"), number("2"), number("3"), Value::Null],
+                vec![number("4"), Value::Null, Value::Null, Value::Null],
+                vec![
+                    Value::String("a".to_string()),
+                    Value::Null,
+                    Value::Bool(true),
+                    Value::Bool(false)
+                ],
+            ]
+        );
+        assert!(repair_table_json("[]").unwrap().is_empty());
+        assert!(repair_table_json(r#"[[1], {"a": 2}]"#).is_err());
+    }
+
+    #[test]
+    fn test_parsers_stop_at_max_depth() {
+        let exceeded = |result: Result<Value>, limit: usize| {
+            matches!(result, Err(RepairError::MaxDepthExceeded { depth, limit: l })
+                if l == limit && depth == limit + 1)
+        };
+        let arrays = format!("{}{}", "[".repeat(50_000), "]".repeat(50_000));
+        assert!(exceeded(parse_json(&arrays), MAX_PARSE_DEPTH));
+        let toml = format!("a = {}", arrays);
+        assert!(exceeded(
+            TomlParser::new(&toml).parse(),
+            MAX_RECURSION_DEPTH
+        ));
+
+        let yaml: String = (0..=MAX_RECURSION_DEPTH)
+            .map(|i| format!("{}k:\n", " ".repeat(i)))
+            .collect();
+        assert!(exceeded(
+            YamlParser::new(&yaml).parse(),
+            MAX_RECURSION_DEPTH
+        ));
+        let yaml = format!("{}k: {}", " ".repeat(MAX_RECURSION_DEPTH), arrays);
+        assert!(exceeded(
+            YamlParser::new(&yaml).parse(),
+            MAX_RECURSION_DEPTH
+        ));
+
+        let nested = format!(
+            "{}{}",
+            "[".repeat(MAX_PARSE_DEPTH),
+            "]".repeat(MAX_PARSE_DEPTH)
+        );
+        let mut out = String::new();
+        write_json(&parse_json(&nested).unwrap(), &mut out);
+        assert_eq!(out, nested);
+    }
+}
@@ -1,8 +1,10 @@
 //! CSV repair module
 
 use crate::error::Result;
+use crate::repairer_base::BaseOptions;
 use crate::traits::{Repair, RepairStrategy, Validator};
 use regex::Regex;
+use std::borrow::Cow;
 use std::sync::OnceLock;
 
 /// Cached regex patterns for CSV performance optimization
@@ -39,9 +41,22 @@ pub struct CsvRepairer {
 impl CsvRepairer {
     /// Create a new CSV repairer
     pub fn new() -> Self {
+        Self::build(None)
+    }
+
+    /// Create a CSV repairer that treats a quoted field spanning lines as missing
+    /// its closing quote once it grows past `max_field_len` bytes, closing it at the
+    /// end of the line it opened on instead of letting it swallow the rows after
+    pub fn with_max_field_len(max_field_len: usize) -> Self {
+        Self::builder().max_field_len(max_field_len).build()
+    }
+
+    fn build(max_field_len: Option<usize>) -> Self {
         let strategies: Vec<Box<dyn RepairStrategy>> = vec![
             Box::new(FixUnquotedStringsStrategy),
             Box::new(FixMalformedQuotesStrategy),
+            Box::new(BalanceFieldQuotesStrategy { max_field_len }),
+            Box::new(JoinBrokenRecordsStrategy),
             Box::new(FixMissingQuotesStrategy),
             Box::new(FixExtraCommasStrategy),
             Box::new(FixMissingCommasStrategy),
@@ -53,6 +68,45 @@ impl CsvRepairer {
 
         Self { inner }
     }
+
+    /// Start configuring a CSV repairer
+    pub fn builder() -> CsvRepairerBuilder {
+        CsvRepairerBuilder::default()
+    }
+}
+
+/// Builder combining any of the options of [`CsvRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct CsvRepairerBuilder {
+    base: BaseOptions,
+    max_field_len: Option<usize>,
+}
+
+impl CsvRepairerBuilder {
+    /// Close a quoted field spanning lines once it grows past `max_field_len` bytes
+    pub fn max_field_len(mut self, max_field_len: usize) -> Self {
+        self.max_field_len = Some(max_field_len);
+        self
+    }
+
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Create the configured CSV repairer
+    pub fn build(self) -> CsvRepairer {
+        let mut repairer = CsvRepairer::build(self.max_field_len);
+        repairer.inner.apply_options(self.base);
+        repairer
+    }
 }
 
 impl Default for CsvRepairer {
@@ -66,46 +120,21 @@ impl Repair for CsvRepairer {
         self.inner.repair(content)
     }
 
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        self.inner.repair_cow(content)
+    }
+
     fn needs_repair(&self, content: &str) -> bool {
         self.inner.needs_repair(content)
     }
 
     fn confidence(&self, content: &str) -> f64 {
-        if content.trim().is_empty() {
-            return 0.0;
+        if self.inner.validator().is_valid(content) {
+            return 1.0;
         }
-
-        // Calculate confidence based on CSV-like patterns
-        let mut score: f64 = 0.0;
-
-        // Check for commas (CSV delimiter)
-        if content.contains(',') {
-            score += 0.4;
-        }
-
-        // Check for quoted strings
-        if content.contains('"') {
-            score += 0.2;
-        }
-
-        // Check for multiple lines
-        if content.lines().count() > 1 {
-            score += 0.2;
-        }
-
-        // Check for consistent column count
-        let lines: Vec<&str> = content.lines().collect();
-        if lines.len() > 1 {
-            let first_line_commas = lines[0].matches(',').count();
-            let consistent_commas = lines
-                .iter()
-                .all(|line| line.matches(',').count() == first_line_commas);
-            if consistent_commas {
-                score += 0.2;
-            }
-        }
-
-        score.min(1.0)
+        // Graded by the share of rows that parse with the header's column count,
+        // never reaching a valid score
+        0.9 * csv_completeness(content)
     }
 }
 
@@ -134,18 +163,19 @@ fn csv_structure_valid(content: &str) -> bool {
         return false;
     }
 
-    let lines: Vec<&str> = content
-        .lines()
-        .map(str::trim)
-        .filter(|l| !l.is_empty())
+    let records = csv_records(content, None);
+    let records: Vec<&str> = records
+        .iter()
+        .map(|r| r.trim())
+        .filter(|r| !r.is_empty())
         .collect();
-    if lines.is_empty() {
+    if records.is_empty() {
         return false;
     }
 
     let mut column_count = None;
-    for line in lines {
-        let fields = match parse_csv_fields(line) {
+    for record in records {
+        let fields = match parse_csv_fields(record) {
             Ok(f) => f,
             Err(_) => return false,
         };
@@ -162,7 +192,110 @@ fn csv_structure_valid(content: &str) -> bool {
     true
 }
 
-fn parse_csv_fields(line: &str) -> std::result::Result<Vec<String>, ()> {
+/// Share (0.0–1.0) of the non-blank rows of `content` that parse and have as
+/// many fields as the first row
+fn csv_completeness(content: &str) -> f64 {
+    let rows: Vec<_> = csv_records(content, None)
+        .iter()
+        .map(|r| r.trim())
+        .filter(|l| !l.is_empty())
+        .map(parse_csv_fields)
+        .collect();
+    let Some(Ok(header)) = rows.first() else {
+        return 0.0;
+    };
+    let good = rows
+        .iter()
+        .filter(|row| {
+            row.as_ref()
+                .is_ok_and(|fields| fields.len() == header.len())
+        })
+        .count();
+    good as f64 / rows.len() as f64
+}
+
+/// Split `content` into records: its lines, except that a quoted field still open
+/// at the end of a line continues onto the following lines when a later quote
+/// closes it. A field that never closes, or that grows past `max_field_len` bytes,
+/// is taken to be missing its closing quote; its record ends with the line it
+/// opened on and the lines after are read as records of their own.
+pub(crate) fn csv_records(content: &str, max_field_len: Option<usize>) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut records = Vec::new();
+    let mut start = 0;
+
+    while start < lines.len() {
+        let mut record = String::new();
+        let mut in_quotes = false;
+        let mut open_at = 0;
+        let mut end = start;
+        loop {
+            if end > start {
+                record.push('\n');
+            }
+            let (still_open, opened_at) = quote_state_after(lines[end], in_quotes);
+            in_quotes = still_open;
+            if let Some(i) = opened_at {
+                open_at = record.len() + i;
+            }
+            record.push_str(lines[end]);
+
+            let too_long =
+                in_quotes && max_field_len.is_some_and(|max| record.len() - open_at > max);
+            if !in_quotes || too_long || end + 1 == lines.len() {
+                break;
+            }
+            end += 1;
+        }
+
+        if in_quotes && end > start {
+            records.push(lines[start].to_string());
+            start += 1;
+        } else {
+            records.push(record);
+            start = end + 1;
+        }
+    }
+
+    records
+}
+
+/// Read `line`, starting inside a quoted field if `in_quotes`, and return whether
+/// a quoted field is still open at its end, with the byte offset in `line` of the
+/// last quote that opened a field
+pub(crate) fn quote_state_after(line: &str, mut in_quotes: bool) -> (bool, Option<usize>) {
+    let mut opened_at = None;
+    let mut field_start = !in_quotes;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        if in_quotes {
+            // Only a quote followed by a separator or the line end closes
+            // the field; `""` is an escaped quote
+            if ch == '"' {
+                match chars.peek() {
+                    Some((_, '"')) => {
+                        chars.next();
+                    }
+                    Some((_, ',')) | None => in_quotes = false,
+                    Some(_) => {}
+                }
+            }
+            continue;
+        }
+        match ch {
+            '"' if field_start => {
+                in_quotes = true;
+                opened_at = Some(i);
+            }
+            ',' => field_start = true,
+            ' ' | '\t' => {}
+            _ => field_start = false,
+        }
+    }
+    (in_quotes, opened_at)
+}
+
+pub(crate) fn parse_csv_fields(line: &str) -> std::result::Result<Vec<String>, ()> {
     let mut fields = Vec::new();
     let mut current = String::new();
     let mut in_quotes = false;
@@ -193,7 +326,7 @@ fn parse_csv_fields(line: &str) -> std::result::Result<Vec<String>, ()> {
     Ok(fields)
 }
 
-fn format_csv_line(fields: &[String]) -> String {
+pub(crate) fn format_csv_line(fields: &[String]) -> String {
     fields
         .iter()
         .map(|field| {
@@ -211,6 +344,187 @@ fn format_csv_line(fields: &[String]) -> String {
         .join(",")
 }
 
+/// Split a row with unbalanced quotes into fields. A quote that neither opens
+/// nor closes a field is kept as text, and a quoted field still open at the end
+/// of the row is closed there, or with `close_at_comma` at its next comma.
+fn split_unbalanced_fields(line: &str, close_at_comma: bool) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if in_quotes => match chars.peek() {
+                Some('"') => {
+                    chars.next();
+                    current.push('"');
+                }
+                Some(',') | None => in_quotes = false,
+                // Stray quote inside a quoted field
+                Some(_) => current.push('"'),
+            },
+            '"' if current.is_empty() => in_quotes = true,
+            ',' if !in_quotes || close_at_comma => {
+                in_quotes = false;
+                fields.push(std::mem::take(&mut current));
+            }
+            c => current.push(c),
+        }
+    }
+
+    fields.push(current);
+    fields
+}
+
+/// Strategy to close quoted fields left open at the end of a row and escape
+/// stray quotes inside fields
+struct BalanceFieldQuotesStrategy {
+    max_field_len: Option<usize>,
+}
+
+impl RepairStrategy for BalanceFieldQuotesStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let records = csv_records(content, self.max_field_len);
+        // Rows are expected to have as many fields as the first well-formed one
+        let columns = records
+            .iter()
+            .map(|r| r.trim())
+            .filter(|r| !r.is_empty())
+            .find_map(|r| parse_csv_fields(r).ok())
+            .map(|fields| fields.len());
+
+        let mut out = Vec::new();
+        for record in records {
+            let trimmed = record.trim();
+            if trimmed.is_empty() || parse_csv_fields(trimmed).is_ok() {
+                out.push(record);
+                continue;
+            }
+            let mut fields = split_unbalanced_fields(trimmed, false);
+            if Some(fields.len()) != columns {
+                // The commas after the open quote may have been meant as separators
+                let split = split_unbalanced_fields(trimmed, true);
+                if Some(split.len()) == columns {
+                    fields = split;
+                }
+            }
+            out.push(format_csv_line(&fields));
+        }
+        Ok(out.join("\n"))
+    }
+
+    // Same priority as FixMalformedQuotes but listed after it, since its pattern
+    // does not understand `""` escapes
+    fn priority(&self) -> u8 {
+        5
+    }
+
+    fn name(&self) -> &str {
+        "BalanceFieldQuotesStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Close quoted fields left open at the end of a row and escape stray quotes"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("name,city\nAnn,\"Paris\nBob,Rome")
+    }
+}
+
+/// Strategy to rejoin a row split by a stray newline in an unquoted field
+///
+/// A row is joined with the rows after it that have fewer fields than the first
+/// row, when together they have exactly that many fields; the broken field's parts
+/// are joined with a space. Quoted fields spanning lines are already one record.
+struct JoinBrokenRecordsStrategy;
+
+impl RepairStrategy for JoinBrokenRecordsStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let records = csv_records(content, None);
+        let parsed: Vec<Option<Vec<String>>> = records
+            .iter()
+            .map(|r| Some(r.trim()).filter(|r| !r.is_empty()))
+            .map(|r| r.and_then(|r| parse_csv_fields(r).ok()))
+            .collect();
+        let Some(header) = records.iter().position(|r| !r.trim().is_empty()) else {
+            return Ok(content.to_string());
+        };
+        let Some(columns) = parsed[header].as_ref().map(Vec::len) else {
+            return Ok(content.to_string());
+        };
+
+        let short =
+            |fields: &Option<Vec<String>>| fields.as_ref().is_some_and(|f| f.len() < columns);
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < records.len() {
+            let start = parsed[i]
+                .clone()
+                .filter(|f| i > header && f.len() <= columns);
+            let Some(mut joined) = start else {
+                out.push(records[i].clone());
+                i += 1;
+                continue;
+            };
+
+            let mut next = i + 1;
+            while joined.len() < columns || next == i + 1 {
+                let Some(Some(rest)) = parsed.get(next).filter(|r| short(r)) else {
+                    break;
+                };
+                // A quoted piece is a complete field, not part of a broken one
+                let quoted = records[next - 1].trim_end().ends_with('"')
+                    || records[next].trim_start().starts_with('"');
+                if quoted {
+                    break;
+                }
+                if joined.len() + rest.len() - 1 > columns {
+                    break;
+                }
+                let mut rest = rest.iter();
+                if let (Some(last), Some(first)) = (joined.last_mut(), rest.next()) {
+                    let first = first.trim_start();
+                    if !last.is_empty() && !first.is_empty() {
+                        last.push(' ');
+                    }
+                    last.push_str(first);
+                }
+                joined.extend(rest.cloned());
+                next += 1;
+            }
+
+            if next > i + 1 && joined.len() == columns {
+                out.push(format_csv_line(&joined));
+                i = next;
+            } else {
+                out.push(records[i].clone());
+                i += 1;
+            }
+        }
+        Ok(out.join("\n"))
+    }
+
+    // Runs after BalanceFieldQuotes, which leaves every record parseable, and
+    // is listed before FixMissingQuotes so the joined row is formatted like the rest
+    fn priority(&self) -> u8 {
+        4
+    }
+
+    fn name(&self) -> &str {
+        "JoinBrokenRecordsStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Rejoin a row split by a stray newline in an unquoted field"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("name,city\nAnn,New\nYork")
+    }
+}
+
 /// Strategy to fix unquoted strings that should be quoted
 struct FixUnquotedStringsStrategy;
 
@@ -239,6 +553,14 @@ impl RepairStrategy for FixUnquotedStringsStrategy {
     fn name(&self) -> &str {
         "FixUnquotedStringsStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix unquoted strings that should be quoted"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("Ann Lee")
+    }
 }
 
 /// Strategy to fix malformed quotes
@@ -265,6 +587,10 @@ impl RepairStrategy for FixMalformedQuotesStrategy {
     fn name(&self) -> &str {
         "FixMalformedQuotesStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix malformed quotes"
+    }
 }
 
 /// Strategy to fix missing quotes around values with commas
@@ -273,10 +599,10 @@ struct FixMissingQuotesStrategy;
 impl RepairStrategy for FixMissingQuotesStrategy {
     fn apply(&self, content: &str) -> Result<String> {
         let mut out = Vec::new();
-        for line in content.lines() {
-            let trimmed = line.trim();
+        for record in csv_records(content, None) {
+            let trimmed = record.trim();
             if trimmed.is_empty() {
-                out.push(line.to_string());
+                out.push(record);
                 continue;
             }
             match parse_csv_fields(trimmed) {
@@ -300,6 +626,14 @@ impl RepairStrategy for FixMissingQuotesStrategy {
     fn name(&self) -> &str {
         "FixMissingQuotesStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix missing quotes around values with commas"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("name,city\nAnn,New York")
+    }
 }
 
 /// Strategy to fix extra commas
@@ -308,9 +642,23 @@ struct FixExtraCommasStrategy;
 impl RepairStrategy for FixExtraCommasStrategy {
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_csv_regex_cache();
-        let result = cache.extra_commas.replace_all(content, ",");
-
-        Ok(result.to_string())
+        let records: Vec<String> = csv_records(content, None)
+            .iter()
+            .map(|record| {
+                // Odd parts are inside quotes and keep their commas
+                record
+                    .split('"')
+                    .enumerate()
+                    .map(|(i, part)| match i % 2 {
+                        0 => cache.extra_commas.replace_all(part, ","),
+                        _ => Cow::Borrowed(part),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\"")
+            })
+            .collect();
+
+        Ok(records.join("\n"))
     }
 
     fn priority(&self) -> u8 {
@@ -320,6 +668,14 @@ impl RepairStrategy for FixExtraCommasStrategy {
     fn name(&self) -> &str {
         "FixExtraCommasStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix extra commas"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("name,age,,\nAnn,30,,")
+    }
 }
 
 /// Strategy to fix missing commas (space-separated fields without commas)
@@ -328,14 +684,15 @@ struct FixMissingCommasStrategy;
 impl RepairStrategy for FixMissingCommasStrategy {
     fn apply(&self, content: &str) -> Result<String> {
         let mut out = Vec::new();
-        for line in content.lines() {
-            let trimmed = line.trim();
+        for record in csv_records(content, None) {
+            let trimmed = record.trim();
             if trimmed.is_empty() {
-                out.push(line.to_string());
+                out.push(record);
                 continue;
             }
-            // Only add commas if line has no commas and multiple whitespace-separated tokens
-            if !trimmed.contains(',') {
+            // Only add commas if the record has no commas or quoted fields and
+            // multiple whitespace-separated tokens
+            if !trimmed.contains([',', '"']) {
                 let tokens: Vec<&str> = trimmed.split_whitespace().collect();
                 if tokens.len() > 1 {
                     out.push(tokens.join(","));
@@ -356,6 +713,14 @@ impl RepairStrategy for FixMissingCommasStrategy {
     fn name(&self) -> &str {
         "FixMissingCommasStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix missing commas (space-separated fields without commas)"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("name age city\nAnn 30 NYC")
+    }
 }
 
 /// Strategy to add headers if missing
@@ -363,17 +728,19 @@ struct AddHeadersStrategy;
 
 impl RepairStrategy for AddHeadersStrategy {
     fn apply(&self, content: &str) -> Result<String> {
-        let lines: Vec<&str> = content.lines().collect();
-        if lines.is_empty() {
+        let Some(first_record) = csv_records(content, None).into_iter().next() else {
             return Ok(content.to_string());
-        }
-
-        let first_line = lines[0].trim();
+        };
+        let first_line = first_record.trim();
 
-        // Check if first line looks like data (contains numbers or unquoted strings)
-        if first_line.chars().any(|c| c.is_ascii_digit())
-            || (!first_line.contains('"') && first_line.contains(','))
-        {
+        // Only a first line with numeric fields looks like data; unquoted text is
+        // taken to be an existing header
+        let looks_like_data = parse_csv_fields(first_line).is_ok_and(|fields| {
+            fields
+                .iter()
+                .any(|f| !f.trim().is_empty() && f.trim().parse::<f64>().is_ok())
+        });
+        if looks_like_data {
             // Add generic headers
             let column_count = first_line.matches(',').count() + 1;
             let headers: Vec<String> = (1..=column_count)
@@ -381,9 +748,7 @@ impl RepairStrategy for AddHeadersStrategy {
                 .collect();
             let header_line = headers.join(",");
 
-            let mut result = vec![header_line];
-            result.extend(lines.iter().map(|s| s.to_string()));
-            Ok(result.join("\n"))
+            Ok(format!("{}\n{}", header_line, content))
         } else {
             Ok(content.to_string())
         }
@@ -396,4 +761,12 @@ impl RepairStrategy for AddHeadersStrategy {
     fn name(&self) -> &str {
         "AddHeadersStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Add headers if missing"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("Ann,30\nBob,25")
+    }
 }
@@ -1,6 +1,7 @@
 //! Diff/Unified diff repair module
 
 use crate::error::Result;
+use crate::repairer_base::BaseOptions;
 use crate::traits::{Repair, RepairStrategy, Validator};
 use regex::Regex;
 use std::sync::OnceLock;
@@ -69,6 +70,38 @@ impl DiffRepairer {
 
         Self { inner }
     }
+
+    /// Start configuring a diff repairer
+    pub fn builder() -> DiffRepairerBuilder {
+        DiffRepairerBuilder::default()
+    }
+}
+
+/// Builder combining any of the options of [`DiffRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct DiffRepairerBuilder {
+    base: BaseOptions,
+}
+
+impl DiffRepairerBuilder {
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Create the configured diff repairer
+    pub fn build(self) -> DiffRepairer {
+        let mut repairer = DiffRepairer::new();
+        repairer.inner.apply_options(self.base);
+        repairer
+    }
 }
 
 impl Default for DiffRepairer {
@@ -346,6 +379,14 @@ impl RepairStrategy for FixMissingHunkHeadersStrategy {
     fn name(&self) -> &str {
         "FixMissingHunkHeaders"
     }
+
+    fn description(&self) -> &str {
+        "Fix missing hunk headers"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(" context\n-old\n+new")
+    }
 }
 
 /// Strategy to fix incorrect line prefixes
@@ -401,6 +442,14 @@ impl RepairStrategy for FixLinePrefixesStrategy {
     fn name(&self) -> &str {
         "FixLinePrefixes"
     }
+
+    fn description(&self) -> &str {
+        "Fix incorrect line prefixes"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("@@ -1,2 +1,2 @@\ncontext\n-old\n+new")
+    }
 }
 
 /// Strategy to fix missing newlines
@@ -428,6 +477,14 @@ impl RepairStrategy for FixMissingNewlinesStrategy {
     fn name(&self) -> &str {
         "FixMissingNewlines"
     }
+
+    fn description(&self) -> &str {
+        "Fix missing newlines"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("@@ -1 +1 @@\n-old\n+new")
+    }
 }
 
 /// Strategy to fix malformed hunk ranges
@@ -485,6 +542,14 @@ impl RepairStrategy for FixMalformedHunkRangesStrategy {
     fn name(&self) -> &str {
         "FixMalformedHunkRanges"
     }
+
+    fn description(&self) -> &str {
+        "Fix malformed hunk ranges"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("@@ 1,2 1,2 @@\n-old\n+new")
+    }
 }
 
 /// Strategy to fix missing file headers
@@ -542,6 +607,14 @@ impl RepairStrategy for FixMissingFileHeadersStrategy {
     fn name(&self) -> &str {
         "FixMissingFileHeaders"
     }
+
+    fn description(&self) -> &str {
+        "Fix missing file headers"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("@@ -1 +1 @@\n-old\n+new")
+    }
 }
 
 /// Strategy to fix inconsistent spacing
@@ -582,6 +655,14 @@ impl RepairStrategy for FixInconsistentSpacingStrategy {
     fn name(&self) -> &str {
         "FixInconsistentSpacing"
     }
+
+    fn description(&self) -> &str {
+        "Fix inconsistent spacing"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("@@  -1  +1  @@\n-old\n+new")
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,446 @@
+//! Dotenv / query-string repair module
+//!
+//! Repairs `KEY=value` blobs emitted by LLMs, either as `.env` files
+//! (`A=1\nB=2`) or URL query strings (`A=1&B=2`).
+
+use crate::error::Result;
+use crate::repairer_base::BaseOptions;
+use crate::traits::{Repair, RepairStrategy, Validator};
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+// ============================================================================
+// Dotenv Validator
+// ============================================================================
+
+/// Dotenv validator
+///
+/// Every non-comment line must be `KEY=value` with an identifier key,
+/// values containing whitespace must be quoted, and keys must be unique.
+pub struct DotenvValidator;
+
+impl Validator for DotenvValidator {
+    fn is_valid(&self, content: &str) -> bool {
+        !content.trim().is_empty() && self.validate(content).is_empty()
+    }
+
+    fn validate(&self, content: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (i, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if is_comment_or_blank(trimmed) {
+                continue;
+            }
+            let Some((key, value)) = split_entry(trimmed) else {
+                errors.push(format!("Line {}: Missing '=' delimiter", i + 1));
+                continue;
+            };
+            if is_query_string(trimmed) {
+                errors.push(format!("Line {}: Multiple entries on one line", i + 1));
+            }
+            if !is_valid_key(key) {
+                errors.push(format!("Line {}: Invalid key '{}'", i + 1, key));
+            }
+            if !is_quoted(value) && value.chars().any(char::is_whitespace) {
+                errors.push(format!("Line {}: Unquoted value with whitespace", i + 1));
+            }
+            if !seen.insert(key.to_string()) {
+                errors.push(format!("Line {}: Duplicate key '{}'", i + 1, key));
+            }
+        }
+
+        errors
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn is_comment_or_blank(trimmed: &str) -> bool {
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+/// Split `KEY=value` (optionally prefixed by `export `) into trimmed key and value.
+fn split_entry(trimmed: &str) -> Option<(&str, &str)> {
+    let body = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+    let (key, value) = body.split_once('=')?;
+    Some((key.trim(), value.trim()))
+}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+fn is_quoted(value: &str) -> bool {
+    value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')))
+}
+
+/// True if a line looks like a query string (`a=1&b=2`, optionally with a leading `?`).
+fn is_query_string(trimmed: &str) -> bool {
+    let body = trimmed.strip_prefix('?').unwrap_or(trimmed);
+    body.contains('&')
+        && !body.chars().any(char::is_whitespace)
+        && body
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .all(|pair| pair.split_once('=').is_some_and(|(k, _)| !k.is_empty()))
+}
+
+// ============================================================================
+// Repair Strategies
+// ============================================================================
+
+/// Strategy to split `A=1&B=2` query strings into one entry per line
+struct SplitQueryStringStrategy;
+
+impl RepairStrategy for SplitQueryStringStrategy {
+    fn name(&self) -> &str {
+        "SplitQueryString"
+    }
+
+    fn description(&self) -> &str {
+        "Split `A=1&B=2` query strings into one entry per line"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("A=1&B=2")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = Vec::new();
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if is_query_string(trimmed) {
+                let body = trimmed.strip_prefix('?').unwrap_or(trimmed);
+                for pair in body.split('&').filter(|p| !p.is_empty()) {
+                    // `+` encodes a space in query strings
+                    result.push(pair.replace('+', " ").replace("%20", " "));
+                }
+            } else {
+                result.push(line.to_string());
+            }
+        }
+        Ok(result.join("\n"))
+    }
+
+    fn priority(&self) -> u8 {
+        100
+    }
+}
+
+/// Strategy to normalize `KEY = value` spacing and uppercase keys on request
+struct NormalizeKeysStrategy {
+    uppercase: bool,
+}
+
+impl RepairStrategy for NormalizeKeysStrategy {
+    fn name(&self) -> &str {
+        "NormalizeKeys"
+    }
+
+    fn description(&self) -> &str {
+        "Normalize `KEY = value` spacing and uppercase keys on request"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("api_key = abc")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = Vec::new();
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if is_comment_or_blank(trimmed) {
+                result.push(trimmed.to_string());
+                continue;
+            }
+            match split_entry(trimmed) {
+                Some((key, value)) => {
+                    let export = if trimmed.starts_with("export ") {
+                        "export "
+                    } else {
+                        ""
+                    };
+                    let key = if self.uppercase {
+                        key.to_uppercase().replace(['-', ' '], "_")
+                    } else {
+                        key.replace(['-', ' '], "_")
+                    };
+                    result.push(format!("{}{}={}", export, key, value));
+                }
+                None => result.push(trimmed.to_string()),
+            }
+        }
+        Ok(result.join("\n"))
+    }
+
+    fn priority(&self) -> u8 {
+        90
+    }
+}
+
+/// Strategy to wrap values containing whitespace in double quotes
+struct QuoteSpacedValuesStrategy;
+
+impl RepairStrategy for QuoteSpacedValuesStrategy {
+    fn name(&self) -> &str {
+        "QuoteSpacedValues"
+    }
+
+    fn description(&self) -> &str {
+        "Wrap values containing whitespace in double quotes"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("GREETING=hello world")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = Vec::new();
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if is_comment_or_blank(trimmed) {
+                result.push(line.to_string());
+                continue;
+            }
+            match trimmed.split_once('=') {
+                Some((key, value))
+                    if !is_quoted(value.trim()) && value.trim().contains(char::is_whitespace) =>
+                {
+                    let value = value.trim().trim_matches(['"', '\'']);
+                    result.push(format!("{}=\"{}\"", key, value.replace('"', "\\\"")));
+                }
+                _ => result.push(line.to_string()),
+            }
+        }
+        Ok(result.join("\n"))
+    }
+
+    fn priority(&self) -> u8 {
+        80
+    }
+}
+
+/// Strategy to drop duplicate keys, keeping the last assignment (dotenv override semantics)
+struct RemoveDuplicateKeysStrategy;
+
+impl RepairStrategy for RemoveDuplicateKeysStrategy {
+    fn name(&self) -> &str {
+        "RemoveDuplicateKeys"
+    }
+
+    fn description(&self) -> &str {
+        "Drop duplicate keys, keeping the last assignment (dotenv override semantics)"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("A=1\nA=2")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut seen = HashSet::new();
+        let mut keep = vec![true; lines.len()];
+
+        for (i, line) in lines.iter().enumerate().rev() {
+            let trimmed = line.trim();
+            if is_comment_or_blank(trimmed) {
+                continue;
+            }
+            if let Some((key, _)) = split_entry(trimmed)
+                && !seen.insert(key.to_string())
+            {
+                keep[i] = false;
+            }
+        }
+
+        Ok(lines
+            .iter()
+            .zip(keep)
+            .filter(|(_, k)| *k)
+            .map(|(l, _)| *l)
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn priority(&self) -> u8 {
+        70
+    }
+}
+
+// ============================================================================
+// Dotenv Repairer
+// ============================================================================
+
+/// Dotenv repairer for `.env` files and query-string blobs
+///
+/// Uses trait-based composition with GenericRepairer for better modularity
+pub struct DotenvRepairer {
+    pub inner: crate::repairer_base::GenericRepairer,
+}
+
+impl DotenvRepairer {
+    /// Create a new dotenv repairer that keeps key casing as-is
+    pub fn new() -> Self {
+        Self::with_uppercase_keys(false)
+    }
+
+    /// Start configuring a dotenv repairer
+    pub fn builder() -> DotenvRepairerBuilder {
+        DotenvRepairerBuilder::default()
+    }
+
+    /// Create a dotenv repairer that optionally uppercases every key
+    pub fn with_uppercase_keys(uppercase: bool) -> Self {
+        let strategies: Vec<Box<dyn RepairStrategy>> = vec![
+            Box::new(SplitQueryStringStrategy),
+            Box::new(NormalizeKeysStrategy { uppercase }),
+            Box::new(QuoteSpacedValuesStrategy),
+            Box::new(RemoveDuplicateKeysStrategy),
+        ];
+
+        let validator: Box<dyn Validator> = Box::new(DotenvValidator);
+        let inner = crate::repairer_base::GenericRepairer::new(validator, strategies);
+
+        Self { inner }
+    }
+}
+
+/// Builder combining any of the options of [`DotenvRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct DotenvRepairerBuilder {
+    base: BaseOptions,
+    uppercase_keys: bool,
+}
+
+impl DotenvRepairerBuilder {
+    /// Uppercase every key
+    pub fn uppercase_keys(mut self, uppercase: bool) -> Self {
+        self.uppercase_keys = uppercase;
+        self
+    }
+
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Create the configured dotenv repairer
+    pub fn build(self) -> DotenvRepairer {
+        let mut repairer = DotenvRepairer::with_uppercase_keys(self.uppercase_keys);
+        repairer.inner.apply_options(self.base);
+        repairer
+    }
+}
+
+impl Default for DotenvRepairer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Repair for DotenvRepairer {
+    fn repair(&mut self, content: &str) -> Result<String> {
+        self.inner.repair(content)
+    }
+
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        self.inner.repair_cow(content)
+    }
+
+    fn needs_repair(&self, content: &str) -> bool {
+        self.inner.needs_repair(content)
+    }
+
+    fn confidence(&self, content: &str) -> f64 {
+        if content.trim().is_empty() {
+            return 0.0;
+        }
+        if self.inner.validator().is_valid(content) {
+            return 1.0;
+        }
+
+        let entries: Vec<&str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !is_comment_or_blank(l))
+            .collect();
+        let with_equals = entries.iter().filter(|l| l.contains('=')).count();
+
+        let mut score: f64 = 0.0;
+        if with_equals > 0 {
+            score += 0.4;
+        }
+        if !entries.is_empty() && with_equals == entries.len() {
+            score += 0.3;
+        }
+        if content.contains('&') || content.contains('#') {
+            score += 0.1;
+        }
+        score.min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dotenv_quotes_spaced_values() {
+        let mut r = DotenvRepairer::new();
+        let result = r.repair("APP_NAME=My App\nPORT=8080").unwrap();
+        assert_eq!(result, "APP_NAME=\"My App\"\nPORT=8080");
+        assert!(DotenvValidator.is_valid(&result));
+    }
+
+    #[test]
+    fn test_dotenv_duplicate_keys_last_wins() {
+        let mut r = DotenvRepairer::new();
+        let result = r.repair("HOST=a\nPORT=1\nHOST=b").unwrap();
+        assert_eq!(result, "PORT=1\nHOST=b");
+    }
+
+    #[test]
+    fn test_dotenv_query_string_split() {
+        let mut r = DotenvRepairer::new();
+        let result = r.repair("A=1&B=hello+world&C=3").unwrap();
+        assert_eq!(result, "A=1\nB=\"hello world\"\nC=3");
+    }
+
+    #[test]
+    fn test_dotenv_uppercase_keys() {
+        let mut r = DotenvRepairer::with_uppercase_keys(true);
+        let result = r
+            .repair("db-host = localhost\napi key=secret value")
+            .unwrap();
+        assert!(result.contains("DB_HOST=localhost"));
+        assert!(result.contains("API_KEY=\"secret value\""));
+    }
+
+    #[test]
+    fn test_dotenv_validator() {
+        let v = DotenvValidator;
+        assert!(v.is_valid("# comment\nKEY=value\nexport OTHER=\"a b\""));
+        assert!(!v.is_valid("KEY=a b"));
+        assert!(!v.is_valid("KEY=1\nKEY=2"));
+        assert!(!v.is_valid("1KEY=1"));
+        assert!(!v.is_valid(""));
+    }
+}
@@ -0,0 +1,229 @@
+//! Fine-grained edits proposed by repair strategies
+//!
+//! Strategies return whole strings, so edits are recovered by diffing each
+//! strategy's input against its output. This lets interactive tools show and
+//! accept individual fixes without producing the final string.
+
+use std::ops::Range;
+
+/// Upper bound on the edit distance explored by the character diff.
+/// Larger rewrites are reported as a single replacement.
+const MAX_DIFF_DISTANCE: usize = 512;
+
+/// A single proposed change.
+///
+/// Edits are sequential: each `byte_range` refers to the text produced by
+/// applying all previous edits in the list, so [`apply_edits`] applies them in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    /// Byte range to replace
+    pub byte_range: Range<usize>,
+    /// Replacement text (empty for a deletion)
+    pub replacement: String,
+    /// Name of the strategy that proposed the edit
+    pub strategy: String,
+}
+
+/// Apply edits in order, as produced by a `preview` call.
+pub fn apply_edits(content: &str, edits: &[Edit]) -> String {
+    let mut result = content.to_string();
+    for edit in edits {
+        result.replace_range(edit.byte_range.clone(), &edit.replacement);
+    }
+    result
+}
+
+/// Map `range`, in the text produced by `edits`, back to the text they were applied to.
+/// A bound inside a replacement is widened to cover the text it replaced.
+pub(crate) fn original_range(edits: &[Edit], range: Range<usize>) -> Range<usize> {
+    let (mut start, mut end) = (range.start, range.end);
+    for edit in edits.iter().rev() {
+        let replaced = &edit.byte_range;
+        let inserted_end = replaced.start + edit.replacement.len();
+        let map = |pos: usize, inside: usize| {
+            if pos >= inserted_end {
+                pos - edit.replacement.len() + replaced.len()
+            } else if pos > replaced.start {
+                inside
+            } else {
+                pos
+            }
+        };
+        start = map(start, replaced.start);
+        end = map(end, replaced.end);
+    }
+    start..end
+}
+
+/// Compute sequential edits turning `before` into `after`, offset by `base` bytes.
+pub(crate) fn diff_edits(before: &str, after: &str, strategy: &str, base: usize) -> Vec<Edit> {
+    let old: Vec<(usize, char)> = before.char_indices().collect();
+    let new: Vec<char> = after.chars().collect();
+
+    // Trim common prefix and suffix so the diff only covers the changed region
+    let prefix = old
+        .iter()
+        .zip(&new)
+        .take_while(|((_, a), b)| a == *b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|((_, a), b)| a == *b)
+        .count();
+    let old_mid: Vec<char> = old[prefix..old.len() - suffix]
+        .iter()
+        .map(|(_, c)| *c)
+        .collect();
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let byte_at = |i: usize| old.get(i).map_or(before.len(), |(b, _)| *b);
+    let ops = myers(&old_mid, new_mid).unwrap_or_else(|| {
+        vec![Op {
+            old: 0..old_mid.len(),
+            new: 0..new_mid.len(),
+        }]
+    });
+
+    // Convert char-index ops into sequential byte edits
+    let mut edits = Vec::new();
+    let mut shift: isize = 0;
+    for op in ops {
+        let Op { old: o, new: n } = op;
+        let start = byte_at(prefix + o.start);
+        let end = byte_at(prefix + o.end);
+        let replacement: String = new_mid[n].iter().collect();
+        let seq_start = (base + start).saturating_add_signed(shift);
+        shift += replacement.len() as isize - (end - start) as isize;
+        edits.push(Edit {
+            byte_range: seq_start..seq_start + (end - start),
+            replacement,
+            strategy: strategy.to_string(),
+        });
+    }
+    edits
+}
+
+/// A replaced span: `old` chars are replaced by `new` chars (either may be empty)
+struct Op {
+    old: Range<usize>,
+    new: Range<usize>,
+}
+
+/// Myers O(ND) diff returning coalesced replacements, or `None` if the
+/// edit distance exceeds [`MAX_DIFF_DISTANCE`].
+fn myers(a: &[char], b: &[char]) -> Option<Vec<Op>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = ((n + m) as usize).min(MAX_DIFF_DISTANCE) as isize;
+    let offset = max + 1;
+    let mut v = vec![0isize; 2 * offset as usize + 1];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    let mut found = false;
+    'outer: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let idx = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                found = true;
+                break 'outer;
+            }
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    // Backtrack into a list of (deleted old index | inserted new index) steps
+    let mut steps: Vec<(bool, usize, usize)> = Vec::new(); // (is_delete, x, y) before the step
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        if d == 0 {
+            break;
+        }
+        let k = x - y;
+        let prev_k =
+            if k == -d || (k != d && v[(k - 1 + offset) as usize] < v[(k + 1 + offset) as usize]) {
+                k + 1
+            } else {
+                k - 1
+            };
+        let prev_x = v[(prev_k + offset) as usize];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+        }
+        if prev_k == k + 1 {
+            steps.push((false, prev_x as usize, prev_y as usize));
+        } else {
+            steps.push((true, prev_x as usize, prev_y as usize));
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    steps.reverse();
+
+    // Coalesce adjacent deletions/insertions into replacements
+    let mut ops: Vec<Op> = Vec::new();
+    for (is_delete, x, y) in steps {
+        let (old, new) = if is_delete {
+            (x..x + 1, y..y)
+        } else {
+            (x..x, y..y + 1)
+        };
+        if let Some(Op { old: o, new: nw }) = ops.last_mut()
+            && o.end == old.start
+            && nw.end == new.start
+        {
+            o.end = old.end;
+            nw.end = new.end;
+            continue;
+        }
+        ops.push(Op { old, new });
+    }
+    Some(ops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_edits_single_deletion() {
+        let edits = diff_edits("[1, 2,]", "[1, 2]", "S", 0);
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].byte_range, 5..6);
+        assert_eq!(edits[0].replacement, "");
+    }
+
+    #[test]
+    fn test_diff_edits_are_sequential() {
+        let before = "{'a': 'b', 'c': 1}";
+        let after = "{\"a\": \"b\", \"c\": 1}";
+        let edits = diff_edits(before, after, "S", 0);
+        assert_eq!(edits.len(), 6);
+        assert_eq!(apply_edits(before, &edits), after);
+    }
+
+    #[test]
+    fn test_diff_edits_multibyte() {
+        let before = "“héllo”";
+        let after = "\"héllo\"";
+        let edits = diff_edits(before, after, "S", 0);
+        assert_eq!(apply_edits(before, &edits), after);
+    }
+}
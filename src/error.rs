@@ -17,6 +17,28 @@ pub enum RepairError {
     #[error("Format detection failed: {0}")]
     FormatDetection(String),
 
+    #[error("Nesting depth {depth} exceeds the limit of {limit}")]
+    MaxDepthExceeded { depth: usize, limit: usize },
+
+    #[error("Conversion failed: {0}")]
+    Conversion(String),
+
+    #[error("Schema validation failed: {0}")]
+    Schema(String),
+
+    #[error("Plugin configuration failed: {0}")]
+    Plugin(String),
+
+    #[error("Repair lowered confidence from {before:.2} to {after:.2}")]
+    ConfidenceDropped { before: f64, after: f64 },
+
+    #[error("Invalid JSON: {0}")]
+    JsonSyntax(#[from] crate::json_util::JsonSyntaxError),
+
+    #[cfg(feature = "strict")]
+    #[error("JSON parse error: {0}")]
+    Json(#[from] serde_json::Error),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -71,6 +93,39 @@ mod tests {
         assert!(debug_str.contains("test"));
     }
 
+    #[test]
+    fn test_json_parse_failure_exposes_source() {
+        use std::error::Error;
+
+        let err = crate::jsonrepair_minified("{\"a\" \"b\" \"c\"}").unwrap_err();
+        assert!(matches!(err, RepairError::JsonSyntax(_)));
+        assert!(err.to_string().starts_with("Invalid JSON: "));
+
+        let source = err.source().expect("JSON error should carry its cause");
+        let syntax = source
+            .downcast_ref::<crate::json_util::JsonSyntaxError>()
+            .unwrap();
+        assert_eq!(syntax.message, "expected ':' after key");
+        assert_eq!(
+            source.to_string(),
+            format!("expected ':' after key at byte {}", syntax.offset)
+        );
+    }
+
+    #[test]
+    fn test_io_and_regex_errors_convert_with_source() {
+        use std::error::Error;
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        let err = RepairError::from(io);
+        assert_eq!(err.to_string(), "IO error: gone");
+        assert!(err.source().is_some());
+
+        #[allow(clippy::invalid_regex)]
+        let regex_err = regex::Regex::new("(").unwrap_err();
+        assert!(RepairError::from(regex_err).source().is_some());
+    }
+
     #[test]
     fn test_result_type() {
         let ok_result: Result<String> = Ok("success".to_string());
@@ -20,13 +20,51 @@ pub fn detect_format(content: &str) -> Option<&'static str> {
 /// Detect format and return a [`DetectionResult`] with confidence.
 ///
 /// Confidence reflects how strong the matching heuristic is (not the
-/// quality of the content). Ambiguous cases score lower.
+/// quality of the content). Ambiguous cases score lower. Detectors added with
+/// [`crate::registry::register_detector`] are consulted after the built-ins.
 pub fn detect_format_with_confidence(content: &str) -> Option<DetectionResult> {
-    let trimmed = content.trim();
+    let trimmed = crate::repairer_base::strip_bom(content).trim();
     if trimmed.is_empty() {
         return None;
     }
 
+    let builtin = detect_builtin(trimmed);
+    // Custom detectors win only when they out-score the built-in match
+    match crate::registry::best_custom_detection(trimmed) {
+        Some((format, confidence)) if builtin.is_none_or(|b| confidence > b.confidence) => {
+            Some(DetectionResult { format, confidence })
+        }
+        _ => builtin,
+    }
+}
+
+/// Map a file path to a format by its name or extension (case-insensitive).
+/// Returns `None` for unknown extensions, so callers can fall back to content detection.
+pub fn format_from_path(path: &std::path::Path) -> Option<&'static str> {
+    let file_name = path.file_name()?.to_str()?.to_ascii_lowercase();
+    // `.env`, `.env.local`, ... have no extension of their own
+    if file_name == ".env" || file_name.starts_with(".env.") {
+        return Some("env");
+    }
+
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "json" => Some("json"),
+        "yaml" | "yml" => Some("yaml"),
+        "md" | "markdown" => Some("markdown"),
+        "xml" | "svg" => Some("xml"),
+        "toml" => Some("toml"),
+        "csv" => Some("csv"),
+        "ini" | "cfg" => Some("ini"),
+        "diff" | "patch" => Some("diff"),
+        "properties" => Some("properties"),
+        "env" => Some("env"),
+        _ => None,
+    }
+}
+
+/// Built-in heuristics, in priority order.
+fn detect_builtin(trimmed: &str) -> Option<DetectionResult> {
     if is_json_like(trimmed) {
         let confidence = if (trimmed.starts_with('{') && trimmed.ends_with('}'))
             || (trimmed.starts_with('[') && trimmed.ends_with(']'))
@@ -114,7 +152,7 @@ pub fn detect_format_with_confidence(content: &str) -> Option<DetectionResult> {
     None
 }
 
-/// All `is_*_like` helpers expect **outer** whitespace already trimmed (as `detect_format` does).
+/// All `is_*_like` helpers expect the BOM and **outer** whitespace already stripped (as `detect_format` does).
 fn is_json_like(trimmed: &str) -> bool {
     (trimmed.starts_with('{') && (trimmed.ends_with('}') || trimmed.contains(':')))
         || trimmed == "[]"
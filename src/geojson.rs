@@ -0,0 +1,275 @@
+//! GeoJSON (RFC 7946) structural rules for [`JsonProfile::GeoJson`]
+//!
+//! Runs on syntactically valid JSON. Gaps with only one sensible reading are
+//! filled in: a bare position becomes a `Point` feature, a missing `type` is
+//! inferred from `features`, `geometry`, `geometries` or a single position, type
+//! names are given their canonical case, and features get the `geometry` and
+//! `properties` members the spec requires. Anything ambiguous, such as
+//! `coordinates` nested two deep with no `type` (`LineString` or `MultiPoint`?),
+//! is left alone and reported by [`validate`].
+//!
+//! [`JsonProfile::GeoJson`]: crate::json::JsonProfile::GeoJson
+
+use crate::convert::{Value, parse_json, write_json};
+use crate::error::Result;
+
+/// Geometry types and how deeply their `coordinates` nest positions
+const GEOMETRY_TYPES: [(&str, usize); 6] = [
+    ("Point", 0),
+    ("MultiPoint", 1),
+    ("LineString", 1),
+    ("MultiLineString", 2),
+    ("Polygon", 2),
+    ("MultiPolygon", 3),
+];
+
+const OTHER_TYPES: [&str; 3] = ["GeometryCollection", "Feature", "FeatureCollection"];
+
+/// Fill the structural gaps in `content` that have a single obvious fix.
+/// Returns `content` unchanged if it is not valid JSON or needs nothing.
+pub fn repair(content: &str) -> String {
+    let Ok(mut value) = parse_json(content) else {
+        return content.to_string();
+    };
+    let original = value.clone();
+    if is_position(&value) {
+        value = point_feature(value);
+    } else {
+        repair_object(&mut value);
+    }
+
+    if value == original {
+        return content.to_string();
+    }
+    let mut out = String::new();
+    write_json(&value, &mut out);
+    out
+}
+
+/// Report what keeps `content` from being a GeoJSON object, one message per
+/// problem prefixed with the JSON Pointer of the offending value (`/` for the root).
+/// Returns `RepairError::Conversion` if `content` is not valid JSON.
+pub fn validate(content: &str) -> Result<Vec<String>> {
+    let value = parse_json(content)?;
+    let mut violations = Vec::new();
+    check(&value, "", &mut violations);
+    Ok(violations)
+}
+
+fn repair_object(value: &mut Value) {
+    let Value::Object(entries) = value else {
+        return;
+    };
+
+    match entries.iter_mut().find(|(key, _)| key == "type") {
+        Some((_, Value::String(name))) => {
+            if let Some(canonical) = canonical_type(name) {
+                *name = canonical.to_string();
+            }
+        }
+        Some(_) => {}
+        None => {
+            if let Some(inferred) = infer_type(entries) {
+                entries.insert(0, ("type".to_string(), Value::String(inferred.to_string())));
+            }
+        }
+    }
+
+    match type_of(entries) {
+        Some("Feature") => {
+            if !has_member(entries, "geometry") {
+                entries.push(("geometry".to_string(), Value::Null));
+            }
+            if !has_member(entries, "properties") {
+                entries.push(("properties".to_string(), Value::Object(Vec::new())));
+            }
+            if let Some(geometry) = member_mut(entries, "geometry") {
+                if is_position(geometry) {
+                    *geometry = point(std::mem::replace(geometry, Value::Null));
+                } else {
+                    repair_object(geometry);
+                }
+            }
+        }
+        Some("FeatureCollection") => {
+            if !has_member(entries, "features") {
+                entries.push(("features".to_string(), Value::Array(Vec::new())));
+            }
+            if let Some(Value::Array(features)) = member_mut(entries, "features") {
+                for feature in features {
+                    if is_position(feature) {
+                        *feature = point_feature(std::mem::replace(feature, Value::Null));
+                    } else {
+                        repair_object(feature);
+                    }
+                }
+            }
+        }
+        Some("GeometryCollection") => {
+            if let Some(Value::Array(geometries)) = member_mut(entries, "geometries") {
+                for geometry in geometries {
+                    if is_position(geometry) {
+                        *geometry = point(std::mem::replace(geometry, Value::Null));
+                    } else {
+                        repair_object(geometry);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The `type` an untyped object can only have
+fn infer_type(entries: &[(String, Value)]) -> Option<&'static str> {
+    if has_member(entries, "features") {
+        Some("FeatureCollection")
+    } else if has_member(entries, "geometry") {
+        Some("Feature")
+    } else if has_member(entries, "geometries") {
+        Some("GeometryCollection")
+    } else {
+        entries
+            .iter()
+            .find(|(key, _)| key == "coordinates")
+            .filter(|(_, coordinates)| is_position(coordinates))
+            .map(|_| "Point")
+    }
+}
+
+fn check(value: &Value, path: &str, out: &mut Vec<String>) {
+    let Value::Object(entries) = value else {
+        out.push(violation(path, "expected a GeoJSON object"));
+        return;
+    };
+    let name = match entries.iter().find(|(key, _)| key == "type") {
+        Some((_, Value::String(name))) => name.as_str(),
+        Some(_) => {
+            out.push(violation(&child(path, "type"), "expected a string"));
+            return;
+        }
+        None => {
+            out.push(violation(path, "missing \"type\""));
+            return;
+        }
+    };
+
+    if let Some(&(_, depth)) = GEOMETRY_TYPES.iter().find(|(known, _)| *known == name) {
+        match member(entries, "coordinates") {
+            Some(coordinates) if nests_positions(coordinates, depth) => {}
+            Some(_) => out.push(violation(
+                &child(path, "coordinates"),
+                &format!("not valid {} coordinates", name),
+            )),
+            None => out.push(violation(path, "missing \"coordinates\"")),
+        }
+        return;
+    }
+
+    match name {
+        "Feature" => {
+            match member(entries, "geometry") {
+                Some(Value::Null) => {}
+                Some(geometry) => check(geometry, &child(path, "geometry"), out),
+                None => out.push(violation(path, "missing \"geometry\"")),
+            }
+            match member(entries, "properties") {
+                Some(Value::Object(_) | Value::Null) => {}
+                Some(_) => out.push(violation(
+                    &child(path, "properties"),
+                    "expected an object or null",
+                )),
+                None => out.push(violation(path, "missing \"properties\"")),
+            }
+        }
+        "FeatureCollection" | "GeometryCollection" => {
+            let list = if name == "FeatureCollection" {
+                "features"
+            } else {
+                "geometries"
+            };
+            match member(entries, list) {
+                Some(Value::Array(items)) => {
+                    for (i, item) in items.iter().enumerate() {
+                        check(item, &child(&child(path, list), &i.to_string()), out);
+                    }
+                }
+                Some(_) => out.push(violation(&child(path, list), "expected an array")),
+                None => out.push(violation(path, &format!("missing \"{}\"", list))),
+            }
+        }
+        _ => out.push(violation(
+            &child(path, "type"),
+            &format!("unknown GeoJSON type \"{}\"", name),
+        )),
+    }
+}
+
+/// Whether `value` is an array of positions nested `depth` arrays deep
+fn nests_positions(value: &Value, depth: usize) -> bool {
+    match (depth, value) {
+        (0, _) => is_position(value),
+        (_, Value::Array(items)) => items.iter().all(|item| nests_positions(item, depth - 1)),
+        _ => false,
+    }
+}
+
+/// `[longitude, latitude]` with an optional altitude
+fn is_position(value: &Value) -> bool {
+    matches!(value, Value::Array(items)
+        if (2..=3).contains(&items.len()) && items.iter().all(|n| matches!(n, Value::Number(_))))
+}
+
+fn canonical_type(name: &str) -> Option<&'static str> {
+    GEOMETRY_TYPES
+        .iter()
+        .map(|(known, _)| *known)
+        .chain(OTHER_TYPES)
+        .find(|known| known.eq_ignore_ascii_case(name))
+}
+
+fn point(position: Value) -> Value {
+    Value::Object(vec![
+        ("type".to_string(), Value::String("Point".to_string())),
+        ("coordinates".to_string(), position),
+    ])
+}
+
+fn point_feature(position: Value) -> Value {
+    Value::Object(vec![
+        ("type".to_string(), Value::String("Feature".to_string())),
+        ("geometry".to_string(), point(position)),
+        ("properties".to_string(), Value::Object(Vec::new())),
+    ])
+}
+
+fn type_of(entries: &[(String, Value)]) -> Option<&str> {
+    match member(entries, "type") {
+        Some(Value::String(name)) => Some(name),
+        _ => None,
+    }
+}
+
+fn has_member(entries: &[(String, Value)], name: &str) -> bool {
+    member(entries, name).is_some()
+}
+
+fn member<'a>(entries: &'a [(String, Value)], name: &str) -> Option<&'a Value> {
+    entries.iter().find(|(key, _)| key == name).map(|(_, v)| v)
+}
+
+fn member_mut<'a>(entries: &'a mut [(String, Value)], name: &str) -> Option<&'a mut Value> {
+    entries
+        .iter_mut()
+        .find(|(key, _)| key == name)
+        .map(|(_, v)| v)
+}
+
+/// JSON Pointer to the member `name` of the value at `path`
+fn child(path: &str, name: &str) -> String {
+    format!("{}/{}", path, name.replace('~', "~0").replace('/', "~1"))
+}
+
+fn violation(path: &str, message: &str) -> String {
+    format!("{}: {}", if path.is_empty() { "/" } else { path }, message)
+}
@@ -3,17 +3,46 @@
 //! Provides comprehensive JSON repair functionality with multiple strategies
 //! for fixing common JSON issues from LLM outputs.
 
+use crate::cache::RepairCache;
+use crate::convert::{Value, parse_json, write_json};
 use crate::error::Result;
+use crate::json_util::json_string;
+use crate::lint::{LintFinding, LintSeverity, findings_from_edits};
+use crate::repairer_base::{BaseOptions, DEFAULT_MAX_DEPTH};
+use crate::report::{RemovedSpan, RepairReport, SafetyMode, Trivia, TriviaKind};
 use crate::traits::{Repair, RepairStrategy, Validator};
 #[cfg(not(feature = "strict"))]
 use crate::json_util::{is_valid_json, validate_json_errors};
-use regex::Regex;
-use std::sync::OnceLock;
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+use std::sync::{Arc, OnceLock};
 
 // ============================================================================
 // JSON Validator
 // ============================================================================
 
+/// Nesting depth at which serde_json stops parsing
+#[cfg(feature = "strict")]
+const SERDE_JSON_MAX_DEPTH: usize = 128;
+
+/// Parse `content` with serde_json, reporting its recursion limit as
+/// `RepairError::MaxDepthExceeded` like the rest of the crate
+#[cfg(feature = "strict")]
+fn parse_strict(content: &str) -> Result<serde_json::Value> {
+    serde_json::from_str(content.trim()).map_err(|e| {
+        if e.to_string().starts_with("recursion limit exceeded") {
+            crate::error::RepairError::MaxDepthExceeded {
+                depth: crate::repairer_base::nesting_depth(content),
+                limit: SERDE_JSON_MAX_DEPTH,
+            }
+        } else {
+            e.into()
+        }
+    })
+}
+
 /// JSON validator
 pub struct JsonValidator;
 
@@ -21,7 +50,7 @@ impl Validator for JsonValidator {
     fn is_valid(&self, content: &str) -> bool {
         #[cfg(feature = "strict")]
         {
-            serde_json::from_str::<serde_json::Value>(content.trim()).is_ok()
+            parse_strict(content).is_ok()
         }
         #[cfg(not(feature = "strict"))]
         {
@@ -32,7 +61,7 @@ impl Validator for JsonValidator {
     fn validate(&self, content: &str) -> Vec<String> {
         #[cfg(feature = "strict")]
         {
-            match serde_json::from_str::<serde_json::Value>(content.trim()) {
+            match parse_strict(content) {
                 Ok(_) => vec![],
                 Err(e) => vec![e.to_string()],
             }
@@ -81,12 +110,12 @@ pub struct RegexCache {
     pub malformed_numbers_leading_zeros: Regex,
     pub malformed_numbers_trailing_dots: Regex,
     pub malformed_numbers_multiple_dots: Regex,
-    pub malformed_numbers_scientific: Regex,
     pub boolean_values: Regex,
     pub boolean_variants: Regex,
     pub null_values: Regex,
     pub undefined_values: Regex,
     pub smart_quotes: Regex,
+    pub bare_word_objects: Regex,
 }
 
 impl RegexCache {
@@ -99,12 +128,12 @@ impl RegexCache {
             malformed_numbers_leading_zeros: Regex::new(r#"\b0+(\d+)\b"#)?,
             malformed_numbers_trailing_dots: Regex::new(r#"\b(\d+)\.\s*([,}\]])"#)?,
             malformed_numbers_multiple_dots: Regex::new(r#"\b(\d+\.\d+)\.(\d+)\b"#)?,
-            malformed_numbers_scientific: Regex::new(r#"\b(\d+)\s*(\+|-)\s*(\d+)\b"#)?,
             boolean_values: Regex::new(r#"\b(True|False|TRUE|FALSE|true|false)\b"#)?,
             boolean_variants: Regex::new(r#"\b(yes|no|on|off|Yes|No|On|Off|YES|NO|ON|OFF)\b"#)?,
             null_values: Regex::new(r#"\b(Null|NULL|null|None|NONE|none|nil|NIL)\b"#)?,
             undefined_values: Regex::new(r#"\b(undefined|Undefined|UNDEFINED)\b"#)?,
             smart_quotes: Regex::new(r#"[\u201c\u201d\u2018\u2019]"#)?,
+            bare_word_objects: Regex::new(r#"\{([^{}\[\]:"'`]*)\}"#)?,
         })
     }
 }
@@ -115,109 +144,347 @@ pub fn get_regex_cache() -> &'static RegexCache {
     REGEX_CACHE.get_or_init(|| RegexCache::new().expect("Failed to initialize regex cache"))
 }
 
+// ============================================================================
+// Tokenizer
+// ============================================================================
+
+/// Kind of a [`Token`] produced by [`tokenize`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// `{`
+    OpenBrace,
+    /// `}`
+    CloseBrace,
+    /// `[`
+    OpenBracket,
+    /// `]`
+    CloseBracket,
+    /// `:`
+    Colon,
+    /// `,`
+    Comma,
+    /// A `"` string, or a `'` string closed on its line where a value or key may
+    /// start; an unterminated `"` string runs to the end of the input
+    String,
+    /// A run starting with a digit (or a sign or `.` before one), kept whole even
+    /// if malformed (`1.2.3`, `0x1F`)
+    Number,
+    /// A bare word: `true`, `None`, an unquoted key
+    Ident,
+    Whitespace,
+    /// A `//` comment up to its newline, or a `/* */` comment
+    Comment,
+    /// Any other single character (`=`, `;`, a stray or unterminated `'`)
+    Other,
+}
+
+/// A lexical token of possibly malformed JSON, located by byte span
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// What the token is
+    pub kind: TokenKind,
+    /// Byte range of the token in the tokenized text
+    pub span: Range<usize>,
+}
+
+/// Split `content` into tokens covering it end to end, without failing on
+/// malformed input. Strings honour `\` escapes, so brackets, commas and
+/// comment markers inside them are part of the string.
+pub fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut at = 0;
+    while let Some(ch) = content[at..].chars().next() {
+        let rest = &content[at..];
+        let (kind, len) = match ch {
+            '{' => (TokenKind::OpenBrace, 1),
+            '}' => (TokenKind::CloseBrace, 1),
+            '[' => (TokenKind::OpenBracket, 1),
+            ']' => (TokenKind::CloseBracket, 1),
+            ':' => (TokenKind::Colon, 1),
+            ',' => (TokenKind::Comma, 1),
+            '"' => (TokenKind::String, quoted_len(rest)),
+            '\'' if string_may_start(&tokens) => {
+                // Unlike `"`, an unterminated `'` may be an apostrophe, so it does
+                // not swallow the rest of the input
+                let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+                closed_quote_len(line).map_or((TokenKind::Other, 1), |len| (TokenKind::String, len))
+            }
+            '/' if rest.starts_with("//") => {
+                (TokenKind::Comment, rest.find('\n').unwrap_or(rest.len()))
+            }
+            '/' if rest.starts_with("/*") => (
+                TokenKind::Comment,
+                rest[2..].find("*/").map_or(rest.len(), |n| n + 4),
+            ),
+            c if c.is_whitespace() => (TokenKind::Whitespace, run_len(rest, char::is_whitespace)),
+            c if c.is_ascii_digit()
+                || (matches!(c, '-' | '+' | '.')
+                    && rest[1..].starts_with(|d: char| d.is_ascii_digit())) =>
+            {
+                (TokenKind::Number, number_len(rest))
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => (
+                TokenKind::Ident,
+                run_len(rest, |c| c.is_alphanumeric() || c == '_' || c == '$'),
+            ),
+            c => (TokenKind::Other, c.len_utf8()),
+        };
+        tokens.push(Token {
+            kind,
+            span: at..at + len,
+        });
+        at += len;
+    }
+    tokens
+}
+
+/// Length of the leading run of `text` whose chars satisfy `pred`
+fn run_len(text: &str, pred: impl Fn(char) -> bool) -> usize {
+    text.find(|c| !pred(c)).unwrap_or(text.len())
+}
+
+/// Length of the string literal opening `text`, up to and including its closing
+/// quote (or to the end of `text` if it is never closed)
+fn quoted_len(text: &str) -> usize {
+    closed_quote_len(text).unwrap_or(text.len())
+}
+
+/// Length of the string literal opening `text` up to and including its closing
+/// quote, or `None` if it is never closed
+fn closed_quote_len(text: &str) -> Option<usize> {
+    let quote = text.chars().next().unwrap_or('"');
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if c == quote => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Length of the number opening `text`: digits, letters, `.` and `_`, plus a sign
+/// right after an exponent marker
+fn number_len(text: &str) -> usize {
+    let mut prev = None;
+    for (i, c) in text.char_indices() {
+        let continues = c.is_alphanumeric()
+            || c == '.'
+            || c == '_'
+            || (matches!(c, '-' | '+') && (i == 0 || matches!(prev, Some('e' | 'E'))));
+        if !continues {
+            return i;
+        }
+        prev = Some(c);
+    }
+    text.len()
+}
+
+/// Whether a `'` after `tokens` opens a string: at the start, or after `{`, `[`,
+/// `:` or `,` (an apostrophe in a bare word does not)
+fn string_may_start(tokens: &[Token]) -> bool {
+    tokens
+        .iter()
+        .rev()
+        .find(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Comment))
+        .is_none_or(|t| {
+            matches!(
+                t.kind,
+                TokenKind::OpenBrace | TokenKind::OpenBracket | TokenKind::Colon | TokenKind::Comma
+            )
+        })
+}
+
 // ============================================================================
 // Repair Strategies
 // ============================================================================
 
-/// Strategy to strip trailing content after JSON closes
+/// The non-blank text of `content` before and after `kept`, as removals
+fn removed_around(content: &str, kept: Range<usize>, reason: &str) -> Vec<RemovedSpan> {
+    [0..kept.start, kept.end..content.len()]
+        .into_iter()
+        .filter_map(|range| {
+            let text = &content[range.clone()];
+            let start = range.start + text.len() - text.trim_start().len();
+            let text = text.trim();
+            (!text.is_empty()).then(|| RemovedSpan {
+                byte_range: start..start + text.len(),
+                text: text.to_string(),
+                reason: reason.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Strategy to strip trailing content after the root JSON value closes
+///
+/// The root may be an object, an array or a top-level scalar (string, number,
+/// `true`/`false`/`null`). Content that looks like a continuation of the document
+/// (`,`, `:`, another `{`/`[` or a string) is left for later strategies.
 pub struct StripTrailingContentStrategy;
 
+impl StripTrailingContentStrategy {
+    /// Byte range of the trailing content after the root value, if there is any to strip
+    pub fn trailing_range(content: &str) -> Option<Range<usize>> {
+        let start = content.len() - content.trim_start().len();
+        let root = &content[start..];
+        let is_container = root.starts_with(['{', '[']);
+        let end = start
+            + if is_container {
+                StripSurroundingProseStrategy::matching_close(root)?
+            } else if root.starts_with('"') {
+                Self::string_end(root)?
+            } else {
+                Self::literal_end(root)?
+            };
+
+        let rest = &content[end..];
+        let junk = rest.trim();
+        if junk.is_empty() || junk.starts_with([',', ':', '{', '[', '"']) {
+            return None;
+        }
+        // Words after a closed container may be a TOML/INI body under a `[section]`
+        // header; prose of that kind is left to `StripSurroundingProseStrategy`
+        if is_container && junk.starts_with(char::is_alphanumeric) {
+            return None;
+        }
+        let junk_start = end + (rest.len() - rest.trim_start().len());
+        Some(junk_start..junk_start + junk.len())
+    }
+
+    /// Byte offset just past the string literal at the start of `body`
+    fn string_end(body: &str) -> Option<usize> {
+        let mut escaped = false;
+        for (i, ch) in body.char_indices().skip(1) {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                return Some(i + 1);
+            }
+        }
+        None
+    }
+
+    /// Byte offset just past a number or `true`/`false`/`null` at the start of `body`
+    fn literal_end(body: &str) -> Option<usize> {
+        let end = body
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-')))
+            .unwrap_or(body.len());
+        let token = &body[..end];
+        let is_number = token.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+            && token.parse::<f64>().is_ok();
+        (is_number || matches!(token, "true" | "false" | "null")).then_some(end)
+    }
+}
+
 impl RepairStrategy for StripTrailingContentStrategy {
     fn name(&self) -> &str {
         "StripTrailingContent"
     }
 
+    fn description(&self) -> &str {
+        "Strip trailing content after the root JSON value closes"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"a": 1} // done"#)
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
-        let mut result = String::new();
-        let mut brace_count = 0;
-        let mut bracket_count = 0;
-        let mut in_string = false;
-        let mut escape_next = false;
-        let mut found_json_end = false;
-        let chars: Vec<char> = content.chars().collect();
-        let len = chars.len();
+        Ok(match Self::trailing_range(content) {
+            Some(range) => content[..range.start].trim_end().to_string(),
+            None => content.to_string(),
+        })
+    }
 
-        for i in 0..len {
-            let ch = chars[i];
+    fn apply_with_log(&self, content: &str) -> Result<(String, Option<String>)> {
+        let description = Self::trailing_range(content).map(|range| {
+            format!(
+                "removed trailing content at bytes {}..{}: {:?}",
+                range.start,
+                range.end,
+                &content[range.clone()]
+            )
+        });
+        Ok((self.apply(content)?, description))
+    }
 
-            if escape_next {
-                result.push(ch);
-                escape_next = false;
-                continue;
-            }
+    fn removals(&self, content: &str) -> Vec<RemovedSpan> {
+        Self::trailing_range(content)
+            .map(|range| RemovedSpan {
+                text: content[range.clone()].to_string(),
+                byte_range: range,
+                reason: "trailing content".to_string(),
+            })
+            .into_iter()
+            .collect()
+    }
 
-            match ch {
-                '\\' if in_string => {
-                    result.push(ch);
-                    escape_next = true;
-                }
-                '"' => {
-                    result.push(ch);
-                    in_string = !in_string;
-                }
-                '{' if !in_string => {
-                    result.push(ch);
-                    brace_count += 1;
-                }
-                '}' if !in_string => {
-                    result.push(ch);
-                    brace_count -= 1;
-                    if brace_count == 0 && bracket_count == 0 {
-                        let mut j = i + 1;
-                        while j < len
-                            && (chars[j] == ' '
-                                || chars[j] == '\n'
-                                || chars[j] == '\t'
-                                || chars[j] == '\r')
-                        {
-                            j += 1;
-                        }
+    fn priority(&self) -> u8 {
+        100
+    }
+}
 
-                        if j < len && (chars[j] == ',' || chars[j] == '{' || chars[j] == '[') {
-                            found_json_end = false;
-                        } else if j >= len || (!chars[j].is_alphanumeric() && chars[j] != '"') {
-                            found_json_end = true;
-                        }
-                    }
-                }
-                '[' if !in_string => {
-                    result.push(ch);
-                    bracket_count += 1;
-                }
-                ']' if !in_string => {
-                    result.push(ch);
-                    bracket_count -= 1;
-                    if brace_count == 0 && bracket_count == 0 {
-                        let mut j = i + 1;
-                        while j < len
-                            && (chars[j] == ' '
-                                || chars[j] == '\n'
-                                || chars[j] == '\t'
-                                || chars[j] == '\r')
-                        {
-                            j += 1;
-                        }
+/// Strategy to convert Python-style triple-quoted strings (`"""..."""`, `'''...'''`)
+/// into JSON strings, escaping the embedded newlines and quotes.
+///
+/// The text between the delimiters is taken literally, as in a raw string. Runs
+/// first, since the extra quotes throw off every other strategy's string tracking.
+pub struct FixTripleQuotedStringsStrategy;
 
-                        if j < len && (chars[j] == ',' || chars[j] == '{' || chars[j] == '[') {
-                            found_json_end = false;
-                        } else if j >= len || (!chars[j].is_alphanumeric() && chars[j] != '"') {
-                            found_json_end = true;
-                        }
-                    }
-                }
-                _ => {
-                    if !found_json_end {
-                        result.push(ch);
-                    }
+impl RepairStrategy for FixTripleQuotedStringsStrategy {
+    fn name(&self) -> &str {
+        "FixTripleQuotedStrings"
+    }
+
+    fn description(&self) -> &str {
+        "Convert Python-style triple-quoted strings into JSON strings"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("{\"text\": \"\"\"line \"one\"\nline two\"\"\"}")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = String::with_capacity(content.len());
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut rest = content;
+
+        while let Some(ch) = rest.chars().next() {
+            if !in_string
+                && let Some(delimiter) = ["\"\"\"", "'''"].into_iter().find(|d| rest.starts_with(d))
+                && let Some(len) = rest[3..].find(delimiter)
+            {
+                result.push_str(&json_string(&rest[3..3 + len]));
+                rest = &rest[3 + len + 3..];
+                continue;
+            }
+
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == '"' {
+                    in_string = false;
                 }
+            } else if ch == '"' {
+                in_string = true;
             }
+            result.push(ch);
+            rest = &rest[ch.len_utf8()..];
         }
 
         Ok(result)
     }
 
     fn priority(&self) -> u8 {
-        100
+        101
     }
 }
 
@@ -229,16 +496,80 @@ impl RepairStrategy for FixTrailingCommasStrategy {
         "FixTrailingCommas"
     }
 
+    fn description(&self) -> &str {
+        "Fix trailing commas"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"a": [1, 2,],}"#)
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_regex_cache();
         Ok(cache.trailing_commas.replace_all(content, "$1").to_string())
     }
 
+    fn apply_with_log(&self, content: &str) -> Result<(String, Option<String>)> {
+        let removed = get_regex_cache().trailing_commas.find_iter(content).count();
+        let description = (removed > 0).then(|| format!("removed {} trailing comma(s)", removed));
+        Ok((self.apply(content)?, description))
+    }
+
     fn priority(&self) -> u8 {
         90
     }
 }
 
+/// Strategy to remove empty array/object elements (`[1,,2]`, `[,1]`), leaving
+/// the trailing `[1,]` case to [`FixTrailingCommasStrategy`].
+///
+/// Commas inside single- or double-quoted strings are left alone.
+pub struct RemoveEmptyElementsStrategy;
+
+impl RepairStrategy for RemoveEmptyElementsStrategy {
+    fn name(&self) -> &str {
+        "RemoveEmptyElements"
+    }
+
+    fn description(&self) -> &str {
+        "Remove empty elements between commas"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"a": [1,, 2], , "b": 3}"#)
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = String::with_capacity(content.len());
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+
+        for ch in content.chars() {
+            if let Some(q) = quote {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == q {
+                    quote = None;
+                }
+            } else if ch == '"' || ch == '\'' {
+                quote = Some(ch);
+            } else if ch == ',' && matches!(result.trim_end().chars().last(), Some(',' | '[' | '{'))
+            {
+                continue;
+            }
+            result.push(ch);
+        }
+
+        Ok(result)
+    }
+
+    fn priority(&self) -> u8 {
+        91
+    }
+}
+
 /// Strategy to fix single quotes
 pub struct FixSingleQuotesStrategy;
 
@@ -247,6 +578,14 @@ impl RepairStrategy for FixSingleQuotesStrategy {
         "FixSingleQuotes"
     }
 
+    fn description(&self) -> &str {
+        "Fix single quotes"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("{'name': 'Ann'}")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_regex_cache();
         Ok(cache
@@ -268,6 +607,14 @@ impl RepairStrategy for AddMissingQuotesStrategy {
         "AddMissingQuotes"
     }
 
+    fn description(&self) -> &str {
+        "Add missing quotes around keys"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{name: "Ann"}"#)
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_regex_cache();
         Ok(cache
@@ -289,32 +636,369 @@ impl RepairStrategy for FixMalformedNumbersStrategy {
         "FixMalformedNumbers"
     }
 
+    fn description(&self) -> &str {
+        "Fix malformed numbers"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"n": 007}"#)
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_regex_cache();
-        let mut result = content.to_string();
+        let result = map_outside_strings(content, |segment| {
+            let segment = strip_leading_zeros(segment);
+            let segment = cache
+                .malformed_numbers_trailing_dots
+                .replace_all(&segment, "$1$2");
+            cache
+                .malformed_numbers_multiple_dots
+                .replace_all(&segment, "$1$2")
+                .into_owned()
+        });
+
+        Ok(fix_number_exponents(&result))
+    }
 
-        result = cache
-            .malformed_numbers_leading_zeros
-            .replace_all(&result, "$1")
-            .to_string();
-        result = cache
-            .malformed_numbers_trailing_dots
-            .replace_all(&result, "$1$2")
-            .to_string();
-        result = cache
-            .malformed_numbers_multiple_dots
-            .replace_all(&result, "$1$2")
-            .to_string();
-        result = cache
-            .malformed_numbers_scientific
-            .replace_all(&result, "$1e$2$3")
-            .to_string();
+    fn priority(&self) -> u8 {
+        75
+    }
+}
 
-        Ok(result)
+/// Apply `fix` to the parts of `content` outside JSON string literals
+fn map_outside_strings(content: &str, mut fix: impl FnMut(&str) -> String) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut segment_start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, ch) in content.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                in_string = false;
+                result.push_str(&content[segment_start..=i]);
+                segment_start = i + 1;
+            }
+        } else if ch == '"' {
+            result.push_str(&fix(&content[segment_start..i]));
+            in_string = true;
+            segment_start = i;
+        }
+    }
+
+    // An unterminated string runs to the end and is kept verbatim
+    if in_string {
+        result.push_str(&content[segment_start..]);
+    } else {
+        result.push_str(&fix(&content[segment_start..]));
+    }
+    result
+}
+
+/// Whether the number at `range` of `segment` stands alone rather than being
+/// part of a version, date or time such as `01.02`, `2024-01-05` or `10:05`
+fn is_standalone_number(segment: &str, range: Range<usize>) -> bool {
+    let joined = |c: Option<char>| matches!(c, Some('.' | ':' | '-' | '/'));
+    let before = &segment[..range.start];
+    let is_sign = before.ends_with('-')
+        && !before[..before.len() - 1].ends_with(|c: char| c.is_alphanumeric());
+    (is_sign || !joined(before.chars().next_back())) && !joined(segment[range.end..].chars().next())
+}
+
+/// Strip leading zeros from bare integers (`007` → `7`), leaving version- and
+/// date-like tokens alone
+fn strip_leading_zeros(segment: &str) -> String {
+    get_regex_cache()
+        .malformed_numbers_leading_zeros
+        .replace_all(segment, |caps: &Captures| {
+            let number = caps.get(0).map_or(0..0, |m| m.range());
+            if is_standalone_number(segment, number) {
+                caps[1].to_string()
+            } else {
+                caps[0].to_string()
+            }
+        })
+        .into_owned()
+}
+
+/// Fix number tokens outside strings: drop truncated exponents (`1e` → `1`,
+/// `1.5e+` → `1.5`) and lowercase the exponent marker (`3E10` → `3e10`).
+/// Anything that isn't a whole number token, like `a + b` or `v2e`, is left alone.
+fn fix_number_exponents(content: &str) -> String {
+    let bytes = content.as_bytes();
+    let digits_end = |from: usize| {
+        from + bytes[from..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+    let mut result = String::with_capacity(content.len());
+    let mut copied = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+            i += 1;
+            continue;
+        }
+
+        let at_token_start = i == 0
+            || !(bytes[i - 1].is_ascii_alphanumeric() || matches!(bytes[i - 1], b'_' | b'.'));
+        let starts_number =
+            b.is_ascii_digit() || (b == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit));
+        if b == b'"' {
+            in_string = true;
+            i += 1;
+        } else if at_token_start && starts_number {
+            let mut end = digits_end(i + 1);
+            if bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+                end = digits_end(end + 1);
+            }
+            if !matches!(bytes.get(end), Some(b'e' | b'E')) {
+                i = end;
+                continue;
+            }
+
+            let mut exponent = end + 1;
+            if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+                exponent += 1;
+            }
+            let exponent_end = digits_end(exponent);
+            let whole_token = !bytes
+                .get(exponent_end)
+                .is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'_');
+            if whole_token {
+                result.push_str(&content[copied..end]);
+                if exponent_end > exponent {
+                    result.push('e');
+                    result.push_str(&content[end + 1..exponent_end]);
+                }
+                copied = exponent_end;
+            }
+            i = exponent_end;
+        } else {
+            i += 1;
+        }
+    }
+
+    result.push_str(&content[copied..]);
+    result
+}
+
+/// Replace string values that spell a JSON literal (`"true"`, `"False"`, `"null"`)
+/// with the literal itself. Keys and a top-level string are left alone.
+fn unquote_literals(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut copied = 0;
+    let mut string_start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, ch) in content.char_indices() {
+        if !in_string {
+            if ch == '"' {
+                in_string = true;
+                string_start = i;
+            }
+            continue;
+        }
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == '"' {
+            in_string = false;
+            let text = &content[string_start + 1..i];
+            let is_value = content[..string_start]
+                .trim_end()
+                .ends_with([':', '[', ','])
+                && !content[i + 1..].trim_start().starts_with(':');
+            if is_value
+                && let Some(literal) = ["true", "false", "null"]
+                    .into_iter()
+                    .find(|literal| text.eq_ignore_ascii_case(literal))
+            {
+                result.push_str(&content[copied..string_start]);
+                result.push_str(literal);
+                copied = i + 1;
+            }
+        }
+    }
+
+    result.push_str(&content[copied..]);
+    result
+}
+
+/// Insert a comma after the last member of every non-empty object and array
+/// (`{"a": [1, 2]}` -> `{"a": [1, 2,],}`), keeping the whitespace before the closer
+fn add_trailing_commas(content: &str) -> String {
+    let mut insert_at = Vec::new();
+    // Byte offset just past the last non-whitespace char outside strings, and that char
+    let mut last: Option<(usize, char)> = None;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, ch) in content.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                in_string = false;
+                last = Some((i + 1, ch));
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            '}' | ']' => {
+                if let Some((end, prev)) = last
+                    && !matches!(prev, '{' | '[' | ',')
+                {
+                    insert_at.push(end);
+                }
+                last = Some((i + 1, ch));
+            }
+            _ if ch.is_whitespace() => {}
+            _ => last = Some((i + ch.len_utf8(), ch)),
+        }
+    }
+
+    let mut result = String::with_capacity(content.len() + insert_at.len());
+    let mut copied = 0;
+    for at in insert_at {
+        result.push_str(&content[copied..at]);
+        result.push(',');
+        copied = at;
+    }
+    result.push_str(&content[copied..]);
+    result
+}
+
+/// Strategy to quote zero-padded integers in value position (`{"zip": 07001}` ->
+/// `{"zip": "07001"}`), keeping identifiers intact instead of stripping their zeros.
+///
+/// Not part of the default pipeline; enabled by
+/// [`JsonRepairer::with_numeric_ids_as_strings`]. Runs before
+/// [`FixMalformedNumbersStrategy`].
+pub struct QuoteNumericIdsStrategy;
+
+impl RepairStrategy for QuoteNumericIdsStrategy {
+    fn name(&self) -> &str {
+        "QuoteNumericIds"
+    }
+
+    fn description(&self) -> &str {
+        "Quote zero-padded integers in value position (opt-in)"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"zip": 07001}"#)
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let cache = get_regex_cache();
+        Ok(map_outside_strings(content, |segment| {
+            cache
+                .malformed_numbers_leading_zeros
+                .replace_all(segment, |caps: &Captures| {
+                    let number = caps.get(0).map_or(0..0, |m| m.range());
+                    let before = segment[..number.start].trim_end();
+                    let after = segment[number.end..].trim_start();
+                    let is_value = before.ends_with([':', '[', ','])
+                        && !after.starts_with(':')
+                        && is_standalone_number(segment, number);
+                    if is_value {
+                        format!("\"{}\"", &caps[0])
+                    } else {
+                        caps[0].to_string()
+                    }
+                })
+                .into_owned()
+        }))
     }
 
     fn priority(&self) -> u8 {
-        75
+        76
+    }
+}
+
+/// Strategy to read an object made only of words (`{name John age 30}`) as
+/// alternating keys and values (`{"name": "John", "age": 30}`).
+///
+/// Speculative, so not part of the default pipeline; enabled by
+/// [`JsonRepairer::with_aggressive`]. Only objects without colons, quotes or
+/// nested brackets whose words pair up evenly under identifier keys are rewritten.
+pub struct PairBareWordsStrategy;
+
+impl RepairStrategy for PairBareWordsStrategy {
+    fn name(&self) -> &str {
+        "PairBareWords"
+    }
+
+    fn description(&self) -> &str {
+        "Pair the words of a colon-less object into keys and values (opt-in)"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("{name John age 30}")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let cache = get_regex_cache();
+        Ok(cache
+            .bare_word_objects
+            .replace_all(content, |caps: &Captures| {
+                let words: Vec<&str> = caps[1]
+                    .split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|word| !word.is_empty())
+                    .collect();
+                let is_key = |word: &&str| {
+                    word.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                        && word
+                            .chars()
+                            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-'))
+                };
+                if words.is_empty()
+                    || !words.len().is_multiple_of(2)
+                    || !words.iter().step_by(2).all(is_key)
+                {
+                    return caps[0].to_string();
+                }
+
+                let members: Vec<String> = words
+                    .chunks(2)
+                    .map(|pair| {
+                        let value = if crate::json_util::is_valid_json(pair[1]) {
+                            pair[1].to_string()
+                        } else {
+                            json_string(pair[1])
+                        };
+                        format!("{}: {}", json_string(pair[0]), value)
+                    })
+                    .collect();
+                format!("{{{}}}", members.join(", "))
+            })
+            .into_owned())
+    }
+
+    fn priority(&self) -> u8 {
+        89
     }
 }
 
@@ -326,6 +1010,14 @@ impl RepairStrategy for FixBooleanNullStrategy {
         "FixBooleanNull"
     }
 
+    fn description(&self) -> &str {
+        "Fix boolean and null values"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"ok": True, "v": None}"#)
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_regex_cache();
         let mut result = content.to_string();
@@ -362,6 +1054,14 @@ impl RepairStrategy for FixSmartQuotesStrategy {
         "FixSmartQuotes"
     }
 
+    fn description(&self) -> &str {
+        "Normalize smart/curly quotes to straight quotes"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("{“name”: “Ann”}")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_regex_cache();
         Ok(cache
@@ -376,6 +1076,81 @@ impl RepairStrategy for FixSmartQuotesStrategy {
             .to_string())
     }
 
+    fn apply_with_log(&self, content: &str) -> Result<(String, Option<String>)> {
+        let result = self.apply(content)?;
+        let converted = get_regex_cache()
+            .boolean_variants
+            .find_iter(content)
+            .count();
+        let description = (result != content)
+            .then(|| format!("converted {} yes/no/on/off value(s) to booleans", converted));
+        Ok((result, description))
+    }
+
+    fn priority(&self) -> u8 {
+        90
+    }
+}
+
+/// Strategy to convert JavaScript template-literal style strings (`` `key` ``)
+/// into JSON strings, escaping any double quotes inside.
+///
+/// Backticks inside double-quoted strings are left alone, as are runs of three
+/// or more (Markdown code fences).
+pub struct FixBacktickQuotesStrategy;
+
+impl RepairStrategy for FixBacktickQuotesStrategy {
+    fn name(&self) -> &str {
+        "FixBacktickQuotes"
+    }
+
+    fn description(&self) -> &str {
+        "Convert backtick-quoted keys and values into JSON strings"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("{`name`: `Ann \"Annie\" Lee`}")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = String::with_capacity(content.len());
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut rest = content;
+
+        while let Some(ch) = rest.chars().next() {
+            if !in_string && ch == '`' {
+                let run = rest.len() - rest.trim_start_matches('`').len();
+                if run < 3
+                    && let Some(len) = rest[1..].find('`')
+                {
+                    result.push_str(&json_string(&rest[1..1 + len]));
+                    rest = &rest[1 + len + 1..];
+                } else {
+                    result.push_str(&rest[..run]);
+                    rest = &rest[run..];
+                }
+                continue;
+            }
+
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == '"' {
+                    in_string = false;
+                }
+            } else if ch == '"' {
+                in_string = true;
+            }
+            result.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+
+        Ok(result)
+    }
+
     fn priority(&self) -> u8 {
         90
     }
@@ -389,6 +1164,14 @@ impl RepairStrategy for FixBooleanVariantsStrategy {
         "FixBooleanVariants"
     }
 
+    fn description(&self) -> &str {
+        "Recognize boolean variants (yes/no, on/off, 1/0 as bare words)"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"enabled": yes, "debug": off}"#)
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_regex_cache();
         Ok(cache
@@ -403,6 +1186,17 @@ impl RepairStrategy for FixBooleanVariantsStrategy {
             .to_string())
     }
 
+    fn apply_with_log(&self, content: &str) -> Result<(String, Option<String>)> {
+        let result = self.apply(content)?;
+        let converted = get_regex_cache()
+            .boolean_variants
+            .find_iter(content)
+            .count();
+        let description = (result != content)
+            .then(|| format!("converted {} yes/no/on/off value(s) to booleans", converted));
+        Ok((result, description))
+    }
+
     fn priority(&self) -> u8 {
         68
     }
@@ -411,82 +1205,462 @@ impl RepairStrategy for FixBooleanVariantsStrategy {
 /// Strategy to extract JSON from surrounding prose/preamble
 pub struct ExtractJsonFromProseStrategy;
 
+impl ExtractJsonFromProseStrategy {
+    /// Byte range of `content` to keep, or `None` to leave it unchanged
+    fn kept_range(content: &str) -> Option<Range<usize>> {
+        let offset = content.len() - content.trim_start().len();
+        let trimmed = content.trim();
+
+        // If already starts with { or [, no extraction needed
+        if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            return Some(offset..offset + trimmed.len());
+        }
+
+        // Only extract if there's actual prose text before the JSON block.
+        // Find the first { or [ and check that preceding text is prose, not a JSON fragment.
+        let pos = trimmed.find('{').or_else(|| trimmed.find('['))?;
+        let prefix = &trimmed[..pos];
+        // Prose detection: prefix must NOT contain double quotes (JSON fragments always do)
+        // and must have 3+ consecutive alphabetic chars (a real word/sentence).
+        // This prevents false positives on streaming JSON chunks where key names
+        // like "name" or "profile" precede a nested {.
+        let has_prose = !prefix.contains('"')
+            && prefix
+                .split(|c: char| !c.is_alphabetic())
+                .any(|word| word.len() >= 3);
+
+        if !has_prose {
+            return None;
+        }
+
+        let extracted = &trimmed[pos..];
+        // Trim trailing non-JSON content
+        let mut brace_depth = 0i32;
+        let mut bracket_depth = 0i32;
+
+        for (i, ch) in extracted.char_indices() {
+            let closed = match ch {
+                '{' => {
+                    brace_depth += 1;
+                    false
+                }
+                '}' => {
+                    brace_depth -= 1;
+                    true
+                }
+                '[' => {
+                    bracket_depth += 1;
+                    false
+                }
+                ']' => {
+                    bracket_depth -= 1;
+                    true
+                }
+                _ => false,
+            };
+            if closed && brace_depth == 0 && bracket_depth == 0 {
+                let start = offset + pos;
+                return Some(start..start + i + 1);
+            }
+        }
+
+        // Only extract if we found a balanced JSON structure.
+        // If braces don't balance, this is a JSON fragment (e.g. from streaming), not prose+JSON.
+        None
+    }
+}
+
 impl RepairStrategy for ExtractJsonFromProseStrategy {
     fn name(&self) -> &str {
         "ExtractJsonFromProse"
     }
 
+    fn description(&self) -> &str {
+        "Extract JSON from surrounding prose/preamble"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"Here is the data: {"a": 1}"#)
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        Ok(match Self::kept_range(content) {
+            Some(range) => content[range].to_string(),
+            None => content.to_string(),
+        })
+    }
+
+    fn removals(&self, content: &str) -> Vec<RemovedSpan> {
+        Self::kept_range(content)
+            .map(|kept| removed_around(content, kept, "prose"))
+            .unwrap_or_default()
+    }
+
+    fn priority(&self) -> u8 {
+        95
+    }
+}
+
+/// Strategy to unwrap JSON returned inside markup: a CDATA section
+/// (`<![CDATA[{"a": 1}]]>`) or a `<pre>` / `<code>` element, nested or not.
+///
+/// Only unwraps when the markup comes before any `{`/`[` and its content is a
+/// JSON object or array, so HTML inside JSON strings is left alone. Entities such
+/// as `&quot;` in `<pre>` / `<code>` content are decoded; CDATA is taken verbatim.
+pub struct UnwrapMarkupStrategy;
+
+impl UnwrapMarkupStrategy {
+    /// Byte range of the JSON inside the markup, and whether its entities need decoding
+    fn kept_range(content: &str) -> Option<(Range<usize>, bool)> {
+        let mut range = 0..content.len();
+        let mut escaped = false;
+        loop {
+            let text = &content[range.clone()];
+            let trimmed = text.trim_start();
+            if trimmed.starts_with(['{', '[']) && range != (0..content.len()) {
+                return Some((range, escaped));
+            }
+            let (open, inner, is_cdata) = Self::wrapped(text)?;
+            if text[..open].contains(['{', '[']) {
+                return None;
+            }
+            escaped |= !is_cdata;
+            range = range.start + inner.start..range.start + inner.end;
+        }
+    }
+
+    /// Start and content range of the first CDATA section or `<pre>` / `<code>`
+    /// element in `text`, and whether it is CDATA. An unclosed wrapper runs to the end.
+    fn wrapped(text: &str) -> Option<(usize, Range<usize>, bool)> {
+        const CDATA_OPEN: &str = "<![CDATA[";
+        let lower = text.to_ascii_lowercase();
+
+        let cdata = lower.find("<![cdata[").map(|open| {
+            let start = open + CDATA_OPEN.len();
+            let end = text[start..].find("]]>").map_or(text.len(), |i| start + i);
+            (open, start..end, true)
+        });
+        let element = ["pre", "code"]
+            .into_iter()
+            .filter_map(|name| {
+                let open = lower
+                    .match_indices(&format!("<{name}"))
+                    .find_map(|(i, tag)| {
+                        let after = lower[i + tag.len()..].chars().next();
+                        after
+                            .is_some_and(|c| c == '>' || c.is_whitespace())
+                            .then_some(i)
+                    })?;
+                let start = open + lower[open..].find('>')? + 1;
+                let end = lower[start..]
+                    .find(&format!("</{name}>"))
+                    .map_or(text.len(), |i| start + i);
+                Some((open, start..end, false))
+            })
+            .min_by_key(|(open, _, _)| *open);
+
+        [cdata, element]
+            .into_iter()
+            .flatten()
+            .min_by_key(|(open, _, _)| *open)
+    }
+}
+
+/// Decode the entities HTML escapes JSON punctuation with
+fn decode_html_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#34;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+impl RepairStrategy for UnwrapMarkupStrategy {
+    fn name(&self) -> &str {
+        "UnwrapMarkup"
+    }
+
+    fn description(&self) -> &str {
+        "Unwrap JSON from CDATA sections and <pre> / <code> elements"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"<pre>{&quot;a&quot;: 1}</pre>"#)
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
+        Ok(match Self::kept_range(content) {
+            Some((range, true)) => decode_html_entities(content[range].trim()),
+            Some((range, false)) => content[range].trim().to_string(),
+            None => content.to_string(),
+        })
+    }
+
+    fn removals(&self, content: &str) -> Vec<RemovedSpan> {
+        Self::kept_range(content)
+            .map(|(kept, _)| removed_around(content, kept, "markup"))
+            .unwrap_or_default()
+    }
+
+    fn priority(&self) -> u8 {
+        102
+    }
+}
+
+/// Strategy to strip LLM chatter around a JSON payload
+///
+/// Discards prose before the first top-level `{`/`[` (e.g. `Sure! Here is the JSON:`)
+/// and after its matching close (e.g. `Let me know if...`). Brackets inside string
+/// literals are ignored. Runs before [`StripTrailingContentStrategy`], which then
+/// only has to deal with trailing junk that directly follows the structure.
+pub struct StripSurroundingProseStrategy;
+
+impl StripSurroundingProseStrategy {
+    /// Prose contains no JSON string quotes and at least one real word
+    fn is_prose(text: &str) -> bool {
+        !text.contains('"')
+            && text
+                .split(|c: char| !c.is_alphabetic())
+                .any(|word| word.chars().count() >= 3)
+    }
+
+    /// Byte range of `content` to keep, or `None` to leave it unchanged
+    fn kept_range(content: &str) -> Option<Range<usize>> {
+        let offset = content.len() - content.trim_start().len();
         let trimmed = content.trim();
+        let start = trimmed.find(['{', '['])?;
 
-        // If already starts with { or [, no extraction needed
-        if trimmed.starts_with('{') || trimmed.starts_with('[') {
-            return Ok(trimmed.to_string());
+        let prefix = &trimmed[..start];
+        let has_leading_prose = !prefix.trim().is_empty();
+        // A non-prose prefix is a JSON fragment (e.g. a streamed `"key": {`), leave it alone
+        if has_leading_prose && !Self::is_prose(prefix) {
+            return None;
         }
 
-        // Only extract if there's actual prose text before the JSON block.
-        // Find the first { or [ and check that preceding text is prose, not a JSON fragment.
-        if let Some(pos) = trimmed.find('{').or_else(|| trimmed.find('[')) {
-            let prefix = &trimmed[..pos];
-            // Prose detection: prefix must NOT contain double quotes (JSON fragments always do)
-            // and must have 3+ consecutive alphabetic chars (a real word/sentence).
-            // This prevents false positives on streaming JSON chunks where key names
-            // like "name" or "profile" precede a nested {.
-            let has_prose = !prefix.contains('"')
-                && prefix
-                    .split(|c: char| !c.is_alphabetic())
-                    .any(|word| word.len() >= 3);
-
-            if !has_prose {
-                return Ok(content.to_string());
-            }
-
-            let extracted = &trimmed[pos..];
-            // Trim trailing non-JSON content
-            let mut brace_depth = 0i32;
-            let mut bracket_depth = 0i32;
-            let mut end_pos = 0usize;
-
-            for (i, ch) in extracted.char_indices() {
-                match ch {
-                    '{' => brace_depth += 1,
-                    '}' => {
-                        brace_depth -= 1;
-                        if brace_depth == 0 && bracket_depth == 0 {
-                            end_pos = i + 1;
-                            break;
-                        }
+        let body = &trimmed[start..];
+        let body_start = offset + start;
+        let Some(end) = Self::matching_close(body) else {
+            // Truncated payload: drop the preamble and let later strategies close it
+            return has_leading_prose.then_some(body_start..offset + trimmed.len());
+        };
+
+        // More JSON after the close (`, {...}`, `}`) is not prose, keep it for other strategies
+        let suffix = body[end..].trim();
+        if suffix.is_empty()
+            || (Self::is_prose(suffix) && !suffix.starts_with([',', ':', '{', '}', '[', ']']))
+        {
+            Some(body_start..body_start + end)
+        } else {
+            has_leading_prose.then_some(body_start..offset + trimmed.len())
+        }
+    }
+
+    /// Byte offset just past the close matching the opening bracket at the start of `body`
+    fn matching_close(body: &str) -> Option<usize> {
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+
+        for (i, ch) in body.char_indices() {
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == '"' {
+                    in_string = false;
+                }
+                continue;
+            }
+            match ch {
+                '"' => in_string = true,
+                '{' | '[' => depth += 1,
+                '}' | ']' => {
+                    depth = depth.checked_sub(1)?;
+                    if depth == 0 {
+                        return Some(i + 1);
                     }
-                    '[' => bracket_depth += 1,
-                    ']' => {
-                        bracket_depth -= 1;
-                        if brace_depth == 0 && bracket_depth == 0 {
-                            end_pos = i + 1;
-                            break;
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+impl RepairStrategy for StripSurroundingProseStrategy {
+    fn name(&self) -> &str {
+        "StripSurroundingProse"
+    }
+
+    fn description(&self) -> &str {
+        "Strip LLM chatter around a JSON payload"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"Sure! {"a": 1} Let me know if you need more."#)
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        Ok(match Self::kept_range(content) {
+            Some(range) => content[range].to_string(),
+            None => content.to_string(),
+        })
+    }
+
+    fn removals(&self, content: &str) -> Vec<RemovedSpan> {
+        Self::kept_range(content)
+            .map(|kept| removed_around(content, kept, "prose"))
+            .unwrap_or_default()
+    }
+
+    fn priority(&self) -> u8 {
+        100
+    }
+}
+
+/// Strategy to drop closers with no open container to close (`{"a": [1]]}` -> `{"a": [1]}`)
+///
+/// Tracks open containers outside strings; a closer that matches none of them is
+/// extra. A closer that matches an outer container instead means inner closers are
+/// missing, and is left to [`FixMismatchedBracketsStrategy`]. Runs before it and
+/// [`AddMissingBracesStrategy`], so neither counts an extra closer as closing a container.
+pub struct RemoveExtraClosersStrategy;
+
+impl RemoveExtraClosersStrategy {
+    /// Byte offsets of the closers in `content` that match no open container
+    fn extra_closers(content: &str) -> Vec<usize> {
+        let mut extra = Vec::new();
+        // Expected closers of the open containers
+        let mut open: Vec<TokenKind> = Vec::new();
+
+        for token in tokenize(content) {
+            match token.kind {
+                TokenKind::OpenBrace => open.push(TokenKind::CloseBrace),
+                TokenKind::OpenBracket => open.push(TokenKind::CloseBracket),
+                TokenKind::CloseBrace | TokenKind::CloseBracket => {
+                    match open.iter().rposition(|&expected| expected == token.kind) {
+                        Some(depth) if depth + 1 == open.len() => {
+                            open.pop();
                         }
+                        // Inner containers are missing their closers; FixMismatchedBrackets decides
+                        Some(_) => {}
+                        None => extra.push(token.span.start),
                     }
-                    _ => {}
                 }
+                _ => {}
             }
+        }
+        extra
+    }
+}
+
+impl RepairStrategy for RemoveExtraClosersStrategy {
+    fn name(&self) -> &str {
+        "RemoveExtraClosers"
+    }
+
+    fn description(&self) -> &str {
+        "Drop closers with no open container to close"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"a": [1, 2]]}"#)
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = content.to_string();
+        // Closers are one byte, so removing back to front keeps the offsets valid
+        for at in Self::extra_closers(content).into_iter().rev() {
+            result.remove(at);
+        }
+        Ok(result)
+    }
+
+    fn removals(&self, content: &str) -> Vec<RemovedSpan> {
+        Self::extra_closers(content)
+            .into_iter()
+            .map(|at| RemovedSpan {
+                byte_range: at..at + 1,
+                text: content[at..at + 1].to_string(),
+                reason: "extra closer".to_string(),
+            })
+            .collect()
+    }
+
+    fn priority(&self) -> u8 {
+        84
+    }
+}
+
+/// Strategy to fix closers of the wrong type (`{"a": 1]` -> `{"a": 1}`, `[1, 2}` -> `[1, 2]`)
+///
+/// Tracks open containers outside strings and replaces a closer that doesn't
+/// match the innermost one. Closers missing at the end (`{"a": [1, 2}` becomes
+/// `{"a": [1, 2]`) are left to [`AddMissingBracesStrategy`].
+pub struct FixMismatchedBracketsStrategy;
+
+impl RepairStrategy for FixMismatchedBracketsStrategy {
+    fn name(&self) -> &str {
+        "FixMismatchedBrackets"
+    }
 
-            // Only extract if we found a balanced JSON structure.
-            // If braces don't balance, this is a JSON fragment (e.g. from streaming), not prose+JSON.
-            if end_pos > 0 {
-                return Ok(extracted[..end_pos].to_string());
+    fn description(&self) -> &str {
+        "Fix closers of the wrong type"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"a": [1, 2}}"#)
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = String::with_capacity(content.len());
+        // Expected closers of the open containers
+        let mut open: Vec<char> = Vec::new();
+        let mut in_string = false;
+        let mut escaped = false;
+
+        for ch in content.chars() {
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == '"' {
+                    in_string = false;
+                }
+                result.push(ch);
+                continue;
             }
 
-            return Ok(content.to_string());
+            match ch {
+                '"' => in_string = true,
+                '{' => open.push('}'),
+                '[' => open.push(']'),
+                '}' | ']' => {
+                    if let Some(expected) = open.pop() {
+                        result.push(expected);
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            result.push(ch);
         }
 
-        Ok(content.to_string())
+        Ok(result)
     }
 
     fn priority(&self) -> u8 {
-        95
+        84
     }
 }
 
 /// Strategy to add missing braces
+///
+/// Closes an unterminated string, then the containers left open, innermost first.
 pub struct AddMissingBracesStrategy;
 
 impl RepairStrategy for AddMissingBracesStrategy {
@@ -494,6 +1668,14 @@ impl RepairStrategy for AddMissingBracesStrategy {
         "AddMissingBraces"
     }
 
+    fn description(&self) -> &str {
+        "Add missing braces"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"a": 1"#)
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let trimmed = content.trim();
 
@@ -502,20 +1684,57 @@ impl RepairStrategy for AddMissingBracesStrategy {
         }
 
         let mut result = trimmed.to_string();
-        let open_braces = trimmed.matches('{').count();
-        let close_braces = trimmed.matches('}').count();
-        let open_brackets = trimmed.matches('[').count();
-        let close_brackets = trimmed.matches(']').count();
+        // Expected closers of the containers still open at the end, outside strings.
+        // Closers of the wrong type were already fixed by FixMismatchedBrackets.
+        let mut open: Vec<char> = Vec::new();
+        let mut in_string = false;
+        let mut string_start = 0;
+        let mut escaped = false;
+        for (i, ch) in trimmed.char_indices() {
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == '"' {
+                    in_string = false;
+                }
+                continue;
+            }
+            match ch {
+                '"' => {
+                    in_string = true;
+                    string_start = i;
+                }
+                '{' => open.push('}'),
+                '[' => open.push(']'),
+                '}' | ']' => {
+                    open.pop();
+                }
+                _ => {}
+            }
+        }
 
-        if open_braces > close_braces {
-            result.push_str(&"}".repeat(open_braces - close_braces));
+        // A closer after the last quote means that quote was a stray one inside a
+        // string rather than the start of a cut-off string, so leave it alone
+        if in_string && trimmed[string_start..].contains(['}', ']']) {
+            return Ok(trimmed.to_string());
         }
 
-        if open_brackets > close_brackets {
-            result.push_str(&"]".repeat(open_brackets - close_brackets));
+        // Input cut off inside a string: drop a dangling escape and close it
+        if in_string {
+            if escaped {
+                result.pop();
+            }
+            result.push('"');
         }
+        result.extend(open.iter().rev());
 
-        if !result.starts_with('{') && !result.starts_with('[') {
+        // A complete top-level scalar (`"text"`, `42`, `null`) is already a document
+        if !result.starts_with('{')
+            && !result.starts_with('[')
+            && !crate::json_util::is_valid_json(&result)
+        {
             result = format!("{{{}}}", result);
         }
 
@@ -535,6 +1754,14 @@ impl RepairStrategy for FixAgenticAiResponseStrategy {
         "FixAgenticAiResponse"
     }
 
+    fn description(&self) -> &str {
+        "Repair common agentic AI response patterns"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"result": undefined,}"#)
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_regex_cache();
         let mut result = content.to_string();
@@ -560,105 +1787,1505 @@ impl RepairStrategy for FixAgenticAiResponseStrategy {
 /// Strategy to strip JavaScript-style comments from JSON
 pub struct StripJsCommentsStrategy;
 
+impl StripJsCommentsStrategy {
+    /// Byte ranges of the comments outside strings. A `//` comment's range includes
+    /// the newline ending it; an unterminated `/*` comment runs to the end.
+    fn comment_ranges(content: &str) -> Vec<Range<usize>> {
+        tokenize(content)
+            .into_iter()
+            .filter(|token| token.kind == TokenKind::Comment)
+            .map(|Token { span, .. }| {
+                let ends_line = content[span.clone()].starts_with("//")
+                    && content[span.end..].starts_with('\n');
+                span.start..span.end + usize::from(ends_line)
+            })
+            .collect()
+    }
+}
+
 impl RepairStrategy for StripJsCommentsStrategy {
     fn name(&self) -> &str {
         "StripJsComments"
     }
 
-    fn apply(&self, content: &str) -> Result<String> {
-        let mut result = String::new();
-        let mut in_string = false;
-        let mut escaped = false;
-        let mut chars = content.chars().peekable();
+    fn description(&self) -> &str {
+        "Strip JavaScript-style comments from JSON"
+    }
 
-        while let Some(c) = chars.next() {
-            match c {
-                '\\' if in_string => {
-                    // Toggle escape state
-                    escaped = !escaped;
-                    result.push(c);
-                }
-                '"' if !escaped => {
-                    in_string = !in_string;
-                    result.push(c);
-                }
-                '/' if !in_string => {
-                    if let Some(&'/') = chars.peek() {
-                        // Single-line comment: //
-                        while chars.next() != Some('\n') && chars.peek().is_some() {
-                            // Skip until newline
-                        }
-                    } else if let Some(&'*') = chars.peek() {
-                        // Multi-line comment: /*
-                        chars.next(); // consume '*'
-                        loop {
-                            match chars.next() {
-                                Some('*') => {
-                                    if chars.peek() == Some(&'/') {
-                                        chars.next(); // consume '/'
-                                        break;
-                                    }
-                                }
-                                Some(_) => continue,
-                                None => break,
-                            }
-                        }
-                    } else {
-                        result.push(c);
-                    }
-                    escaped = false;
-                }
-                _ => {
-                    result.push(c);
-                    // Reset escape state for non-backslash characters
-                    if c != '\\' {
-                        escaped = false;
-                    }
-                }
-            }
-        }
+    fn example(&self) -> Option<&str> {
+        Some("{\"a\": 1 // count\n}")
+    }
 
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = String::with_capacity(content.len());
+        let mut last = 0;
+        for range in Self::comment_ranges(content) {
+            result.push_str(&content[last..range.start]);
+            last = range.end;
+        }
+        result.push_str(&content[last..]);
         Ok(result)
     }
 
+    fn removals(&self, content: &str) -> Vec<RemovedSpan> {
+        Self::comment_ranges(content)
+            .into_iter()
+            .map(|range| {
+                let text = content[range.clone()].trim_end();
+                RemovedSpan {
+                    byte_range: range.start..range.start + text.len(),
+                    text: text.to_string(),
+                    reason: "comment".to_string(),
+                }
+            })
+            .collect()
+    }
+
     fn priority(&self) -> u8 {
         95
     }
 }
 
-// ============================================================================
-// JSON Repairer
-// ============================================================================
-
-/// JSON repairer that can fix common JSON issues
+/// Strategy to remove `;` statement terminators copied from code
+///
+/// Outside string literals, a `;` before `,`, `}`, `]` or the end of input is
+/// dropped; a `;` between two values was used as a separator and becomes a comma.
+pub struct StripStatementTerminatorsStrategy;
+
+impl RepairStrategy for StripStatementTerminatorsStrategy {
+    fn name(&self) -> &str {
+        "StripStatementTerminators"
+    }
+
+    fn description(&self) -> &str {
+        "Remove `;` statement terminators copied from code"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"a": 1; "b": 2;}"#)
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = String::with_capacity(content.len());
+        let mut in_string = false;
+        let mut escaped = false;
+
+        for (i, c) in content.char_indices() {
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    in_string = false;
+                }
+                result.push(c);
+                continue;
+            }
+
+            match c {
+                '"' => {
+                    in_string = true;
+                    result.push(c);
+                }
+                ';' => {
+                    let next = content[i + 1..]
+                        .trim_start_matches(|c: char| c.is_whitespace() || c == ';');
+                    let after_separator =
+                        result.trim_end().ends_with([',', '{', '[']) || result.trim().is_empty();
+                    if !(next.is_empty() || next.starts_with([',', '}', ']']) || after_separator) {
+                        result.push(',');
+                    }
+                }
+                _ => result.push(c),
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn priority(&self) -> u8 {
+        91
+    }
+}
+
+/// Strategy to quote bare-word values (`{"status": active}` -> `{"status": "active"}`)
+///
+/// Runs after the boolean/null strategies, so a bare word that is still not
+/// `true`, `false`, `null` or a number is treated as a string. Only object
+/// values and array elements are touched; keys are left to `AddMissingQuotes`.
+pub struct QuoteUnquotedValuesStrategy;
+
+impl QuoteUnquotedValuesStrategy {
+    fn is_bare_string(token: &str) -> bool {
+        token.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && !matches!(token, "true" | "false" | "null")
+            && !token.contains(['"', '\'', ':', '{', '[', '\\'])
+    }
+}
+
+impl RepairStrategy for QuoteUnquotedValuesStrategy {
+    fn name(&self) -> &str {
+        "QuoteUnquotedValues"
+    }
+
+    fn description(&self) -> &str {
+        "Quote bare-word values"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"status": active}"#)
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = String::with_capacity(content.len() + 16);
+        // Open containers: true for arrays, false for objects
+        let mut containers: Vec<bool> = Vec::new();
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut expect_value = false;
+        let mut rest = content;
+
+        while let Some(c) = rest.chars().next() {
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    in_string = false;
+                }
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+
+            if expect_value && !c.is_whitespace() {
+                expect_value = false;
+                let end = rest.find([',', '}', ']', '\n']).unwrap_or(rest.len());
+                let token = rest[..end].trim_end();
+                if Self::is_bare_string(token) {
+                    result.push('"');
+                    result.push_str(token);
+                    result.push('"');
+                    rest = &rest[token.len()..];
+                    continue;
+                }
+            }
+
+            match c {
+                '"' => in_string = true,
+                '{' => containers.push(false),
+                '[' => {
+                    containers.push(true);
+                    expect_value = true;
+                }
+                '}' | ']' => {
+                    containers.pop();
+                }
+                ':' => expect_value = true,
+                ',' => expect_value = containers.last() == Some(&true),
+                _ => {}
+            }
+            result.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+
+        Ok(result)
+    }
+
+    fn priority(&self) -> u8 {
+        58
+    }
+}
+
+/// Strategy to insert commas missing between array elements or object members
+///
+/// Outside string literals, a value end (closing quote, number, `true`/`false`/`null`,
+/// `}` or `]`) followed by a value start (`"`, number, literal, `{` or `[`) gets a comma.
+/// Same-line whitespace between the two is replaced by the comma; line breaks are kept.
+pub struct InsertMissingCommasStrategy;
+
+impl InsertMissingCommasStrategy {
+    fn is_literal_or_number(word: &str) -> bool {
+        matches!(word, "true" | "false" | "null")
+            || word
+                .trim_start_matches('-')
+                .starts_with(|c: char| c.is_ascii_digit())
+    }
+}
+
+impl RepairStrategy for InsertMissingCommasStrategy {
+    fn name(&self) -> &str {
+        "InsertMissingCommas"
+    }
+
+    fn description(&self) -> &str {
+        "Insert commas missing between array elements or object members"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"a": 1 "b": 2}"#)
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let chars: Vec<char> = content.chars().collect();
+        let mut result = String::with_capacity(content.len() + 16);
+        let mut in_string = false;
+        let mut escaped = false;
+        // Byte offset in `result` just past the last complete value, if no separator followed it
+        let mut value_end: Option<usize> = None;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let ch = chars[i];
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == '"' {
+                    in_string = false;
+                    value_end = Some(result.len() + 1);
+                }
+                result.push(ch);
+                i += 1;
+                continue;
+            }
+
+            if ch.is_whitespace() {
+                result.push(ch);
+                i += 1;
+                continue;
+            }
+
+            // Read a bare word (number or literal) so it can be classified as a whole
+            let word_len = chars[i..]
+                .iter()
+                .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.' | '_'))
+                .count();
+            let word: String = chars[i..i + word_len].iter().collect();
+            let starts_value = matches!(ch, '"' | '{' | '[')
+                || (word_len > 0 && Self::is_literal_or_number(&word));
+
+            if let Some(end) = value_end
+                && starts_value
+            {
+                let gap = &result[end..];
+                if gap.contains('\n') {
+                    result.insert(end, ',');
+                } else {
+                    result.truncate(end);
+                    result.push(',');
+                }
+            }
+            value_end = None;
+
+            if word_len > 0 {
+                result.push_str(&word);
+                i += word_len;
+                if Self::is_literal_or_number(&word) {
+                    value_end = Some(result.len());
+                }
+                continue;
+            }
+
+            match ch {
+                '"' => in_string = true,
+                '}' | ']' => value_end = Some(result.len() + 1),
+                _ => {}
+            }
+            result.push(ch);
+            i += 1;
+        }
+
+        Ok(result)
+    }
+
+    fn priority(&self) -> u8 {
+        65
+    }
+}
+
+/// Strategy to replace `=` used as a key/value separator with `:`
+///
+/// Only a lone `=` outside string literals that directly follows a key
+/// (a closing quote or an identifier) is rewritten, so `==`, `!=`, `<=` and
+/// `=` inside strings are left alone.
+pub struct FixKeyValueSeparatorStrategy;
+
+impl RepairStrategy for FixKeyValueSeparatorStrategy {
+    fn name(&self) -> &str {
+        "FixKeyValueSeparator"
+    }
+
+    fn description(&self) -> &str {
+        "Replace `=` used as a key/value separator with `:`"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"{"a" = 1}"#)
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let chars: Vec<char> = content.chars().collect();
+        let mut result = String::with_capacity(content.len());
+        let mut in_string = false;
+        let mut escaped = false;
+
+        for (i, &ch) in chars.iter().enumerate() {
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' {
+                    escaped = true;
+                } else if ch == '"' {
+                    in_string = false;
+                }
+                result.push(ch);
+                continue;
+            }
+
+            if ch == '"' {
+                in_string = true;
+            } else if ch == '='
+                && chars.get(i + 1).is_none_or(|c| !matches!(c, '=' | '>'))
+                && i > 0
+                && !matches!(chars[i - 1], '=' | '!' | '<' | '>')
+                && chars[..i]
+                    .iter()
+                    .rev()
+                    .find(|c| !c.is_whitespace())
+                    .is_some_and(|c| *c == '"' || c.is_alphanumeric() || *c == '_')
+            {
+                // `"a" = 1` becomes `"a": 1`
+                result.truncate(result.trim_end().len());
+                result.push(':');
+                continue;
+            }
+            result.push(ch);
+        }
+
+        Ok(result)
+    }
+
+    fn priority(&self) -> u8 {
+        88
+    }
+}
+
+/// Strategy to normalize invalid or over-escaped sequences inside string literals
+///
+/// - `\'` becomes `'`
+/// - `\xNN` becomes `\u00NN`
+/// - `\\"` becomes `\"` when the quote would otherwise close the string early
+/// - any other invalid escape (e.g. a lone backslash in `C:\Users`) is doubled
+pub struct NormalizeEscapesStrategy;
+
+impl NormalizeEscapesStrategy {
+    /// True if the text after a candidate closing quote looks like the string really ended.
+    fn closes_string(rest: &[char]) -> bool {
+        match rest.iter().find(|c| !c.is_whitespace()) {
+            None => true,
+            Some(c) => matches!(c, ',' | '}' | ']' | ':'),
+        }
+    }
+}
+
+impl RepairStrategy for NormalizeEscapesStrategy {
+    fn name(&self) -> &str {
+        "NormalizeEscapes"
+    }
+
+    fn description(&self) -> &str {
+        "Normalize invalid or over-escaped sequences inside string literals"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("{\"path\": \"C:\\Users\", \"q\": \"it\\'s\"}")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let chars: Vec<char> = content.chars().collect();
+        let mut result = String::with_capacity(content.len());
+        let mut in_string = false;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let ch = chars[i];
+
+            if !in_string {
+                if ch == '"' {
+                    in_string = true;
+                }
+                result.push(ch);
+                i += 1;
+                continue;
+            }
+
+            match ch {
+                '"' => {
+                    in_string = false;
+                    result.push(ch);
+                    i += 1;
+                }
+                '\\' => match chars.get(i + 1).copied() {
+                    Some('\\') if chars.get(i + 2) == Some(&'"') => {
+                        if Self::closes_string(&chars[i + 3..]) {
+                            // Genuine trailing backslash followed by the closing quote
+                            result.push_str("\\\\");
+                            i += 2;
+                        } else {
+                            // Over-escaped quote: `\\"` meant `\"`
+                            result.push_str("\\\"");
+                            i += 3;
+                        }
+                    }
+                    Some(c @ ('"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't')) => {
+                        result.push('\\');
+                        result.push(c);
+                        i += 2;
+                    }
+                    Some('u')
+                        if chars.len() >= i + 6
+                            && chars[i + 2..i + 6].iter().all(|c| c.is_ascii_hexdigit()) =>
+                    {
+                        result.push('\\');
+                        result.extend(&chars[i + 1..i + 6]);
+                        i += 6;
+                    }
+                    Some('\'') => {
+                        result.push('\'');
+                        i += 2;
+                    }
+                    Some('x')
+                        if chars.len() >= i + 4
+                            && chars[i + 2..i + 4].iter().all(|c| c.is_ascii_hexdigit()) =>
+                    {
+                        result.push_str("\\u00");
+                        result.extend(&chars[i + 2..i + 4]);
+                        i += 4;
+                    }
+                    _ => {
+                        // Under-escaped backslash: keep it literally
+                        result.push_str("\\\\");
+                        i += 1;
+                    }
+                },
+                _ => {
+                    result.push(ch);
+                    i += 1;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn priority(&self) -> u8 {
+        92
+    }
+}
+
+/// Strategy to escape raw newlines, tabs and carriage returns inside string literals
+pub struct EscapeLiteralWhitespaceStrategy;
+
+impl RepairStrategy for EscapeLiteralWhitespaceStrategy {
+    fn name(&self) -> &str {
+        "EscapeLiteralWhitespace"
+    }
+
+    fn description(&self) -> &str {
+        "Escape raw newlines, tabs and carriage returns inside string literals"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("{\"text\": \"line1\nline2\"}")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let chars: Vec<char> = content.chars().collect();
+        let mut result = String::with_capacity(content.len());
+        let mut in_string = false;
+        let mut escaped = false;
+        // Whether the open string really continues up to its next quote; if that
+        // quote is not followed by a delimiter the opening one was never closed
+        let mut spans_lines = false;
+
+        for (i, &ch) in chars.iter().enumerate() {
+            if !in_string {
+                if ch == '"' {
+                    in_string = true;
+                    spans_lines = Self::string_continues(&chars[i + 1..]);
+                }
+                result.push(ch);
+                continue;
+            }
+
+            match ch {
+                _ if escaped => {
+                    escaped = false;
+                    result.push(ch);
+                }
+                '\\' => {
+                    escaped = true;
+                    result.push(ch);
+                }
+                '"' => {
+                    in_string = false;
+                    result.push(ch);
+                }
+                '\n' if spans_lines => result.push_str("\\n"),
+                '\r' if spans_lines => result.push_str("\\r"),
+                '\t' if spans_lines => result.push_str("\\t"),
+                _ => result.push(ch),
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn priority(&self) -> u8 {
+        92
+    }
+}
+
+impl EscapeLiteralWhitespaceStrategy {
+    /// True if the string whose contents start at `rest` ends at a quote that
+    /// closes it, or runs to the end of (truncated) input
+    fn string_continues(rest: &[char]) -> bool {
+        let mut escaped = false;
+        for (i, &c) in rest.iter().enumerate() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => return NormalizeEscapesStrategy::closes_string(&rest[i + 1..]),
+                _ => {}
+            }
+        }
+        true
+    }
+}
+
+/// Strategy to escape quotes inside a string value that would otherwise end it
+/// early (`"He said "hi""` -> `"He said \"hi\""`).
+///
+/// A quote is taken as interior when it does not close the string cleanly and a
+/// later quote on the same line does. A quote closes cleanly before `}`, `]`, `:`
+/// or the end, or before a `,` that is followed by the next member or element
+/// (`"key":`, a value), not by more text. A quote followed by another string
+/// (`"a" "b"`) or by `=` is left for `InsertMissingCommas` and `FixKeyValueSeparator`.
+pub struct EscapeInnerQuotesStrategy;
+
+impl EscapeInnerQuotesStrategy {
+    /// True if the quote whose following text is `rest` is part of the string
+    /// rather than its end
+    fn is_inner_quote(rest: &[char]) -> bool {
+        match rest.first() {
+            // A doubled closing quote: `"hi""`
+            Some('"') => Self::closes_cleanly(&rest[1..]),
+            _ => {
+                // Text that follows a string in other repairable mistakes: another
+                // string, `=` for `:`, or a statement terminator or comment
+                let next = trim_start_chars(rest);
+                let other_mistake = matches!(next.first(), Some('"' | '=' | ';'))
+                    || next.starts_with(&['/', '/'])
+                    || next.starts_with(&['/', '*']);
+                !Self::closes_cleanly(rest) && !other_mistake && Self::closes_later(rest)
+            }
+        }
+    }
+
+    /// True if `rest`, the text after a quote, shows the string really ended there
+    fn closes_cleanly(rest: &[char]) -> bool {
+        let rest = trim_start_chars(rest);
+        match rest.first() {
+            None | Some('}' | ']' | ':') => true,
+            Some(',') => {
+                let next = trim_start_chars(&rest[1..]);
+                match next.first() {
+                    None | Some('"' | '\'' | '{' | '[' | '}' | ']' | '-') => true,
+                    Some(c) if c.is_ascii_digit() => true,
+                    Some(c) if c.is_alphabetic() || *c == '_' => {
+                        // An unquoted key or a literal, not more prose
+                        let len = next
+                            .iter()
+                            .take_while(|c| c.is_alphanumeric() || **c == '_')
+                            .count();
+                        let word: String = next[..len].iter().collect();
+                        trim_start_chars(&next[len..]).first() == Some(&':')
+                            || matches!(word.as_str(), "true" | "false" | "null")
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// True if an unescaped quote later on the line closes the string cleanly
+    fn closes_later(rest: &[char]) -> bool {
+        let mut escaped = false;
+        for (i, &c) in rest.iter().enumerate() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '\n' => return false,
+                '"' if Self::closes_cleanly(&rest[i + 1..]) => return true,
+                _ => {}
+            }
+        }
+        false
+    }
+}
+
+/// `chars` without leading whitespace
+fn trim_start_chars(chars: &[char]) -> &[char] {
+    let start = chars
+        .iter()
+        .position(|c| !c.is_whitespace())
+        .unwrap_or(chars.len());
+    &chars[start..]
+}
+
+impl RepairStrategy for EscapeInnerQuotesStrategy {
+    fn name(&self) -> &str {
+        "EscapeInnerQuotes"
+    }
+
+    fn description(&self) -> &str {
+        "Escape unescaped double quotes inside string values"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("{\"quote\": \"He said \"hi\" to me\"}")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let chars: Vec<char> = content.chars().collect();
+        let mut result = String::with_capacity(content.len());
+        let mut in_string = false;
+        let mut escaped = false;
+
+        for (i, &ch) in chars.iter().enumerate() {
+            if !in_string {
+                in_string = ch == '"';
+            } else if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                if Self::is_inner_quote(&chars[i + 1..]) {
+                    result.push('\\');
+                } else {
+                    in_string = false;
+                }
+            }
+            result.push(ch);
+        }
+
+        Ok(result)
+    }
+
+    // Runs before the other string-literal strategies, which would otherwise see
+    // an inner quote as the end of the string
+    fn priority(&self) -> u8 {
+        93
+    }
+}
+
+// ============================================================================
+// JSON Repairer
+// ============================================================================
+
+/// Domain-specific structural rules [`JsonRepairer::with_profile`] applies after
+/// syntactic repair. Each profile is behind a cargo feature of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonProfile {
+    /// GeoJSON geometries, features and feature collections (`geojson` feature);
+    /// see [`crate::geojson`]
+    #[cfg(feature = "geojson")]
+    GeoJson,
+}
+
+// With every profile feature disabled the enum has no variants to use `content`
+#[cfg_attr(not(feature = "geojson"), allow(unused_variables))]
+impl JsonProfile {
+    /// Name reported in [`RepairReport::applied_strategies`] when the profile changes content
+    pub fn name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "geojson")]
+            Self::GeoJson => "GeoJsonProfile",
+        }
+    }
+
+    /// Fill the structural gaps this profile can infer in `content`, which should
+    /// already be valid JSON; anything else is returned unchanged
+    pub fn repair(self, content: &str) -> String {
+        match self {
+            #[cfg(feature = "geojson")]
+            Self::GeoJson => crate::geojson::repair(content),
+        }
+    }
+
+    /// Structural problems left in `content`, each prefixed with the JSON Pointer of
+    /// the offending value. Returns `RepairError::Conversion` if `content` is not valid JSON.
+    pub fn validate(self, content: &str) -> Result<Vec<String>> {
+        match self {
+            #[cfg(feature = "geojson")]
+            Self::GeoJson => crate::geojson::validate(content),
+        }
+    }
+}
+
+/// Output dialect of [`JsonRepairer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonDialect {
+    /// Strict RFC 8259 JSON
+    #[default]
+    Json,
+    /// JSON5, which additionally allows trailing commas
+    /// (see [`JsonRepairer::with_trailing_commas`])
+    Json5,
+}
+
+/// Casing [`JsonRepairer::with_key_case`] gives every object key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyCase {
+    /// Keep keys as written
+    #[default]
+    AsIs,
+    /// `firstname`
+    Lower,
+    /// `FIRSTNAME`
+    Upper,
+    /// `first_name`
+    Snake,
+    /// `firstName`
+    Camel,
+}
+
+impl KeyCase {
+    /// Rewrite `key` in this case. Snake and camel case split words at `_`, `-`,
+    /// spaces and case changes (`HTTPServer` -> `http_server`).
+    pub fn apply(self, key: &str) -> String {
+        match self {
+            Self::AsIs => key.to_string(),
+            Self::Lower => key.to_lowercase(),
+            Self::Upper => key.to_uppercase(),
+            Self::Snake => split_words(key)
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Self::Camel => split_words(key)
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    let word = word.to_lowercase();
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) if i > 0 => first.to_uppercase().chain(chars).collect(),
+                        _ => word,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// How [`JsonRepairer::with_unicode_output`] writes non-ASCII characters in strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnicodeOutput {
+    /// Keep characters and `\uXXXX` escapes as written
+    #[default]
+    AsIs,
+    /// Decode `\u00e9` to `é`, except escapes of control characters, `"` and `\`
+    Literal,
+    /// Encode `é` as `\u00e9` (surrogate pairs beyond U+FFFF) for ASCII-only output
+    EscapeNonAscii,
+}
+
+/// Split an identifier into words at separators and case changes
+fn split_words(key: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in key.split(['_', '-', ' ']).filter(|part| !part.is_empty()) {
+        let chars: Vec<(usize, char)> = part.char_indices().collect();
+        let mut start = 0;
+        for i in 1..chars.len() {
+            let (at, c) = chars[i];
+            let prev = chars[i - 1].1;
+            let next_is_lower = chars.get(i + 1).is_some_and(|(_, n)| n.is_lowercase());
+            // `fooBar` splits before `B`, `HTTPServer` before `S`
+            if c.is_uppercase() && (!prev.is_uppercase() || next_is_lower) {
+                words.push(&part[start..at]);
+                start = at;
+            }
+        }
+        words.push(&part[start..]);
+    }
+    words
+}
+
+/// Rewrite every object key of the JSON document `content` in `case`.
+/// Returns `content` unchanged if it is not valid JSON or no key changes.
+fn recase_keys(content: &str, case: KeyCase) -> String {
+    fn walk(value: &mut Value, case: KeyCase) {
+        match value {
+            Value::Object(entries) => {
+                for (key, item) in entries {
+                    *key = case.apply(key);
+                    walk(item, case);
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| walk(item, case)),
+            _ => {}
+        }
+    }
+
+    let Ok(mut value) = parse_json(content) else {
+        return content.to_string();
+    };
+    let original = value.clone();
+    walk(&mut value, case);
+    if value == original {
+        return content.to_string();
+    }
+    let mut out = String::new();
+    write_json(&value, &mut out);
+    out
+}
+
+/// A user transform [`JsonRepairer`] runs before or after its repair pipeline
+#[derive(Clone)]
+struct Processor(Arc<dyn Fn(&str) -> String>);
+
+impl fmt::Debug for Processor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Processor")
+    }
+}
+
+/// Trim the whitespace padding object keys (`{" name ": 1}` -> `{"name": 1}`).
+/// When a trimmed key collides with another key of its object, the last value
+/// wins, at the position of the first; duplicates without padding are kept.
+fn trim_keys(content: &str) -> String {
+    fn walk(value: &mut Value) {
+        match value {
+            Value::Object(entries) => {
+                // Trimmed entries, and whether each key was padded
+                let mut merged: Vec<((String, Value), bool)> = Vec::with_capacity(entries.len());
+                for (key, mut item) in std::mem::take(entries) {
+                    walk(&mut item);
+                    let trimmed = key.trim();
+                    let padded = trimmed.len() != key.len();
+                    let collision = merged
+                        .iter_mut()
+                        .find(|((k, _), was_padded)| k == trimmed && (padded || *was_padded));
+                    match collision {
+                        Some(((_, slot), was_padded)) => {
+                            *slot = item;
+                            *was_padded = true;
+                        }
+                        None => merged.push(((trimmed.to_string(), item), padded)),
+                    }
+                }
+                *entries = merged.into_iter().map(|(entry, _)| entry).collect();
+            }
+            Value::Array(items) => items.iter_mut().for_each(walk),
+            _ => {}
+        }
+    }
+
+    let Ok(mut value) = parse_json(content) else {
+        return content.to_string();
+    };
+    let original = value.clone();
+    walk(&mut value);
+    if value == original {
+        return content.to_string();
+    }
+    let mut out = String::new();
+    write_json(&value, &mut out);
+    out
+}
+
+/// Read the four hex digits of a `\uXXXX` escape starting at `chars[at]`
+fn unicode_escape(chars: &[char], at: usize) -> Option<u32> {
+    if chars.get(at..at + 2)? != ['\\', 'u'] {
+        return None;
+    }
+    let hex: String = chars.get(at + 2..at + 6)?.iter().collect();
+    u32::from_str_radix(&hex, 16).ok()
+}
+
+/// Rewrite the strings of the JSON document `content` as `mode` asks, leaving
+/// everything else as written. Returns `content` unchanged if it is not valid JSON.
+fn rewrite_unicode(content: &str, mode: UnicodeOutput) -> String {
+    if mode == UnicodeOutput::AsIs || parse_json(content).is_err() {
+        return content.to_string();
+    }
+    let chars: Vec<char> = content.chars().collect();
+    let mut result = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        if !in_string {
+            in_string = ch == '"';
+            result.push(ch);
+            i += 1;
+            continue;
+        }
+        match ch {
+            '"' => {
+                in_string = false;
+                result.push(ch);
+                i += 1;
+            }
+            '\\' if mode == UnicodeOutput::Literal => {
+                let decoded = unicode_escape(&chars, i).and_then(|unit| {
+                    match (unit, unicode_escape(&chars, i + 6)) {
+                        (0xD800..=0xDBFF, Some(low @ 0xDC00..=0xDFFF)) => {
+                            let code = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+                            char::from_u32(code).map(|c| (c, 12))
+                        }
+                        _ => char::from_u32(unit).map(|c| (c, 6)),
+                    }
+                });
+                match decoded.filter(|(c, _)| !c.is_control() && !matches!(c, '"' | '\\')) {
+                    Some((c, len)) => {
+                        result.push(c);
+                        i += len;
+                    }
+                    // Keep other escapes, including the escaped char, verbatim
+                    None => {
+                        result.extend(&chars[i..(i + 2).min(chars.len())]);
+                        i += 2;
+                    }
+                }
+            }
+            '\\' => {
+                result.extend(&chars[i..(i + 2).min(chars.len())]);
+                i += 2;
+            }
+            _ if mode == UnicodeOutput::EscapeNonAscii && !ch.is_ascii() => {
+                let mut units = [0u16; 2];
+                for unit in ch.encode_utf16(&mut units) {
+                    result.push_str(&format!("\\u{:04x}", unit));
+                }
+                i += 1;
+            }
+            _ => {
+                result.push(ch);
+                i += 1;
+            }
+        }
+    }
+    result
+}
+
+/// JSON repairer that can fix common JSON issues
 ///
 /// Uses trait-based composition with GenericRepairer for better modularity
 pub struct JsonRepairer {
     pub inner: crate::repairer_base::GenericRepairer,
+    cache: Option<Arc<RepairCache>>,
+    /// Format part of the cache key, fingerprinting the options that shape the output
+    cache_key: String,
+    safety: SafetyMode,
+    coerce_quoted_literals: bool,
+    profile: Option<JsonProfile>,
+    trim_keys: bool,
+    key_case: KeyCase,
+    unicode_output: UnicodeOutput,
+    dialect: JsonDialect,
+    trailing_commas: bool,
+    preprocessor: Option<Processor>,
+    postprocessor: Option<Processor>,
 }
 
 impl JsonRepairer {
     /// Create a new JSON repairer
     pub fn new() -> Self {
-        let strategies: Vec<Box<dyn RepairStrategy>> = vec![
+        Self::with_strategies(Self::default_strategies())
+    }
+
+    /// Start configuring a JSON repairer
+    pub fn builder() -> JsonRepairerBuilder {
+        JsonRepairerBuilder::default()
+    }
+
+    fn default_strategies() -> Vec<Box<dyn RepairStrategy>> {
+        vec![
+            Box::new(UnwrapMarkupStrategy),
+            Box::new(FixTripleQuotedStringsStrategy),
+            Box::new(StripSurroundingProseStrategy),
             Box::new(ExtractJsonFromProseStrategy),
             Box::new(StripTrailingContentStrategy),
             Box::new(StripJsCommentsStrategy),
+            Box::new(EscapeInnerQuotesStrategy),
+            Box::new(NormalizeEscapesStrategy),
+            Box::new(EscapeLiteralWhitespaceStrategy),
+            Box::new(FixKeyValueSeparatorStrategy),
             Box::new(FixSmartQuotesStrategy),
+            Box::new(FixBacktickQuotesStrategy),
             Box::new(AddMissingQuotesStrategy),
+            Box::new(StripStatementTerminatorsStrategy),
+            Box::new(RemoveEmptyElementsStrategy),
             Box::new(FixTrailingCommasStrategy),
+            Box::new(InsertMissingCommasStrategy),
             Box::new(AddMissingBracesStrategy),
             Box::new(FixSingleQuotesStrategy),
+            Box::new(RemoveExtraClosersStrategy),
+            Box::new(FixMismatchedBracketsStrategy),
             Box::new(FixMalformedNumbersStrategy),
             Box::new(FixBooleanNullStrategy),
             Box::new(FixBooleanVariantsStrategy),
+            Box::new(QuoteUnquotedValuesStrategy),
             Box::new(FixAgenticAiResponseStrategy),
+        ]
+    }
+
+    fn with_strategies(strategies: Vec<Box<dyn RepairStrategy>>) -> Self {
+        let validator: Box<dyn Validator> = Box::new(JsonValidator);
+        let mut inner = crate::repairer_base::GenericRepairer::new(validator, strategies);
+        inner.set_max_depth(Some(DEFAULT_MAX_DEPTH));
+
+        Self {
+            inner,
+            cache: None,
+            cache_key: "json".to_string(),
+            safety: SafetyMode::default(),
+            coerce_quoted_literals: false,
+            profile: None,
+            trim_keys: false,
+            key_case: KeyCase::AsIs,
+            unicode_output: UnicodeOutput::AsIs,
+            dialect: JsonDialect::default(),
+            trailing_commas: false,
+            preprocessor: None,
+            postprocessor: None,
+        }
+    }
+
+    /// Create a JSON repairer that reuses reports from a shared [`RepairCache`]
+    pub fn with_cache(cache: Arc<RepairCache>) -> Self {
+        Self::builder().cache(cache).build()
+    }
+
+    /// Create a JSON repairer that rejects input nested deeper than `max_depth`
+    /// with `RepairError::MaxDepthExceeded` (the default is
+    /// [`DEFAULT_MAX_DEPTH`])
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self::builder().max_depth(Some(max_depth)).build()
+    }
+
+    /// Create a JSON repairer that guards against repairs lowering confidence
+    /// (see [`SafetyMode`])
+    pub fn with_safety(safety: SafetyMode) -> Self {
+        Self::builder().safety(safety).build()
+    }
+
+    /// Create a JSON repairer that, when `enabled`, quotes zero-padded numbers
+    /// such as `07001` (zip codes, IDs) instead of stripping their leading zeros
+    pub fn with_numeric_ids_as_strings(enabled: bool) -> Self {
+        Self::builder().numeric_ids_as_strings(enabled).build()
+    }
+
+    /// Create a JSON repairer that, when `enabled`, turns string values spelling a
+    /// literal into the literal (`{"active": "true"}` -> `{"active": true}`).
+    /// Off by default, since a string that happens to read `"null"` loses its type.
+    pub fn with_coerce_quoted_booleans(enabled: bool) -> Self {
+        Self::builder().coerce_quoted_booleans(enabled).build()
+    }
+
+    /// Create a JSON repairer that, after syntactic repair, fills the structural gaps
+    /// `profile` can infer (see [`JsonProfile`])
+    pub fn with_profile(profile: JsonProfile) -> Self {
+        Self::builder().profile(profile).build()
+    }
+
+    /// Create a JSON repairer that, when `enabled`, also makes speculative repairs
+    /// such as reading `{name John age 30}` as `{"name": "John", "age": 30}`
+    /// (see [`PairBareWordsStrategy`]). Off by default, since the guesses can be wrong.
+    pub fn with_aggressive(enabled: bool) -> Self {
+        Self::builder().aggressive(enabled).build()
+    }
+
+    /// Create a JSON repairer that, when `enabled`, trims whitespace padding object
+    /// keys (`{" name ": 1}` -> `{"name": 1}`). A trimmed key that collides with
+    /// another key of its object is merged with it, the last value winning.
+    /// Re-serializes the document when a key changes, like [`Self::with_key_case`].
+    pub fn with_trim_keys(enabled: bool) -> Self {
+        Self::builder().trim_keys(enabled).build()
+    }
+
+    /// Create a JSON repairer that rewrites every object key of the repaired
+    /// document in `case`. Re-serializes the document when a key changes, so
+    /// the original whitespace is not kept; keys that end up equal are all kept.
+    pub fn with_key_case(case: KeyCase) -> Self {
+        Self::builder().key_case(case).build()
+    }
+
+    /// Create a JSON repairer that writes non-ASCII characters in strings as
+    /// `mode` asks: decoding `\u00e9` escapes to `é`, or escaping `é` as `\u00e9`
+    pub fn with_unicode_output(mode: UnicodeOutput) -> Self {
+        Self::builder().unicode_output(mode).build()
+    }
+
+    /// Create a JSON5 repairer that, when `enabled`, adds a trailing comma after the
+    /// last member of every object and array, so appending a member touches one line
+    /// of a diff. Strict JSON output never gets trailing commas.
+    pub fn with_trailing_commas(enabled: bool) -> Self {
+        Self::builder()
+            .dialect(JsonDialect::Json5)
+            .trailing_commas(enabled)
+            .build()
+    }
+
+    /// Create a JSON repairer that runs `preprocessor` on the input before any
+    /// repair strategy, e.g. to strip a domain-specific envelope
+    pub fn with_preprocessor(preprocessor: impl Fn(&str) -> String + 'static) -> Self {
+        Self::builder().preprocessor(preprocessor).build()
+    }
+
+    /// Create a JSON repairer that runs `postprocessor` on the output after the
+    /// last repair strategy and opt-in pass
+    pub fn with_postprocessor(postprocessor: impl Fn(&str) -> String + 'static) -> Self {
+        Self::builder().postprocessor(postprocessor).build()
+    }
+
+    /// Repair content and report which strategies changed it.
+    /// Served from the cache, if one is attached and has seen this input.
+    /// With a preprocessor, `removed` spans index into the preprocessed input.
+    pub fn repair_report(&mut self, content: &str) -> Result<RepairReport> {
+        if let Some(cache) = &self.cache {
+            // A cached report must not bypass the nesting limit
+            self.inner.check_depth(content)?;
+            if let Some(report) = cache.get(&self.cache_key, content) {
+                return Ok(report);
+            }
+        }
+
+        let mut applied_strategies = Vec::new();
+        let input = match &self.preprocessor {
+            Some(Processor(preprocess)) => {
+                let preprocessed = preprocess(content);
+                if preprocessed != content {
+                    applied_strategies.push("Preprocessor".to_string());
+                }
+                Cow::Owned(preprocessed)
+            }
+            None => Cow::Borrowed(content),
+        };
+        let (repaired, applied, removed) = self.inner.repair_with_removals(&input)?;
+        applied_strategies.extend(applied);
+        let repaired = self.post_process(repaired, &mut applied_strategies);
+        let report = RepairReport {
+            confidence_before: self.confidence(content),
+            confidence_after: self.confidence(&repaired),
+            repaired,
+            applied_strategies,
+            removed,
+        };
+        let report = self.safety.check(content, report)?;
+        if let Some(cache) = &self.cache {
+            cache.insert(&self.cache_key, content, report.clone());
+        }
+        Ok(report)
+    }
+
+    /// Repair content, also returning the comments it stripped and the whitespace
+    /// trimmed from around the document, ordered by their offset in `content`,
+    /// so JSONC tooling can reattach them to the clean output
+    pub fn repair_with_trivia(&mut self, content: &str) -> Result<(String, Vec<Trivia>)> {
+        let report = self.repair_report(content)?;
+        let mut trivia: Vec<Trivia> = report
+            .removed
+            .into_iter()
+            .filter(|span| span.reason == "comment")
+            .map(|span| Trivia {
+                byte_offset: span.byte_range.start,
+                kind: TriviaKind::Comment,
+                text: span.text,
+            })
+            .collect();
+
+        let body = content.strip_prefix('\u{feff}').unwrap_or(content);
+        let leading = &body[..body.len() - body.trim_start().len()];
+        if !leading.is_empty() && !report.repaired.starts_with(leading) {
+            trivia.push(Trivia {
+                byte_offset: content.len() - body.len(),
+                kind: TriviaKind::Whitespace,
+                text: leading.to_string(),
+            });
+        }
+        let trailing = &body[body.trim_end().len()..];
+        let kept = trailing
+            .bytes()
+            .rev()
+            .zip(report.repaired.bytes().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        if kept < trailing.len() && !body.trim().is_empty() {
+            trivia.push(Trivia {
+                byte_offset: content.len() - trailing.len(),
+                kind: TriviaKind::Whitespace,
+                text: trailing[..trailing.len() - kept].to_string(),
+            });
+        }
+
+        trivia.sort_by_key(|t| t.byte_offset);
+        Ok((report.repaired, trivia))
+    }
+
+    /// Run the opt-in passes on syntactically repaired output, naming each one
+    /// that changed it in `applied_strategies`
+    fn post_process(&self, mut repaired: String, applied_strategies: &mut Vec<String>) -> String {
+        if self.coerce_quoted_literals {
+            let coerced = unquote_literals(&repaired);
+            if coerced != repaired {
+                applied_strategies.push("CoerceQuotedBooleans".to_string());
+                repaired = coerced;
+            }
+        }
+        if let Some(profile) = self.profile {
+            let restructured = profile.repair(&repaired);
+            if restructured != repaired {
+                applied_strategies.push(profile.name().to_string());
+                repaired = restructured;
+            }
+        }
+        if self.trim_keys {
+            let trimmed = trim_keys(&repaired);
+            if trimmed != repaired {
+                applied_strategies.push("TrimKeys".to_string());
+                repaired = trimmed;
+            }
+        }
+        if self.key_case != KeyCase::AsIs {
+            let recased = recase_keys(&repaired, self.key_case);
+            if recased != repaired {
+                applied_strategies.push("NormalizeKeyCase".to_string());
+                repaired = recased;
+            }
+        }
+        if self.unicode_output != UnicodeOutput::AsIs {
+            let rewritten = rewrite_unicode(&repaired, self.unicode_output);
+            if rewritten != repaired {
+                applied_strategies.push("NormalizeUnicode".to_string());
+                repaired = rewritten;
+            }
+        }
+        if self.adds_trailing_commas() {
+            let with_commas = add_trailing_commas(&repaired);
+            if with_commas != repaired {
+                applied_strategies.push("AddTrailingCommas".to_string());
+                repaired = with_commas;
+            }
+        }
+        if let Some(Processor(postprocess)) = &self.postprocessor {
+            let postprocessed = postprocess(&repaired);
+            if postprocessed != repaired {
+                applied_strategies.push("Postprocessor".to_string());
+                repaired = postprocessed;
+            }
+        }
+        repaired
+    }
+
+    /// Trailing commas are only written in the JSON5 dialect
+    fn adds_trailing_commas(&self) -> bool {
+        self.trailing_commas && self.dialect == JsonDialect::Json5
+    }
+
+    /// Propose the individual edits `repair` would make, without applying them.
+    /// Applying them in order with [`crate::edit::apply_edits`] reproduces the repair.
+    pub fn preview(&self, content: &str) -> Result<Vec<crate::edit::Edit>> {
+        self.inner.preview(content)
+    }
+
+    /// Report what `repair` would fix, one finding per proposed edit, without
+    /// producing the repaired string. Stripping prose, markup or comments around
+    /// the document is a warning; every other fix is an error.
+    pub fn lint(&self, content: &str) -> Result<Vec<LintFinding>> {
+        const WRAPPER_STRATEGIES: [&str; 5] = [
+            "UnwrapMarkup",
+            "StripSurroundingProse",
+            "ExtractJsonFromProse",
+            "StripTrailingContent",
+            "StripJsComments",
         ];
 
-        let validator: Box<dyn Validator> = Box::new(JsonValidator);
-        let inner = crate::repairer_base::GenericRepairer::new(validator, strategies);
+        let edits = self.inner.preview(content)?;
+        Ok(findings_from_edits(content, &edits, |strategy| {
+            if WRAPPER_STRATEGIES.contains(&strategy) {
+                LintSeverity::Warning
+            } else {
+                LintSeverity::Error
+            }
+        }))
+    }
+}
+
+/// Builder combining any of the options of [`JsonRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct JsonRepairerBuilder {
+    base: BaseOptions,
+    cache: Option<Arc<RepairCache>>,
+    safety: SafetyMode,
+    numeric_ids_as_strings: bool,
+    coerce_quoted_booleans: bool,
+    aggressive: bool,
+    profile: Option<JsonProfile>,
+    trim_keys: bool,
+    key_case: KeyCase,
+    unicode_output: UnicodeOutput,
+    dialect: JsonDialect,
+    trailing_commas: bool,
+    preprocessor: Option<Processor>,
+    postprocessor: Option<Processor>,
+}
+
+impl JsonRepairerBuilder {
+    /// Reuse reports from a shared [`RepairCache`]. Reports are keyed by the
+    /// repairer's options, so differently configured repairers can share a cache;
+    /// a repairer with a pre- or postprocessor does not use it.
+    pub fn cache(mut self, cache: Arc<RepairCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Guard against repairs lowering confidence (see [`SafetyMode`])
+    pub fn safety(mut self, safety: SafetyMode) -> Self {
+        self.safety = safety;
+        self
+    }
+
+    /// Quote zero-padded numbers such as `07001` instead of stripping their leading zeros
+    pub fn numeric_ids_as_strings(mut self, enabled: bool) -> Self {
+        self.numeric_ids_as_strings = enabled;
+        self
+    }
+
+    /// Turn string values spelling a literal into the literal (`"true"` -> `true`)
+    pub fn coerce_quoted_booleans(mut self, enabled: bool) -> Self {
+        self.coerce_quoted_booleans = enabled;
+        self
+    }
+
+    /// Also make speculative repairs, such as pairing the words of `{name John age 30}`
+    pub fn aggressive(mut self, enabled: bool) -> Self {
+        self.aggressive = enabled;
+        self
+    }
+
+    /// Fill the structural gaps `profile` can infer after syntactic repair
+    pub fn profile(mut self, profile: JsonProfile) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Trim whitespace padding object keys, merging keys that then collide
+    pub fn trim_keys(mut self, enabled: bool) -> Self {
+        self.trim_keys = enabled;
+        self
+    }
+
+    /// Rewrite every object key of the repaired document in `case`
+    pub fn key_case(mut self, case: KeyCase) -> Self {
+        self.key_case = case;
+        self
+    }
+
+    /// Decode or escape non-ASCII characters in strings (kept as written by default)
+    pub fn unicode_output(mut self, mode: UnicodeOutput) -> Self {
+        self.unicode_output = mode;
+        self
+    }
+
+    /// Dialect of the repaired output (strict JSON by default)
+    pub fn dialect(mut self, dialect: JsonDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Add a trailing comma before every closer; ignored unless the dialect is JSON5
+    pub fn trailing_commas(mut self, enabled: bool) -> Self {
+        self.trailing_commas = enabled;
+        self
+    }
+
+    /// Transform the input before the first repair strategy
+    pub fn preprocessor(mut self, preprocessor: impl Fn(&str) -> String + 'static) -> Self {
+        self.preprocessor = Some(Processor(Arc::new(preprocessor)));
+        self
+    }
+
+    /// Transform the output after the last repair strategy and opt-in pass
+    pub fn postprocessor(mut self, postprocessor: impl Fn(&str) -> String + 'static) -> Self {
+        self.postprocessor = Some(Processor(Arc::new(postprocessor)));
+        self
+    }
+
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
 
-        Self { inner }
+    /// Create the configured JSON repairer
+    pub fn build(self) -> JsonRepairer {
+        let mut strategies = JsonRepairer::default_strategies();
+        if self.numeric_ids_as_strings {
+            strategies.push(Box::new(QuoteNumericIdsStrategy));
+        }
+        if self.aggressive {
+            strategies.push(Box::new(PairBareWordsStrategy));
+        }
+        let mut repairer = JsonRepairer::with_strategies(strategies);
+        // Processors are arbitrary closures, so their output can't be keyed
+        if self.preprocessor.is_none() && self.postprocessor.is_none() {
+            repairer.cache = self.cache;
+        }
+        // `max_depth` only rejects input and is checked before every lookup
+        repairer.cache_key = format!(
+            "json {:?}",
+            (
+                self.base.preserve_bom,
+                self.safety,
+                self.numeric_ids_as_strings,
+                self.coerce_quoted_booleans,
+                self.aggressive,
+                self.profile,
+                self.trim_keys,
+                self.key_case,
+                self.unicode_output,
+                self.dialect,
+                self.trailing_commas,
+            )
+        );
+        repairer.safety = self.safety;
+        repairer.coerce_quoted_literals = self.coerce_quoted_booleans;
+        repairer.profile = self.profile;
+        repairer.trim_keys = self.trim_keys;
+        repairer.key_case = self.key_case;
+        repairer.unicode_output = self.unicode_output;
+        repairer.dialect = self.dialect;
+        repairer.trailing_commas = self.trailing_commas;
+        repairer.preprocessor = self.preprocessor;
+        repairer.postprocessor = self.postprocessor;
+        repairer.inner.apply_options(self.base);
+        repairer
     }
 }
 
@@ -670,7 +3297,38 @@ impl Default for JsonRepairer {
 
 impl Repair for JsonRepairer {
     fn repair(&mut self, content: &str) -> Result<String> {
-        self.inner.repair(content)
+        if self.cache.is_some() || self.safety != SafetyMode::Allow || self.preprocessor.is_some() {
+            return self.repair_report(content).map(|report| report.repaired);
+        }
+        self.repair_cow(content).map(Cow::into_owned)
+    }
+
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        if self.cache.is_some() || self.safety != SafetyMode::Allow || self.preprocessor.is_some() {
+            let repaired = self.repair_report(content)?.repaired;
+            return Ok(if repaired == content {
+                Cow::Borrowed(content)
+            } else {
+                Cow::Owned(repaired)
+            });
+        }
+        let repaired = self.inner.repair_cow(content)?;
+        if !self.coerce_quoted_literals
+            && self.profile.is_none()
+            && !self.trim_keys
+            && self.key_case == KeyCase::AsIs
+            && self.unicode_output == UnicodeOutput::AsIs
+            && !self.adds_trailing_commas()
+            && self.postprocessor.is_none()
+        {
+            return Ok(repaired);
+        }
+        let processed = self.post_process(repaired.into_owned(), &mut Vec::new());
+        Ok(if processed == content {
+            Cow::Borrowed(content)
+        } else {
+            Cow::Owned(processed)
+        })
     }
 
     fn needs_repair(&self, content: &str) -> bool {
@@ -935,6 +3593,43 @@ mod tests {
         assert!(!result.contains('\u{201d}'));
     }
 
+    #[test]
+    fn test_trailing_commas_log() {
+        let strategy = FixTrailingCommasStrategy;
+        let (result, log) = strategy.apply_with_log(r#"{"a": [1, 2,],}"#).unwrap();
+        assert_eq!(result, r#"{"a": [1, 2]}"#);
+        assert_eq!(log.as_deref(), Some("removed 2 trailing comma(s)"));
+
+        let (_, log) = strategy.apply_with_log(r#"{"a": [1, 2]}"#).unwrap();
+        assert_eq!(log, None);
+    }
+
+    #[test]
+    fn test_boolean_variants_log() {
+        let strategy = FixBooleanVariantsStrategy;
+        let (_, log) = strategy.apply_with_log(r#"{"a": yes, "b": OFF}"#).unwrap();
+        assert_eq!(
+            log.as_deref(),
+            Some("converted 2 yes/no/on/off value(s) to booleans")
+        );
+
+        let (_, log) = strategy.apply_with_log(r#"{"a": true}"#).unwrap();
+        assert_eq!(log, None);
+    }
+
+    #[test]
+    fn test_default_apply_with_log() {
+        let strategy = StripJsCommentsStrategy;
+        let (_, log) = strategy.apply_with_log("[1 /* one */]").unwrap();
+        assert_eq!(log.as_deref(), Some("StripJsComments changed the content"));
+        assert_eq!(strategy.apply_with_log("[1]").unwrap().1, None);
+
+        let mut repairer = JsonRepairer::new();
+        let (repaired, log) = repairer.inner.repair_with_log("[1, 2,]").unwrap();
+        assert_eq!(repaired, "[1, 2]");
+        assert_eq!(log, vec!["removed 1 trailing comma(s)".to_string()]);
+    }
+
     #[test]
     fn test_boolean_variants_yes_no() {
         let strategy = FixBooleanVariantsStrategy;
@@ -1001,4 +3696,1021 @@ mod tests {
         assert!(result.ends_with('}'));
         assert!(result.contains("\"b\""));
     }
+    #[test]
+    fn test_leading_zeros_skip_strings_and_versions() {
+        let strategy = FixMalformedNumbersStrategy;
+        assert_eq!(
+            strategy
+                .apply(r#"{"code": "007", "n": 007, "m": -0042, "ip": "10.0.0.1"}"#)
+                .unwrap(),
+            r#"{"code": "007", "n": 7, "m": -42, "ip": "10.0.0.1"}"#
+        );
+        let input = r#"{"v": 01.02, "day": 2024-01-05, "at": 10:05, "s": "a \"01\" b"}"#;
+        assert_eq!(strategy.apply(input).unwrap(), input);
+
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(
+            repairer.repair(r#"{"code": "007", "n": 007,}"#).unwrap(),
+            r#"{"code": "007", "n": 7}"#
+        );
+    }
+
+    #[test]
+    fn test_builder_combines_options() {
+        let mut repairer = JsonRepairer::builder()
+            .numeric_ids_as_strings(true)
+            .coerce_quoted_booleans(true)
+            .preserve_bom(true)
+            .max_depth(Some(2))
+            .build();
+        assert_eq!(
+            repairer
+                .repair("\u{feff}{\"zip\": 07001, \"active\": \"true\",}")
+                .unwrap(),
+            "\u{feff}{\"zip\": \"07001\", \"active\": true}"
+        );
+        assert!(matches!(
+            repairer.repair("[[[1]]]"),
+            Err(crate::error::RepairError::MaxDepthExceeded { depth: 3, limit: 2 })
+        ));
+
+        let mut default = JsonRepairer::builder().build();
+        assert_eq!(
+            default
+                .repair(r#"{"zip": 07001, "active": "true",}"#)
+                .unwrap(),
+            r#"{"zip": 7001, "active": "true"}"#
+        );
+    }
+
+    #[test]
+    fn test_report_lists_removed_trailing_junk() {
+        let mut repairer = JsonRepairer::new();
+        let input = "  {\"a\": [1, 2,]} ***";
+        let report = repairer.repair_report(input).unwrap();
+        assert_eq!(report.repaired, r#"{"a": [1, 2]}"#);
+        assert_eq!(
+            report.removed,
+            vec![RemovedSpan {
+                byte_range: 17..20,
+                text: "***".to_string(),
+                reason: "trailing content".to_string(),
+            }]
+        );
+
+        let report = repairer
+            .repair_report(r#"Sure! {"a": 1} Let me know if you need more."#)
+            .unwrap();
+        let removed: Vec<_> = report.removed.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(removed, vec!["Sure!", "Let me know if you need more."]);
+    }
+
+    #[test]
+    fn test_report_lists_removed_comments() {
+        let mut repairer = JsonRepairer::new();
+        let input = "{\n  // the id\n  \"id\": 7, /* was 6 */\n  'b': 2,\n}";
+        let report = repairer.repair_report(input).unwrap();
+        assert_eq!(report.repaired, "{\n    \"id\": 7, \n  \"b\": 2\n}");
+        for span in &report.removed {
+            assert_eq!(&input[span.byte_range.clone()], span.text);
+            assert_eq!(span.reason, "comment");
+        }
+        let removed: Vec<_> = report.removed.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(removed, vec!["// the id", "/* was 6 */"]);
+
+        // Valid input removes nothing
+        assert!(repairer.repair_report("[1]").unwrap().removed.is_empty());
+    }
+
+    #[test]
+    fn test_repair_with_trivia_keeps_comments() {
+        let mut repairer = JsonRepairer::new();
+        let input = "  {\n  // leading\n  \"a\": 1, /* inline */ \"b\": 2,\n} \n";
+        let (repaired, trivia) = repairer.repair_with_trivia(input).unwrap();
+        assert!(crate::json_util::is_valid_json(&repaired));
+        assert!(!repaired.contains("leading") && !repaired.contains("inline"));
+
+        let found: Vec<_> = trivia
+            .iter()
+            .map(|t| (t.byte_offset, t.kind, t.text.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (0, TriviaKind::Whitespace, "  "),
+                (6, TriviaKind::Comment, "// leading"),
+                (27, TriviaKind::Comment, "/* inline */"),
+                (49, TriviaKind::Whitespace, " "),
+            ]
+        );
+        for t in &trivia {
+            assert_eq!(&input[t.byte_offset..t.byte_offset + t.text.len()], t.text);
+        }
+
+        // Valid input has no trivia
+        let (repaired, trivia) = repairer.repair_with_trivia("[1]").unwrap();
+        assert_eq!(repaired, "[1]");
+        assert!(trivia.is_empty());
+    }
+
+    #[test]
+    fn test_remove_empty_elements() {
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(repairer.repair("[1,,2]").unwrap(), "[1,2]");
+        assert_eq!(
+            repairer.repair(r#"{"a":1,,"b":2}"#).unwrap(),
+            r#"{"a":1,"b":2}"#
+        );
+        assert_eq!(repairer.repair("[1,2,,]").unwrap(), "[1,2]");
+        assert_eq!(repairer.repair("[, 1, ,\n 2]").unwrap(), "[ 1, \n 2]");
+
+        let valid = r#"{"csv": "a,,b", "list": ["x,,", ",y"]}"#;
+        assert_eq!(RemoveEmptyElementsStrategy.apply(valid).unwrap(), valid);
+        assert_eq!(repairer.repair(valid).unwrap(), valid);
+    }
+
+    #[test]
+    fn test_key_case() {
+        let mut snake = JsonRepairer::with_key_case(KeyCase::Snake);
+        assert_eq!(
+            snake.repair(r#"{"FirstName": 1}"#).unwrap(),
+            r#"{"first_name": 1}"#
+        );
+        let report = snake
+            .repair_report(r#"{"userID": [{"HTTPServer": "x",}], "zip-code": 2}"#)
+            .unwrap();
+        assert_eq!(
+            report.repaired,
+            r#"{"user_id": [{"http_server": "x"}], "zip_code": 2}"#
+        );
+        assert_eq!(
+            report.applied_strategies,
+            vec!["FixTrailingCommas", "NormalizeKeyCase"]
+        );
+
+        let mut lower = JsonRepairer::with_key_case(KeyCase::Lower);
+        assert_eq!(
+            lower.repair(r#"{"FirstName": 1}"#).unwrap(),
+            r#"{"firstname": 1}"#
+        );
+
+        assert_eq!(KeyCase::Camel.apply("first_name"), "firstName");
+        assert_eq!(KeyCase::Upper.apply("firstName"), "FIRSTNAME");
+
+        // Already in case: left byte for byte
+        let valid = "{\n  \"first_name\": {\"a\": 1}\n}";
+        assert_eq!(snake.repair(valid).unwrap(), valid);
+    }
+
+    #[test]
+    fn test_unwrap_json_from_markup() {
+        let mut repairer = JsonRepairer::new();
+        let report = repairer
+            .repair_report("<result><![CDATA[\n{'a': 1, 'b': [1, 2,]}\n]]></result>")
+            .unwrap();
+        assert_eq!(report.repaired, r#"{"a": 1, "b": [1, 2]}"#);
+        assert_eq!(report.applied_strategies[0], "UnwrapMarkup");
+        let removed: Vec<_> = report.removed.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(removed, vec!["<result><![CDATA[", "]]></result>"]);
+
+        // Entities in <pre> / <code> are decoded
+        assert_eq!(
+            repairer
+                .repair("Here:\n<pre class=\"json\"><code>{&quot;q&quot;: &quot;a &amp; b&quot;,}</code></pre>")
+                .unwrap(),
+            r#"{"q": "a & b"}"#
+        );
+
+        // Markup inside a JSON string is content, not a wrapper
+        let input = r#"{"html": "<pre>{x}</pre>"}"#;
+        assert_eq!(repairer.repair(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_pre_and_postprocessors_run_in_order() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let (pre_calls, post_calls) = (calls.clone(), calls.clone());
+        let mut repairer = JsonRepairer::builder()
+            .preprocessor(move |input| {
+                pre_calls.borrow_mut().push(format!("pre {input}"));
+                input.trim_start_matches("DATA:").to_string()
+            })
+            .postprocessor(move |output| {
+                post_calls.borrow_mut().push(format!("post {output}"));
+                format!("{output} // checked")
+            })
+            .build();
+
+        let report = repairer.repair_report("DATA:{'a': 1,}").unwrap();
+        assert_eq!(report.repaired, r#"{"a": 1} // checked"#);
+        assert_eq!(report.applied_strategies.first().unwrap(), "Preprocessor");
+        assert_eq!(report.applied_strategies.last().unwrap(), "Postprocessor");
+        assert_eq!(
+            *calls.borrow(),
+            vec!["pre DATA:{'a': 1,}", r#"post {"a": 1}"#]
+        );
+
+        // The hooks also run around input that needs no repair
+        let mut marked = JsonRepairer::with_postprocessor(|output| format!("{output}!"));
+        assert_eq!(marked.repair("[1]").unwrap(), "[1]!");
+        let mut stripped = JsonRepairer::with_preprocessor(|input| input.replace("DATA:", ""));
+        assert_eq!(stripped.repair("DATA:[1]").unwrap(), "[1]");
+    }
+
+    #[test]
+    fn test_trim_keys() {
+        let mut repairer = JsonRepairer::with_trim_keys(true);
+        let report = repairer
+            .repair_report(r#"{" name ": "Ann", "tags": [{"\tid ": 1}]}"#)
+            .unwrap();
+        assert_eq!(report.repaired, r#"{"name": "Ann", "tags": [{"id": 1}]}"#);
+        assert_eq!(report.applied_strategies, vec!["TrimKeys"]);
+
+        // A collision keeps the first position and the last value
+        assert_eq!(
+            repairer.repair(r#"{" a ": 1, "b": 2, "a": 3}"#).unwrap(),
+            r#"{"a": 3, "b": 2}"#
+        );
+
+        // Unpadded keys, even duplicated ones, are left as written
+        let input = r#"{"a": 1, "a": 2}"#;
+        assert_eq!(repairer.repair(input).unwrap(), input);
+        let input = r#"{" name ": 1}"#;
+        assert_eq!(JsonRepairer::new().repair(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_unicode_output() {
+        let mut literal = JsonRepairer::with_unicode_output(UnicodeOutput::Literal);
+        let report = literal.repair_report(r#"{"n":"\u00e9"}"#).unwrap();
+        assert_eq!(report.repaired, r#"{"n":"é"}"#);
+        assert_eq!(report.applied_strategies, vec!["NormalizeUnicode"]);
+        // Surrogate pairs decode to one char; quotes and control chars stay escaped
+        assert_eq!(
+            literal
+                .repair(r#"{"e":"\ud83d\ude00 \u0022 \u000a \n"}"#)
+                .unwrap(),
+            r#"{"e":"😀 \u0022 \u000a \n"}"#
+        );
+
+        let mut escaped = JsonRepairer::with_unicode_output(UnicodeOutput::EscapeNonAscii);
+        assert_eq!(escaped.repair(r#"{"n":"é"}"#).unwrap(), r#"{"n":"\u00e9"}"#);
+        assert_eq!(
+            escaped.repair(r#"{"😀":1}"#).unwrap(),
+            r#"{"\ud83d\ude00":1}"#
+        );
+
+        let input = r#"{"n":"\u00e9 é"}"#;
+        assert_eq!(JsonRepairer::new().repair(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_json5_trailing_commas() {
+        let mut json5 = JsonRepairer::with_trailing_commas(true);
+        assert_eq!(
+            json5
+                .repair("{\n  \"a\": [1, 2],\n  \"b\": {\"c\": \"}\"}\n}")
+                .unwrap(),
+            "{\n  \"a\": [1, 2,],\n  \"b\": {\"c\": \"}\",},\n}"
+        );
+        assert_eq!(
+            json5.repair("{'a': [], 'b': {}}").unwrap(),
+            r#"{"a": [], "b": {},}"#
+        );
+        let report = json5.repair_report(r#"{"a": 1}"#).unwrap();
+        assert_eq!(report.repaired, r#"{"a": 1,}"#);
+        assert_eq!(report.applied_strategies, vec!["AddTrailingCommas"]);
+
+        // Strict JSON never gets them, even when asked
+        let mut strict = JsonRepairer::builder().trailing_commas(true).build();
+        assert_eq!(
+            strict.repair(r#"{"a": [1, 2,],}"#).unwrap(),
+            r#"{"a": [1, 2]}"#
+        );
+        let mut off = JsonRepairer::with_trailing_commas(false);
+        assert_eq!(
+            off.repair(r#"{"a": [1, 2,],}"#).unwrap(),
+            r#"{"a": [1, 2]}"#
+        );
+    }
+
+    #[test]
+    fn test_backtick_quotes() {
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(repairer.repair("{`a`:`b`}").unwrap(), r#"{"a":"b"}"#);
+        assert_eq!(
+            repairer.repair("{`a`: `say \"hi\"`}").unwrap(),
+            r#"{"a": "say \"hi\""}"#
+        );
+
+        let valid = r#"{"cmd": "run `ls` now"}"#;
+        assert_eq!(FixBacktickQuotesStrategy.apply(valid).unwrap(), valid);
+        assert_eq!(repairer.repair(valid).unwrap(), valid);
+    }
+
+    #[test]
+    fn test_repair_cow_borrows_valid_input() {
+        let mut repairer = JsonRepairer::new();
+        let valid = r#"{"name": "Ann", "tags": [1, 2]}"#;
+        assert!(matches!(
+            repairer.repair_cow(valid).unwrap(),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            crate::repair_cow(valid).unwrap(),
+            Cow::Borrowed(_)
+        ));
+
+        let repaired = repairer.repair_cow(r#"{"a": 1,}"#).unwrap();
+        assert!(matches!(repaired, Cow::Owned(_)));
+        assert_eq!(repaired, r#"{"a": 1}"#);
+    }
+
+    #[test]
+    fn test_coerce_quoted_booleans() {
+        let input =
+            r#"{"active": "true", "flags": ["False", "null"], "true": "yes", "note": "is true"}"#;
+        let mut repairer = JsonRepairer::with_coerce_quoted_booleans(true);
+        assert_eq!(
+            repairer.repair(input).unwrap(),
+            r#"{"active": true, "flags": [false, null], "true": "yes", "note": "is true"}"#
+        );
+        assert_eq!(repairer.repair(r#""true""#).unwrap(), r#""true""#);
+
+        let report = repairer.repair_report(r#"{"active": "true",}"#).unwrap();
+        assert_eq!(report.repaired, r#"{"active": true}"#);
+        assert_eq!(
+            report.applied_strategies,
+            vec!["FixTrailingCommas", "CoerceQuotedBooleans"]
+        );
+
+        let mut repairer = JsonRepairer::with_coerce_quoted_booleans(false);
+        assert_eq!(
+            repairer.repair(r#"{"active": "true"}"#).unwrap(),
+            r#"{"active": "true"}"#
+        );
+    }
+
+    #[test]
+    fn test_numeric_ids_as_strings() {
+        let input = r#"{"zip": 07001, "ids": [007, 42], "n": -007, "v": "01"}"#;
+        let mut repairer = JsonRepairer::with_numeric_ids_as_strings(true);
+        assert_eq!(
+            repairer.repair(input).unwrap(),
+            r#"{"zip": "07001", "ids": ["007", 42], "n": -7, "v": "01"}"#
+        );
+
+        let mut repairer = JsonRepairer::with_numeric_ids_as_strings(false);
+        assert_eq!(
+            repairer.repair(r#"{"zip": 07001}"#).unwrap(),
+            r#"{"zip": 7001}"#
+        );
+        assert_eq!(
+            JsonRepairer::new().repair(r#"{"zip": 07001}"#).unwrap(),
+            r#"{"zip": 7001}"#
+        );
+    }
+
+    #[test]
+    fn test_aggressive_pairs_bare_words() {
+        let mut repairer = JsonRepairer::with_aggressive(true);
+        let repaired = repairer.repair("{name John age 30}").unwrap();
+        assert_eq!(repaired, r#"{"name": "John", "age": 30}"#);
+        assert!(crate::json_util::is_valid_json(&repaired));
+        assert_eq!(
+            repairer
+                .repair("[{id 1, ok true}, {id 2 ok null}]")
+                .unwrap(),
+            r#"[{"id": 1, "ok": true}, {"id": 2, "ok": null}]"#
+        );
+
+        // Odd word counts are left for the other strategies
+        let strategy = PairBareWordsStrategy;
+        assert_eq!(strategy.apply("{a b c}").unwrap(), "{a b c}");
+
+        assert_eq!(
+            JsonRepairer::new().repair("{name John age 30}").unwrap(),
+            "{name John age 30}"
+        );
+    }
+
+    #[test]
+    fn test_malformed_exponents() {
+        let strategy = FixMalformedNumbersStrategy;
+        assert_eq!(
+            strategy
+                .apply(r#"{"a": 1e, "b": 1.5e+, "c": -2E-}"#)
+                .unwrap(),
+            r#"{"a": 1, "b": 1.5, "c": -2}"#
+        );
+        assert_eq!(
+            strategy.apply("[1.5e+3, 3E10, 2E-4]").unwrap(),
+            "[1.5e+3, 3e10, 2e-4]"
+        );
+
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(
+            repairer.repair(r#"{"x": 1e, "y": 3E10,}"#).unwrap(),
+            r#"{"x": 1, "y": 3e10}"#
+        );
+    }
+
+    #[test]
+    fn test_exponents_leave_non_numbers_alone() {
+        let strategy = FixMalformedNumbersStrategy;
+        let input = r#"{"expr": "1 + 2", "unit": "3E", "v": 1 + 2, "id": v2e}"#;
+        assert_eq!(strategy.apply(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_quote_unquoted_values() {
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(
+            repairer.repair(r#"{"status": active}"#).unwrap(),
+            r#"{"status": "active"}"#
+        );
+        assert_eq!(
+            repairer
+                .repair(r#"{"name": John Smith, "tags": [new, hot], "n": 42, "ok": true}"#)
+                .unwrap(),
+            r#"{"name": "John Smith", "tags": ["new", "hot"], "n": 42, "ok": true}"#
+        );
+    }
+
+    #[test]
+    fn test_quote_unquoted_values_keeps_literals() {
+        let strategy = QuoteUnquotedValuesStrategy;
+        let input = r#"{"a": true, "b": null, "c": 42, "d": -1.5, "e": [false, 0], "f": "x: y"}"#;
+        assert_eq!(strategy.apply(input).unwrap(), input);
+
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(
+            repairer.repair(r#"{"a": True, "b": yes, "c": None}"#).unwrap(),
+            r#"{"a": true, "b": true, "c": null}"#
+        );
+    }
+
+    /// Deliberately destructive strategy for exercising [`SafetyMode`]
+    struct MangleStrategy;
+
+    impl RepairStrategy for MangleStrategy {
+        fn name(&self) -> &str {
+            "Mangle"
+        }
+
+        fn apply(&self, _content: &str) -> Result<String> {
+            Ok("garbage".to_string())
+        }
+
+        fn priority(&self) -> u8 {
+            100
+        }
+    }
+
+    fn mangling_repairer(safety: SafetyMode) -> JsonRepairer {
+        let mut repairer = JsonRepairer::with_safety(safety);
+        repairer.inner = crate::repairer_base::GenericRepairer::new(
+            Box::new(JsonValidator),
+            vec![Box::new(MangleStrategy)],
+        );
+        repairer
+    }
+
+    #[test]
+    fn test_safety_rejects_worse_output() {
+        let input = r#"{"a": 1,}"#;
+        assert_eq!(
+            mangling_repairer(SafetyMode::Allow).repair(input).unwrap(),
+            "garbage"
+        );
+        assert_eq!(
+            mangling_repairer(SafetyMode::RejectIfWorse)
+                .repair(input)
+                .unwrap(),
+            input
+        );
+        assert!(matches!(
+            mangling_repairer(SafetyMode::ErrorIfWorse).repair(input),
+            Err(crate::error::RepairError::ConfidenceDropped { .. })
+        ));
+    }
+
+    #[test]
+    fn test_safety_keeps_improving_repairs() {
+        let mut repairer = JsonRepairer::with_safety(SafetyMode::ErrorIfWorse);
+        assert_eq!(repairer.repair("[1, 2,]").unwrap(), "[1, 2]");
+    }
+
+    #[test]
+    fn test_max_depth_exceeded() {
+        let deep = format!("{}1{}", "[".repeat(20), "]".repeat(19));
+        let mut repairer = JsonRepairer::with_max_depth(10);
+        assert!(matches!(
+            repairer.repair(&deep),
+            Err(crate::error::RepairError::MaxDepthExceeded {
+                depth: 20,
+                limit: 10
+            })
+        ));
+        assert!(JsonRepairer::with_max_depth(20).repair(&deep).is_ok());
+    }
+
+    #[test]
+    fn test_adversarial_depth_does_not_overflow_stack() {
+        let deep = format!("{{\"a\": {}", "[".repeat(200_000));
+        let mut repairer = JsonRepairer::new();
+        assert!(matches!(
+            repairer.repair(&deep),
+            Err(crate::error::RepairError::MaxDepthExceeded { limit: 512, .. })
+        ));
+        assert!(crate::repair(&deep).is_err());
+    }
+
+    #[test]
+    fn test_statement_terminators_removed() {
+        let mut repairer = JsonRepairer::new();
+        let result = repairer.repair("{\"a\": 1;, \"b\": 2;}").unwrap();
+        assert!(JsonValidator.is_valid(&result));
+        assert_eq!(result, "{\"a\": 1, \"b\": 2}");
+    }
+
+    #[test]
+    fn test_statement_terminator_as_separator() {
+        let strategy = StripStatementTerminatorsStrategy;
+        assert_eq!(
+            strategy
+                .apply("{\"a\": \"x;y\"; \"b\": [1;; 2;]};")
+                .unwrap(),
+            "{\"a\": \"x;y\", \"b\": [1, 2]}"
+        );
+    }
+
+    #[test]
+    fn test_strip_surrounding_prose_leading() {
+        let strategy = StripSurroundingProseStrategy;
+        let input = "Sure! Here is the JSON:\n{\"a\": 1, \"b\": [1, 2]}";
+        assert_eq!(strategy.apply(input).unwrap(), "{\"a\": 1, \"b\": [1, 2]}");
+    }
+
+    #[test]
+    fn test_strip_surrounding_prose_trailing() {
+        let strategy = StripSurroundingProseStrategy;
+        let input = "[1, 2, {\"a\": 3}]\nLet me know if you need anything else!";
+        assert_eq!(strategy.apply(input).unwrap(), "[1, 2, {\"a\": 3}]");
+    }
+
+    #[test]
+    fn test_strip_trailing_content_reports_removed_text() {
+        let strategy = StripTrailingContentStrategy;
+        let input = r#"{"a": "x} y"} // trailing junk"#;
+        assert_eq!(
+            StripTrailingContentStrategy::trailing_range(input),
+            Some(14..30)
+        );
+        let (repaired, description) = strategy.apply_with_log(input).unwrap();
+        assert_eq!(repaired, r#"{"a": "x} y"}"#);
+        assert_eq!(
+            description.as_deref(),
+            Some(r#"removed trailing content at bytes 14..30: "// trailing junk""#)
+        );
+
+        // Further documents or key fragments are not trailing junk
+        assert_eq!(
+            strategy.apply_with_log(r#"{"a": 1}, {"b": 2}"#).unwrap().1,
+            None
+        );
+        assert_eq!(strategy.apply_with_log(r#""a": 1"#).unwrap().1, None);
+    }
+
+    #[test]
+    fn test_strip_trailing_content_top_level_scalar() {
+        let input = r#""just a string" trailing junk"#;
+        assert_eq!(
+            StripTrailingContentStrategy.apply(input).unwrap(),
+            r#""just a string""#
+        );
+        assert_eq!(
+            StripTrailingContentStrategy.apply("-1.5e3 units").unwrap(),
+            "-1.5e3"
+        );
+
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(repairer.repair(input).unwrap(), r#""just a string""#);
+        assert_eq!(repairer.repair("null // nothing").unwrap(), "null");
+    }
+
+    #[test]
+    fn test_strip_surrounding_prose_both_sides() {
+        let mut repairer = JsonRepairer::new();
+        let input =
+            "Here's the JSON:\n{\"a\": \"x}\", \"b\": [1, 2],}\nLet me know if {that} helps.";
+        assert_eq!(
+            repairer.repair(input).unwrap(),
+            "{\"a\": \"x}\", \"b\": [1, 2]}"
+        );
+    }
+
+    #[test]
+    fn test_strip_surrounding_prose_leaves_fragments() {
+        let strategy = StripSurroundingProseStrategy;
+        let fragment = "\"profile\": {\"name\": \"x\"}";
+        assert_eq!(strategy.apply(fragment).unwrap(), fragment);
+        let concatenated = "{\"a\": 1}, {\"b\": 2}";
+        assert_eq!(strategy.apply(concatenated).unwrap(), concatenated);
+    }
+
+    #[test]
+    fn test_cache_hit_on_identical_input() {
+        let cache = Arc::new(RepairCache::new(16));
+        let mut repairer = JsonRepairer::with_cache(Arc::clone(&cache));
+
+        let first = repairer.repair_report(r#"{"a": 1,}"#).unwrap();
+        assert_eq!(first.repaired, r#"{"a": 1}"#);
+        assert_eq!(first.applied_strategies, vec!["FixTrailingCommas"]);
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+
+        // A second repairer sharing the cache hits it
+        let mut other = JsonRepairer::with_cache(Arc::clone(&cache));
+        assert_eq!(other.repair(r#"{"a": 1,}"#).unwrap(), r#"{"a": 1}"#);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        assert_eq!(other.repair(r#"{"b": 2,}"#).unwrap(), r#"{"b": 2}"#);
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_cache_keyed_by_options() {
+        let cache = Arc::new(RepairCache::new(16));
+        let input = r#"{"userName": 1,}"#;
+        let mut snake = JsonRepairer::builder()
+            .cache(Arc::clone(&cache))
+            .key_case(KeyCase::Snake)
+            .build();
+        let mut as_is = JsonRepairer::with_cache(Arc::clone(&cache));
+        assert_eq!(snake.repair(input).unwrap(), r#"{"user_name": 1}"#);
+        assert_eq!(as_is.repair(input).unwrap(), r#"{"userName": 1}"#);
+        assert_eq!(snake.repair(input).unwrap(), r#"{"user_name": 1}"#);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.hits(), 1);
+
+        // The depth limit still applies to input already in the cache
+        let deep = format!("{}{}", "[".repeat(5), "]".repeat(5));
+        as_is.repair(&deep).unwrap();
+        let mut shallow = JsonRepairer::builder()
+            .cache(Arc::clone(&cache))
+            .max_depth(Some(3))
+            .build();
+        assert!(shallow.repair(&deep).is_err());
+    }
+
+    #[test]
+    fn test_cache_shared_across_threads() {
+        let cache = Arc::new(RepairCache::new(16));
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                let cache = Arc::clone(&cache);
+                s.spawn(move || {
+                    let mut repairer = JsonRepairer::with_cache(cache);
+                    assert_eq!(repairer.repair("[1, 2,]").unwrap(), "[1, 2]");
+                });
+            }
+        });
+        assert_eq!(cache.hits() + cache.misses(), 4);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_preview_trailing_comma() {
+        let repairer = JsonRepairer::new();
+        let input = r#"{"a": 1, "b": 2,}"#;
+        let edits = repairer.preview(input).unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].byte_range, 15..16);
+        assert_eq!(&input[edits[0].byte_range.clone()], ",");
+        assert_eq!(edits[0].replacement, "");
+        assert_eq!(edits[0].strategy, "FixTrailingCommas");
+    }
+
+    #[test]
+    fn test_preview_matches_repair() {
+        let mut repairer = JsonRepairer::new();
+        let input = "  {'name': 'x', active: True,}";
+        let edits = repairer.preview(input).unwrap();
+        let applied = crate::edit::apply_edits(input, &edits);
+        assert_eq!(applied.trim(), repairer.repair(input).unwrap());
+        assert!(repairer.preview(r#"{"ok": true}"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fix_mismatched_brackets() {
+        let strategy = FixMismatchedBracketsStrategy;
+        assert_eq!(strategy.apply(r#"{"a":1]"#).unwrap(), r#"{"a":1}"#);
+        assert_eq!(strategy.apply("[1,2}").unwrap(), "[1,2]");
+
+        let nested = r#"{"a": [1, {"b": "]}"}], "c": {}}"#;
+        assert_eq!(strategy.apply(nested).unwrap(), nested);
+
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(
+            repairer.repair(r#"[{"a": 1], {"b": 2}}"#).unwrap(),
+            r#"[{"a": 1}, {"b": 2}]"#
+        );
+        assert_eq!(
+            repairer.repair(r#"{"a": [1, 2}"#).unwrap(),
+            r#"{"a": [1, 2]}"#
+        );
+    }
+
+    #[test]
+    fn test_lint_reports_without_fixing() {
+        let repairer = JsonRepairer::new();
+        let content = "{\n  \"a\": 1,\n  \"b\": [1, 2,],\n}";
+        let findings = repairer.lint(content).unwrap();
+
+        assert_eq!(findings.len(), 2);
+        assert!(findings.iter().all(|f| f.rule == "trailing-comma"));
+        assert_eq!(findings[0].severity, LintSeverity::Error);
+        assert_eq!((findings[0].line, findings[0].column), (3, 13));
+        assert_eq!(&content[findings[0].byte_range.clone()], ",");
+        assert_eq!(findings[0].replacement, "");
+        assert_eq!((findings[1].line, findings[1].column), (3, 15));
+        assert_eq!(&content[findings[1].byte_range.clone()], ",");
+
+        let findings = repairer.lint("Here it is: {\"a\": 1}").unwrap();
+        assert_eq!(findings[0].rule, "surrounding-prose");
+        assert_eq!(findings[0].severity, LintSeverity::Warning);
+        assert!(repairer.lint(r#"{"a": 1}"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tokenize() {
+        use TokenKind as K;
+        let kinds_and_spans = |content: &str| -> Vec<(TokenKind, Range<usize>)> {
+            tokenize(content)
+                .into_iter()
+                .map(|token| (token.kind, token.span))
+                .collect()
+        };
+
+        assert_eq!(
+            kinds_and_spans("{\"a\": 1, // c\n}"),
+            vec![
+                (K::OpenBrace, 0..1),
+                (K::String, 1..4),
+                (K::Colon, 4..5),
+                (K::Whitespace, 5..6),
+                (K::Number, 6..7),
+                (K::Comma, 7..8),
+                (K::Whitespace, 8..9),
+                (K::Comment, 9..13),
+                (K::Whitespace, 13..14),
+                (K::CloseBrace, 14..15),
+            ]
+        );
+
+        // Single-quoted strings hide brackets and comment markers; an apostrophe
+        // in a bare word does not open one
+        let kinds: Vec<TokenKind> = tokenize("['a]//', it's, -1e-5, \"x\\\"")
+            .into_iter()
+            .map(|token| token.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                K::OpenBracket,
+                K::String,
+                K::Comma,
+                K::Whitespace,
+                K::Ident,
+                K::Other,
+                K::Ident,
+                K::Comma,
+                K::Whitespace,
+                K::Number,
+                K::Comma,
+                K::Whitespace,
+                K::String,
+            ]
+        );
+
+        let input = "{'url': 'http://example.com/[x]'}";
+        assert_eq!(StripJsCommentsStrategy.apply(input).unwrap(), input);
+        assert_eq!(RemoveExtraClosersStrategy.apply(input).unwrap(), input);
+
+        // An unterminated `'` does not hide the closers and comments after it
+        let input = "{'a': 'x}}\n// note\n";
+        assert_eq!(RemoveExtraClosersStrategy::extra_closers(input), vec![9]);
+        assert_eq!(StripJsCommentsStrategy::comment_ranges(input), vec![11..19]);
+        assert_eq!(
+            RemoveExtraClosersStrategy.apply(input).unwrap(),
+            "{'a': 'x}\n// note\n"
+        );
+    }
+
+    #[test]
+    fn test_add_missing_braces_closes_cut_off_string() {
+        let strategy = AddMissingBracesStrategy;
+        assert_eq!(
+            strategy.apply(r#"{"a": ["x", "cut"#).unwrap(),
+            r#"{"a": ["x", "cut"]}"#
+        );
+        // A raw inner quote followed by the closers is not a cut-off string
+        let stray = r#"{"c": "it"s"}"#;
+        assert_eq!(strategy.apply(stray).unwrap(), stray);
+        assert_eq!(
+            JsonRepairer::new().repair(r#"{"c": 'it"s'}"#).unwrap(),
+            stray
+        );
+    }
+
+    #[test]
+    fn test_remove_extra_closers() {
+        let strategy = RemoveExtraClosersStrategy;
+        assert_eq!(strategy.apply(r#"{"a":1}}"#).unwrap(), r#"{"a":1}"#);
+        assert_eq!(strategy.apply("[1]]").unwrap(), "[1]");
+        assert_eq!(
+            strategy.apply(r#"{"a": [1]], "b": "]}"}"#).unwrap(),
+            r#"{"a": [1], "b": "]}"}"#
+        );
+        // A closer of an outer container is a missing inner closer, not an extra one
+        let missing = r#"{"a": [1, 2}"#;
+        assert_eq!(strategy.apply(missing).unwrap(), missing);
+
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(repairer.repair(r#"{"a":1}}"#).unwrap(), r#"{"a":1}"#);
+        assert_eq!(repairer.repair("[1]]").unwrap(), "[1]");
+        let report = repairer
+            .repair_report(r#"{"a": {"b": [1, 2]]}, "c": 3}"#)
+            .unwrap();
+        assert_eq!(report.repaired, r#"{"a": {"b": [1, 2]}, "c": 3}"#);
+        assert_eq!(report.applied_strategies, vec!["RemoveExtraClosers"]);
+        assert_eq!(report.removed[0].byte_range, 18..19);
+    }
+
+    #[test]
+    fn test_triple_quoted_strings() {
+        let strategy = FixTripleQuotedStringsStrategy;
+        assert_eq!(
+            strategy
+                .apply("{\"sql\": \"\"\"SELECT *\nFROM \"users\"\n\"\"\", 'q': '''a\\b'''}")
+                .unwrap(),
+            r#"{"sql": "SELECT *\nFROM \"users\"\n", 'q': "a\\b"}"#
+        );
+
+        let mut repairer = JsonRepairer::new();
+        let repaired = repairer
+            .repair("{\n  \"sql\": \"\"\"SELECT id\n  FROM t\"\"\",\n  \"n\": 1\n}")
+            .unwrap();
+        assert_eq!(
+            repaired,
+            "{\n  \"sql\": \"SELECT id\\n  FROM t\",\n  \"n\": 1\n}"
+        );
+        assert!(crate::json_util::is_valid_json(&repaired));
+
+        // Quotes inside ordinary strings are not delimiters
+        let input = r#"{"a": "say '''hi'''"}"#;
+        assert_eq!(strategy.apply(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_escape_inner_quotes() {
+        let mut repairer = JsonRepairer::new();
+        let repaired = repairer.repair(r#"{"q":"He said "hi""}"#).unwrap();
+        assert_eq!(repaired, r#"{"q":"He said \"hi\""}"#);
+        assert!(crate::json_util::is_valid_json(&repaired));
+
+        let repaired = repairer
+            .repair(r#"{"quote": "He said "hi" to me", "n": 1}"#)
+            .unwrap();
+        assert_eq!(repaired, r#"{"quote": "He said \"hi\" to me", "n": 1}"#);
+
+        // A quote before a path is inner; a `,` followed by more text is not the end
+        for input in [
+            r#"{"prompt": "expose a "/hello" endpoint returning "Hello, World!".", "n": 1}"#,
+            r#"{"q":"He said "hi", ok", "b": 1}"#,
+        ] {
+            let repaired = repairer.repair(input).unwrap();
+            assert!(crate::json_util::is_valid_json(&repaired), "{}", repaired);
+        }
+        assert_eq!(
+            repairer
+                .repair(r#"{"q":"He said "hi", ok", "b": 1}"#)
+                .unwrap(),
+            r#"{"q":"He said \"hi\", ok", "b": 1}"#
+        );
+        let mailform = include_str!("../examples/data/json/complex/mailform_response.json");
+        assert!(crate::json_util::is_valid_json(
+            &repairer.repair(mailform).unwrap()
+        ));
+
+        // Adjacent strings are a missing comma, not an inner quote
+        let strategy = EscapeInnerQuotesStrategy;
+        let input = r#"{"a": "x" "b": 1}"#;
+        assert_eq!(strategy.apply(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_escape_literal_whitespace() {
+        let mut repairer = JsonRepairer::new();
+        let repaired = repairer
+            .repair("{\"text\": \"line1\nline2\tend\r\n\", \"n\": 1}")
+            .unwrap();
+        assert_eq!(repaired, r#"{"text": "line1\nline2\tend\r\n", "n": 1}"#);
+        assert!(crate::json_util::is_valid_json(&repaired));
+
+        // A newline after an unclosed string is layout, not part of the value
+        let strategy = EscapeLiteralWhitespaceStrategy;
+        let input = "{\"a\": \"hello,\n  \"b\": 2}";
+        assert_eq!(strategy.apply(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_insert_missing_commas_array() {
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(repairer.repair("[1 2 3]").unwrap(), "[1,2,3]");
+        assert_eq!(
+            repairer.repair("[true false null]").unwrap(),
+            "[true,false,null]"
+        );
+    }
+
+    #[test]
+    fn test_insert_missing_commas_object() {
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(
+            repairer.repair(r#"{"a":1 "b":2}"#).unwrap(),
+            r#"{"a":1,"b":2}"#
+        );
+        assert_eq!(
+            repairer.repair("[{\"a\": 1}\n{\"b\": [2] \"c\"}]").unwrap(),
+            "[{\"a\": 1},\n{\"b\": [2],\"c\"}]"
+        );
+    }
+
+    #[test]
+    fn test_insert_missing_commas_leaves_strings() {
+        let strategy = InsertMissingCommasStrategy;
+        let input = r#"{"text": "1 2 3 \"quoted\" [x] {y}", "n": -1.5e3}"#;
+        assert_eq!(strategy.apply(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_key_value_separator_equals() {
+        let mut repairer = JsonRepairer::new();
+        assert_eq!(repairer.repair(r#"{"a" = 1}"#).unwrap(), r#"{"a": 1}"#);
+        assert_eq!(
+            repairer.repair(r#"{"a" = 1, "b"= "x=y"}"#).unwrap(),
+            r#"{"a": 1, "b": "x=y"}"#
+        );
+    }
+
+    #[test]
+    fn test_key_value_separator_leaves_strings_and_operators() {
+        let mut repairer = JsonRepairer::new();
+        let input = r#"{"eq": "a==b"}"#;
+        assert_eq!(repairer.repair(input).unwrap(), input);
+
+        let strategy = FixKeyValueSeparatorStrategy;
+        for input in ["{\"k\": a == b}", "{\"k\": a != b}", "{\"s\": \"\\\"=\"}"] {
+            assert_eq!(strategy.apply(input).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn test_normalize_escapes_over_escaped_quotes() {
+        let strategy = NormalizeEscapesStrategy;
+        let input = r#"{"msg": "He said \\"hi\\""}"#;
+        let result = strategy.apply(input).unwrap();
+        assert_eq!(result, r#"{"msg": "He said \"hi\""}"#);
+        assert!(crate::json_util::is_valid_json(&result));
+    }
+
+    #[test]
+    fn test_normalize_escapes_under_escaped_backslash() {
+        let strategy = NormalizeEscapesStrategy;
+        let input = r#"{"path": "C:\Users\me", "q": "it\'s"}"#;
+        let result = strategy.apply(input).unwrap();
+        assert_eq!(result, r#"{"path": "C:\\Users\\me", "q": "it's"}"#);
+        assert!(crate::json_util::is_valid_json(&result));
+
+        // A genuine trailing backslash before the closing quote is kept
+        let input2 = r#"{"dir": "C:\\", "n": 1}"#;
+        assert_eq!(strategy.apply(input2).unwrap(), input2);
+    }
+
+    #[test]
+    fn test_normalize_escapes_hex_escape() {
+        let strategy = NormalizeEscapesStrategy;
+        let input = r#"{"letter": "\x41"}"#;
+        let result = strategy.apply(input).unwrap();
+        assert_eq!(result, r#"{"letter": "\u0041"}"#);
+
+        let mut repairer = JsonRepairer::new();
+        let repaired = repairer.repair(input).unwrap();
+        assert!(crate::json_util::is_valid_json(&repaired));
+    }
 }
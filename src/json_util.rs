@@ -26,6 +26,31 @@ pub fn is_valid_json(content: &str) -> bool {
     parse_json_value(content.trim()).is_ok()
 }
 
+/// Remove all insignificant whitespace from JSON, leaving string contents untouched.
+pub fn minify_json(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for ch in content.chars() {
+        if in_string {
+            out.push(ch);
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                in_string = false;
+            }
+        } else if ch == '"' {
+            in_string = true;
+            out.push(ch);
+        } else if !matches!(ch, ' ' | '\t' | '\n' | '\r') {
+            out.push(ch);
+        }
+    }
+    out
+}
+
 /// Validation errors for invalid JSON (empty if valid).
 pub fn validate_json_errors(content: &str) -> Vec<String> {
     match parse_json_value(content.trim()) {
@@ -154,7 +179,7 @@ fn extract_object_string_field(json: &str, key: &str) -> Result<Option<String>,
         let value_start = skip_whitespace(bytes, i);
 
         if field_key == key {
-            let value_end = parse_value(bytes, value_start)?;
+            let value_end = parse_value(bytes, value_start, 1)?;
             let raw = std::str::from_utf8(&bytes[value_start..value_end])
                 .map_err(|_| "invalid UTF-8 in JSON value".to_string())?;
             let raw = raw.trim();
@@ -167,7 +192,7 @@ fn extract_object_string_field(json: &str, key: &str) -> Result<Option<String>,
             return parse_json_string(raw).map(Some);
         }
 
-        i = parse_value(bytes, value_start)?;
+        i = parse_value(bytes, value_start, 1)?;
         i = skip_whitespace(bytes, i);
         if i >= bytes.len() {
             return Err(format!("missing field '{}'", key));
@@ -212,7 +237,7 @@ fn extract_object_value_field(json: &str, key: &str) -> Result<Option<String>, S
         let value_start = skip_whitespace(bytes, i);
 
         if field_key == key {
-            let value_end = parse_value(bytes, value_start)?;
+            let value_end = parse_value(bytes, value_start, 1)?;
             return Ok(Some(
                 std::str::from_utf8(&bytes[value_start..value_end])
                     .map_err(|_| "invalid UTF-8 in JSON value".to_string())?
@@ -220,7 +245,7 @@ fn extract_object_value_field(json: &str, key: &str) -> Result<Option<String>, S
             ));
         }
 
-        i = parse_value(bytes, value_start)?;
+        i = parse_value(bytes, value_start, 1)?;
         i = skip_whitespace(bytes, i);
         if i >= bytes.len() {
             return Err(format!("missing field '{}'", key));
@@ -274,6 +299,36 @@ fn parse_json_string(s: &str) -> Result<String, String> {
     Ok(out)
 }
 
+/// A JSON syntax error and the byte offset where it was detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonSyntaxError {
+    pub offset: usize,
+    pub message: String,
+}
+
+impl JsonSyntaxError {
+    fn new(offset: usize, message: &str) -> Self {
+        Self {
+            offset,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for JsonSyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for JsonSyntaxError {}
+
+impl From<JsonSyntaxError> for String {
+    fn from(e: JsonSyntaxError) -> Self {
+        e.message
+    }
+}
+
 fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
     while i < bytes.len() && bytes[i].is_ascii_whitespace() {
         i += 1;
@@ -281,24 +336,29 @@ fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
     i
 }
 
-fn parse_value(bytes: &[u8], mut i: usize) -> Result<usize, String> {
+/// Deepest nesting anyrepair's recursive parsers descend into before giving up,
+/// so adversarial input cannot overflow the stack
+pub(crate) const MAX_PARSE_DEPTH: usize = 1024;
+
+fn parse_value(bytes: &[u8], mut i: usize, depth: usize) -> Result<usize, JsonSyntaxError> {
     i = skip_whitespace(bytes, i);
     if i >= bytes.len() {
-        return Err("unexpected end of JSON".to_string());
+        return Err(JsonSyntaxError::new(i, "unexpected end of JSON"));
     }
     match bytes[i] {
         b'"' => parse_string(bytes, i),
-        b'{' => parse_object(bytes, i),
-        b'[' => parse_array(bytes, i),
+        b'{' | b'[' if depth >= MAX_PARSE_DEPTH => Err(JsonSyntaxError::new(i, "nesting too deep")),
+        b'{' => parse_object(bytes, i, depth + 1),
+        b'[' => parse_array(bytes, i, depth + 1),
         b't' if bytes[i..].starts_with(b"true") => Ok(i + 4),
         b'f' if bytes[i..].starts_with(b"false") => Ok(i + 5),
         b'n' if bytes[i..].starts_with(b"null") => Ok(i + 4),
         b'-' | b'0'..=b'9' => parse_number(bytes, i),
-        _ => Err("invalid JSON token".to_string()),
+        _ => Err(JsonSyntaxError::new(i, "invalid JSON token")),
     }
 }
 
-fn parse_string(bytes: &[u8], mut i: usize) -> Result<usize, String> {
+fn parse_string(bytes: &[u8], mut i: usize) -> Result<usize, JsonSyntaxError> {
     i += 1;
     let mut escape = false;
     while i < bytes.len() {
@@ -316,17 +376,23 @@ fn parse_string(bytes: &[u8], mut i: usize) -> Result<usize, String> {
         if b == b'"' {
             return Ok(i + 1);
         }
+        if b < 0x20 {
+            return Err(JsonSyntaxError::new(
+                i,
+                "unescaped control character in string",
+            ));
+        }
         i += 1;
     }
-    Err("unterminated string".to_string())
+    Err(JsonSyntaxError::new(i, "unterminated string"))
 }
 
-fn parse_number(bytes: &[u8], mut i: usize) -> Result<usize, String> {
+fn parse_number(bytes: &[u8], mut i: usize) -> Result<usize, JsonSyntaxError> {
     if bytes[i] == b'-' {
         i += 1;
     }
     if i >= bytes.len() {
-        return Err("invalid number".to_string());
+        return Err(JsonSyntaxError::new(i, "invalid number"));
     }
     if bytes[i] == b'0' {
         i += 1;
@@ -335,12 +401,12 @@ fn parse_number(bytes: &[u8], mut i: usize) -> Result<usize, String> {
             i += 1;
         }
     } else {
-        return Err("invalid number".to_string());
+        return Err(JsonSyntaxError::new(i, "invalid number"));
     }
     if i < bytes.len() && bytes[i] == b'.' {
         i += 1;
         if i >= bytes.len() || !bytes[i].is_ascii_digit() {
-            return Err("invalid number".to_string());
+            return Err(JsonSyntaxError::new(i, "invalid number"));
         }
         while i < bytes.len() && bytes[i].is_ascii_digit() {
             i += 1;
@@ -352,7 +418,7 @@ fn parse_number(bytes: &[u8], mut i: usize) -> Result<usize, String> {
             i += 1;
         }
         if i >= bytes.len() || !bytes[i].is_ascii_digit() {
-            return Err("invalid number".to_string());
+            return Err(JsonSyntaxError::new(i, "invalid number"));
         }
         while i < bytes.len() && bytes[i].is_ascii_digit() {
             i += 1;
@@ -361,33 +427,34 @@ fn parse_number(bytes: &[u8], mut i: usize) -> Result<usize, String> {
     Ok(i)
 }
 
-fn parse_array(bytes: &[u8], mut i: usize) -> Result<usize, String> {
+fn parse_array(bytes: &[u8], mut i: usize, depth: usize) -> Result<usize, JsonSyntaxError> {
     i += 1;
     i = skip_whitespace(bytes, i);
     if i < bytes.len() && bytes[i] == b']' {
         return Ok(i + 1);
     }
     loop {
-        i = parse_value(bytes, i)?;
+        i = parse_value(bytes, i, depth)?;
         i = skip_whitespace(bytes, i);
         if i >= bytes.len() {
-            return Err("unterminated array".to_string());
+            return Err(JsonSyntaxError::new(i, "unterminated array"));
         }
         match bytes[i] {
             b']' => return Ok(i + 1),
             b',' => {
+                let comma = i;
                 i += 1;
                 i = skip_whitespace(bytes, i);
                 if i < bytes.len() && bytes[i] == b']' {
-                    return Err("trailing comma in array".to_string());
+                    return Err(JsonSyntaxError::new(comma, "trailing comma in array"));
                 }
             }
-            _ => return Err("expected ',' or ']' in array".to_string()),
+            _ => return Err(JsonSyntaxError::new(i, "expected ',' or ']' in array")),
         }
     }
 }
 
-fn parse_object(bytes: &[u8], mut i: usize) -> Result<usize, String> {
+fn parse_object(bytes: &[u8], mut i: usize, depth: usize) -> Result<usize, JsonSyntaxError> {
     i += 1;
     i = skip_whitespace(bytes, i);
     if i < bytes.len() && bytes[i] == b'}' {
@@ -396,43 +463,54 @@ fn parse_object(bytes: &[u8], mut i: usize) -> Result<usize, String> {
     loop {
         i = skip_whitespace(bytes, i);
         if i >= bytes.len() || bytes[i] != b'"' {
-            return Err("expected string key in object".to_string());
+            return Err(JsonSyntaxError::new(i, "expected string key in object"));
         }
         i = parse_string(bytes, i)?;
         i = skip_whitespace(bytes, i);
         if i >= bytes.len() || bytes[i] != b':' {
-            return Err("expected ':' after key".to_string());
+            return Err(JsonSyntaxError::new(i, "expected ':' after key"));
         }
         i += 1;
-        i = parse_value(bytes, i)?;
+        i = parse_value(bytes, i, depth)?;
         i = skip_whitespace(bytes, i);
         if i >= bytes.len() {
-            return Err("unterminated object".to_string());
+            return Err(JsonSyntaxError::new(i, "unterminated object"));
         }
         match bytes[i] {
             b'}' => return Ok(i + 1),
             b',' => {
+                let comma = i;
                 i += 1;
                 i = skip_whitespace(bytes, i);
                 if i < bytes.len() && bytes[i] == b'}' {
-                    return Err("trailing comma in object".to_string());
+                    return Err(JsonSyntaxError::new(comma, "trailing comma in object"));
                 }
             }
-            _ => return Err("expected ',' or '}' in object".to_string()),
+            _ => return Err(JsonSyntaxError::new(i, "expected ',' or '}' in object")),
         }
     }
 }
 
 fn parse_json_value(s: &str) -> Result<(), String> {
-    let s = s.trim();
-    if s.is_empty() {
-        return Err("empty JSON".to_string());
+    match json_syntax_error(s) {
+        Some(e) => Err(e.message),
+        None => Ok(()),
     }
-    let end = parse_value(s.as_bytes(), 0)?;
-    if skip_whitespace(s.as_bytes(), end) != s.len() {
-        return Err("trailing characters".to_string());
+}
+
+/// Locate the first JSON syntax error in `content`, if any.
+/// The offset is a byte index into `content` itself (surrounding whitespace included).
+pub fn json_syntax_error(content: &str) -> Option<JsonSyntaxError> {
+    let bytes = content.as_bytes();
+    if content.trim().is_empty() {
+        return Some(JsonSyntaxError::new(0, "empty JSON"));
     }
-    Ok(())
+    let end = match parse_value(bytes, 0, 0) {
+        Ok(end) => end,
+        Err(e) => return Some(e),
+    };
+    let rest = skip_whitespace(bytes, end);
+    (rest != bytes.len()).then(|| JsonSyntaxError::new(rest, "trailing characters"))
 }
 
 #[cfg(test)]
@@ -444,6 +522,24 @@ mod tests {
         assert!(is_valid_json(r#"{"a":1}"#));
     }
 
+    #[test]
+    fn deep_nesting_is_rejected_without_recursing() {
+        let deep = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        let err = json_syntax_error(&deep).unwrap();
+        assert_eq!(err.message, "nesting too deep");
+        assert_eq!(err.offset, MAX_PARSE_DEPTH);
+        let shallow = format!("{}{}", "[".repeat(100), "]".repeat(100));
+        assert!(is_valid_json(&shallow));
+    }
+
+    #[test]
+    fn raw_newline_in_string_is_invalid() {
+        let err = json_syntax_error("{\"a\": \"x\ny\"}").unwrap();
+        assert_eq!(err.message, "unescaped control character in string");
+        assert_eq!(err.offset, 8);
+        assert!(is_valid_json(r#"{"a": "x\ny"}"#));
+    }
+
     #[test]
     fn invalid_trailing_comma() {
         assert!(!is_valid_json(r#"{"a":1,}"#));
@@ -1,16 +1,29 @@
 //! Key-value format repair module (INI, .env, .properties)
 
 use crate::error::Result;
+use crate::repairer_base::{BaseOptions, swap_comment_marker};
 use crate::traits::{Repair, RepairStrategy, Validator};
+use std::borrow::Cow;
 use std::collections::HashSet;
 
-struct FixMissingEqualsStrategy;
+struct FixMissingEqualsStrategy {
+    /// Leave `; comment` / `# comment` after the value out of the entry (INI)
+    inline_comments: bool,
+}
 
 impl RepairStrategy for FixMissingEqualsStrategy {
     fn name(&self) -> &str {
         "FixMissingEquals"
     }
 
+    fn description(&self) -> &str {
+        "Add `=` between a key and its value"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("host localhost")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let mut result = Vec::new();
         for line in content.lines() {
@@ -19,15 +32,21 @@ impl RepairStrategy for FixMissingEqualsStrategy {
                 result.push(line.to_string());
                 continue;
             }
-            if !trimmed.contains('=') {
-                let parts: Vec<&str> = trimmed.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    result.push(format!("{}={}", parts[0], parts[1..].join(" ")));
+            let (entry, comment) = match self.inline_comments {
+                true => split_inline_comment(trimmed),
+                false => (trimmed, None),
+            };
+            if !entry.contains('=') {
+                let parts: Vec<&str> = entry.split_whitespace().collect();
+                let fixed = if parts.len() >= 2 {
+                    format!("{}={}", parts[0], parts[1..].join(" "))
                 } else if parts.len() == 1 {
-                    result.push(format!("{}=", parts[0]));
+                    format!("{}=", parts[0])
                 } else {
                     result.push(line.to_string());
-                }
+                    continue;
+                };
+                result.push(with_inline_comment(fixed, comment));
             } else {
                 result.push(line.to_string());
             }
@@ -40,13 +59,24 @@ impl RepairStrategy for FixMissingEqualsStrategy {
     }
 }
 
-struct FixWhitespaceAroundEqualsStrategy;
+struct FixWhitespaceAroundEqualsStrategy {
+    /// Leave `; comment` / `# comment` after the value out of the entry (INI)
+    inline_comments: bool,
+}
 
 impl RepairStrategy for FixWhitespaceAroundEqualsStrategy {
     fn name(&self) -> &str {
         "FixWhitespaceAroundEquals"
     }
 
+    fn description(&self) -> &str {
+        "Remove whitespace around `=`"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("host   =   localhost")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let mut result = Vec::new();
         for line in content.lines() {
@@ -55,10 +85,14 @@ impl RepairStrategy for FixWhitespaceAroundEqualsStrategy {
                 result.push(line.to_string());
                 continue;
             }
-            if let Some(eq_pos) = trimmed.find('=') {
-                let key = trimmed[..eq_pos].trim();
-                let value = trimmed[eq_pos + 1..].trim();
-                result.push(format!("{}={}", key, value));
+            let (entry, comment) = match self.inline_comments {
+                true => split_inline_comment(trimmed),
+                false => (trimmed, None),
+            };
+            if let Some(eq_pos) = entry.find('=') {
+                let key = entry[..eq_pos].trim();
+                let value = entry[eq_pos + 1..].trim();
+                result.push(with_inline_comment(format!("{}={}", key, value), comment));
             } else {
                 result.push(line.to_string());
             }
@@ -80,6 +114,14 @@ impl RepairStrategy for FixEmptyKeysStrategy {
         "FixEmptyKeys"
     }
 
+    fn description(&self) -> &str {
+        "Generate a key for entries that start with `=`"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("=orphan")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let mut result = Vec::new();
         for (i, line) in content.lines().enumerate() {
@@ -110,11 +152,25 @@ impl RepairStrategy for FixMalformedCommentsStrategy {
         "FixMalformedComments"
     }
 
+    fn description(&self) -> &str {
+        "Move stray `#` comments to the start of the line"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("note # comment")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let mut result = Vec::new();
         for line in content.lines() {
             let trimmed = line.trim();
-            if trimmed.contains('#') && !trimmed.starts_with('#') && !trimmed.contains('=') {
+            // `[section] # comment` is a header with an inline comment
+            let is_header = split_inline_comment(trimmed).0.starts_with('[');
+            if trimmed.contains('#')
+                && !trimmed.starts_with('#')
+                && !trimmed.contains('=')
+                && !is_header
+            {
                 if let Some(hash_pos) = trimmed.find('#') {
                     let before = trimmed[..hash_pos].trim();
                     let after = &trimmed[hash_pos..];
@@ -145,6 +201,14 @@ impl RepairStrategy for FixQuotedValuesStrategy {
         "FixQuotedValues"
     }
 
+    fn description(&self) -> &str {
+        "Close values with an unbalanced quote"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"name="Ann"#)
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let mut result = Vec::new();
         for line in content.lines() {
@@ -155,10 +219,12 @@ impl RepairStrategy for FixQuotedValuesStrategy {
             }
             if let Some(eq_pos) = trimmed.find('=') {
                 let value = trimmed[eq_pos + 1..].trim();
-                if value.starts_with('"') && !value.ends_with('"') {
-                    result.push(format!("{}=\"{}\"", &trimmed[..eq_pos], &value[1..]));
-                } else if value.starts_with('\'') && !value.ends_with('\'') {
-                    result.push(format!("{}='{}'", &trimmed[..eq_pos], &value[1..]));
+                if let Some(inner) = value.strip_prefix('"').filter(|_| !value.ends_with('"')) {
+                    result.push(format!("{}=\"{}\"", &trimmed[..eq_pos], inner));
+                } else if let Some(inner) =
+                    value.strip_prefix('\'').filter(|_| !value.ends_with('\''))
+                {
+                    result.push(format!("{}='{}'", &trimmed[..eq_pos], inner));
                 } else if value.ends_with('"') && !value.starts_with('"') {
                     result.push(format!(
                         "{}\"{}\"",
@@ -194,14 +260,15 @@ impl RepairStrategy for FixMalformedSectionsStrategy {
     fn apply(&self, content: &str) -> Result<String> {
         let mut result = Vec::new();
         for line in content.lines() {
-            let trimmed = line.trim();
-            if trimmed.starts_with('[') && !trimmed.ends_with(']') {
+            let (header, comment) = split_inline_comment(line.trim());
+            if header.starts_with('[') && !header.ends_with(']') {
                 let indent = line
                     .chars()
                     .take_while(|c| c.is_whitespace())
                     .collect::<String>();
-                let section_name = trimmed.trim_start_matches('[');
-                result.push(format!("{}[{}]", indent, section_name));
+                let section_name = header.trim_start_matches('[');
+                let fixed = format!("{}[{}]", indent, section_name);
+                result.push(with_inline_comment(fixed, comment));
             } else {
                 result.push(line.to_string());
             }
@@ -216,6 +283,14 @@ impl RepairStrategy for FixMalformedSectionsStrategy {
     fn name(&self) -> &str {
         "FixMalformedSectionsStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Add the missing `]` to section headers"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("[server\nport=80")
+    }
 }
 
 struct FixMalformedKeysStrategy;
@@ -225,17 +300,19 @@ impl RepairStrategy for FixMalformedKeysStrategy {
         let mut result = Vec::new();
         for line in content.lines() {
             let trimmed = line.trim();
-            if is_skip_line(trimmed) || trimmed.contains('=') {
+            let (entry, comment) = split_inline_comment(trimmed);
+            if is_skip_line(trimmed) || entry.contains('=') {
                 result.push(line.to_string());
                 continue;
             }
-            let parts: Vec<&str> = trimmed.splitn(2, ' ').collect();
+            let parts: Vec<&str> = entry.splitn(2, ' ').collect();
             if parts.len() == 2 {
                 let indent = line
                     .chars()
                     .take_while(|c| c.is_whitespace())
                     .collect::<String>();
-                result.push(format!("{}{} = {}", indent, parts[0], parts[1]));
+                let fixed = format!("{}{} = {}", indent, parts[0], parts[1]);
+                result.push(with_inline_comment(fixed, comment));
             } else {
                 result.push(line.to_string());
             }
@@ -250,6 +327,14 @@ impl RepairStrategy for FixMalformedKeysStrategy {
     fn name(&self) -> &str {
         "FixMalformedKeysStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Turn space-separated `key value` lines into `key = value`"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("host localhost")
+    }
 }
 
 struct RemoveDuplicateSectionsStrategy;
@@ -279,6 +364,14 @@ impl RepairStrategy for RemoveDuplicateSectionsStrategy {
     fn name(&self) -> &str {
         "RemoveDuplicateSectionsStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Drop repeated section headers"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("[a]\nx=1\n[a]\ny=2")
+    }
 }
 
 struct AddDefaultSectionStrategy;
@@ -306,41 +399,293 @@ impl RepairStrategy for AddDefaultSectionStrategy {
     fn name(&self) -> &str {
         "AddDefaultSectionStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Add a `[default]` section when entries precede any header"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("x=1\n[a]\ny=2")
+    }
+}
+
+/// Rewrite `#` and `;` full-line comments to use one marker
+struct NormalizeCommentMarkerStrategy {
+    marker: char,
+}
+
+impl RepairStrategy for NormalizeCommentMarkerStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let other = other_comment_marker(self.marker);
+        let lines: Vec<String> = content
+            .lines()
+            .map(|line| {
+                swap_comment_marker(line, other, self.marker).unwrap_or_else(|| line.to_string())
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    fn priority(&self) -> u8 {
+        110
+    }
+
+    fn name(&self) -> &str {
+        "NormalizeCommentMarkerStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Rewrite `#` and `;` full-line comments to use one marker"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("# one\n; two\nx=1")
+    }
+}
+
+/// Fold `\`-continued and indented continuation lines into their `key = value` entry
+struct JoinContinuationLinesStrategy {
+    policy: ContinuationPolicy,
+}
+
+impl RepairStrategy for JoinContinuationLinesStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        // Each entry's line and the trimmed text of its continuation lines
+        let mut result: Vec<(String, Vec<String>)> = Vec::new();
+        let mut entry_indent: Option<usize> = None;
+        let mut continues = false;
+        for line in content.lines() {
+            let trimmed = line.trim();
+            let indent = line.len() - line.trim_start().len();
+            let is_continuation = match entry_indent {
+                Some(_) if continues => !trimmed.is_empty(),
+                Some(entry) => indent > entry && !is_skip_line(trimmed) && !trimmed.contains('='),
+                None => false,
+            };
+
+            if is_continuation {
+                let (entry, parts) = result.last_mut().expect("continuation follows an entry");
+                if parts.is_empty() {
+                    *entry = strip_continuation(entry).to_string();
+                }
+                parts.push(strip_continuation(trimmed).to_string());
+            } else {
+                entry_indent = (!is_skip_line(trimmed) && trimmed.contains('=')).then_some(indent);
+                result.push((line.to_string(), Vec::new()));
+            }
+            continues = entry_indent.is_some() && trimmed.ends_with('\\');
+        }
+
+        let lines: Vec<String> = result
+            .into_iter()
+            .map(|(entry, parts)| self.join(entry, &parts))
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    fn priority(&self) -> u8 {
+        120
+    }
+
+    fn name(&self) -> &str {
+        "JoinContinuationLinesStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Merge `\\`-continued and indented continuation lines into their entry's value"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("[s]\nkey = first \\\n  second part")
+    }
+}
+
+impl JoinContinuationLinesStrategy {
+    /// `entry` (a `key = value` line) with `parts` appended to its value
+    fn join(&self, entry: String, parts: &[String]) -> String {
+        if parts.is_empty() {
+            return entry;
+        }
+        let (key, value) = entry.split_once('=').unwrap_or((&entry, ""));
+        let value = value.trim();
+        let mut values: Vec<&str> = Vec::with_capacity(parts.len() + 1);
+        if !value.is_empty() {
+            values.push(value);
+        }
+        values.extend(parts.iter().map(String::as_str).filter(|p| !p.is_empty()));
+        match self.policy {
+            ContinuationPolicy::Join => format!("{}={}", key.trim_end(), values.join(" ")),
+            ContinuationPolicy::Quote => format!(
+                "{}=\"{}\"",
+                key.trim_end(),
+                values.join("\n").replace('"', "\\\"").replace('\n', "\\n")
+            ),
+        }
+    }
 }
 
 // --- Helpers ---
 
+/// `text` without a trailing `\` line-continuation marker
+fn strip_continuation(text: &str) -> &str {
+    text.trim_end().trim_end_matches('\\').trim_end()
+}
+
+/// Split an INI line at an inline comment: a `;` or `#` that follows whitespace
+/// and is outside a quoted value (`key = a ; note`, `[s] # note`). Returns the
+/// text before it, trimmed, and the comment from its marker on.
+fn split_inline_comment(text: &str) -> (&str, Option<&str>) {
+    let mut quote = None;
+    let mut after_space = false;
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if (c == '"' || c == '\'') && (after_space || text[..i].ends_with('=')) => {
+                quote = Some(c)
+            }
+            None if (c == ';' || c == '#') && after_space => {
+                return (text[..i].trim_end(), Some(&text[i..]));
+            }
+            None => {}
+        }
+        after_space = c.is_whitespace();
+    }
+    (text, None)
+}
+
+/// `entry` followed by its inline `comment`, if any
+fn with_inline_comment(entry: String, comment: Option<&str>) -> String {
+    match comment {
+        Some(comment) => format!("{} {}", entry, comment),
+        None => entry,
+    }
+}
+
+/// The INI comment marker that is not `marker`
+fn other_comment_marker(marker: char) -> char {
+    if marker == ';' { '#' } else { ';' }
+}
+
 fn is_skip_line(trimmed: &str) -> bool {
     trimmed.is_empty()
         || trimmed.starts_with('#')
+        || trimmed.starts_with(';')
         || trimmed.starts_with('!')
         || trimmed.starts_with('[')
 }
 
 // --- Public types ---
 
+/// How [`IniRepairer`] rewrites a value spread over continuation lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContinuationPolicy {
+    /// Join the lines into one value separated by spaces (the default)
+    #[default]
+    Join,
+    /// Keep the line breaks as `\n` escapes in a double-quoted value
+    Quote,
+}
+
 pub struct IniRepairer {
     pub inner: crate::repairer_base::GenericRepairer,
 }
 
 impl IniRepairer {
     pub fn new() -> Self {
-        let strategies: Vec<Box<dyn RepairStrategy>> = vec![
+        Self::build(None, ContinuationPolicy::default())
+    }
+
+    /// Start configuring an INI repairer
+    pub fn builder() -> IniRepairerBuilder {
+        IniRepairerBuilder::default()
+    }
+
+    /// Create an INI repairer that rewrites every full-line comment to use
+    /// `marker` (`#` or `;`). By default both markers are accepted as-is.
+    pub fn with_comment_marker(marker: char) -> Self {
+        Self::build(Some(marker), ContinuationPolicy::default())
+    }
+
+    /// Create an INI repairer that rewrites values spanning continuation lines
+    /// according to `policy`
+    pub fn with_continuation_policy(policy: ContinuationPolicy) -> Self {
+        Self::builder().continuation_policy(policy).build()
+    }
+
+    fn build(comment_marker: Option<char>, continuations: ContinuationPolicy) -> Self {
+        let mut strategies: Vec<Box<dyn RepairStrategy>> = vec![
+            Box::new(JoinContinuationLinesStrategy {
+                policy: continuations,
+            }),
             Box::new(FixMalformedSectionsStrategy),
             Box::new(FixMalformedKeysStrategy),
-            Box::new(FixMissingEqualsStrategy),
-            Box::new(FixWhitespaceAroundEqualsStrategy),
+            Box::new(FixMissingEqualsStrategy {
+                inline_comments: true,
+            }),
+            Box::new(FixWhitespaceAroundEqualsStrategy {
+                inline_comments: true,
+            }),
             Box::new(FixMalformedCommentsStrategy),
             Box::new(RemoveDuplicateSectionsStrategy),
             Box::new(AddDefaultSectionStrategy),
         ];
-        let validator: Box<dyn Validator> = Box::new(IniValidator);
+        let validator: Box<dyn Validator> = match comment_marker {
+            Some(marker) => {
+                strategies.push(Box::new(NormalizeCommentMarkerStrategy { marker }));
+                Box::new(IniStyleValidator {
+                    comment_marker: marker,
+                })
+            }
+            None => Box::new(IniValidator),
+        };
         Self {
             inner: crate::repairer_base::GenericRepairer::new(validator, strategies),
         }
     }
 }
 
+/// Builder combining any of the options of [`IniRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct IniRepairerBuilder {
+    base: BaseOptions,
+    comment_marker: Option<char>,
+    continuations: ContinuationPolicy,
+}
+
+impl IniRepairerBuilder {
+    /// Rewrite every full-line comment to use `marker` (`#` or `;`)
+    pub fn comment_marker(mut self, marker: char) -> Self {
+        self.comment_marker = Some(marker);
+        self
+    }
+
+    /// How values spanning continuation lines are rewritten
+    pub fn continuation_policy(mut self, policy: ContinuationPolicy) -> Self {
+        self.continuations = policy;
+        self
+    }
+
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Create the configured INI repairer
+    pub fn build(self) -> IniRepairer {
+        let mut repairer = IniRepairer::build(self.comment_marker, self.continuations);
+        repairer.inner.apply_options(self.base);
+        repairer
+    }
+}
+
 impl Default for IniRepairer {
     fn default() -> Self {
         Self::new()
@@ -352,6 +697,10 @@ impl Repair for IniRepairer {
         self.inner.repair(content)
     }
 
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        self.inner.repair_cow(content)
+    }
+
     fn needs_repair(&self, content: &str) -> bool {
         self.inner.needs_repair(content)
     }
@@ -387,6 +736,33 @@ impl Repair for IniRepairer {
 
 pub struct IniValidator;
 
+/// INI validator that also requires every full-line comment to use `comment_marker`
+pub struct IniStyleValidator {
+    pub comment_marker: char,
+}
+
+impl Validator for IniStyleValidator {
+    fn is_valid(&self, content: &str) -> bool {
+        IniValidator.is_valid(content) && self.validate(content).is_empty()
+    }
+
+    fn validate(&self, content: &str) -> Vec<String> {
+        let mut errors = IniValidator.validate(content);
+        let other = other_comment_marker(self.comment_marker);
+        for (i, line) in content.lines().enumerate() {
+            if line.trim_start().starts_with(other) {
+                errors.push(format!(
+                    "Comment at line {} uses '{}' instead of '{}'",
+                    i + 1,
+                    other,
+                    self.comment_marker
+                ));
+            }
+        }
+        errors
+    }
+}
+
 impl Validator for IniValidator {
     fn is_valid(&self, content: &str) -> bool {
         if content.trim().is_empty() {
@@ -395,9 +771,14 @@ impl Validator for IniValidator {
         let lines: Vec<&str> = content.lines().collect();
         for line in &lines {
             let line = line.trim();
-            if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with(';')
+                || line.starts_with('!')
+            {
                 continue;
             }
+            let (line, _) = split_inline_comment(line);
             if line.starts_with('[') && !line.ends_with(']') {
                 return false;
             }
@@ -410,7 +791,7 @@ impl Validator for IniValidator {
             .any(|l| l.trim().starts_with('[') && l.contains(']'));
         let has_keys = lines
             .iter()
-            .any(|l| l.contains('=') && !l.trim().starts_with('#') && !l.trim().starts_with('['));
+            .any(|l| l.contains('=') && !is_skip_line(l.trim()));
         has_sections || has_keys
     }
 
@@ -422,9 +803,10 @@ impl Validator for IniValidator {
         }
         for (i, line) in content.lines().enumerate() {
             let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
                 continue;
             }
+            let (line, _) = split_inline_comment(line);
             if line.starts_with('[') && !line.contains(']') {
                 errors.push(format!(
                     "Malformed section header at line {}: {}",
@@ -453,8 +835,12 @@ pub struct EnvRepairer {
 impl EnvRepairer {
     pub fn new() -> Self {
         let strategies: Vec<Box<dyn RepairStrategy>> = vec![
-            Box::new(FixMissingEqualsStrategy),
-            Box::new(FixWhitespaceAroundEqualsStrategy),
+            Box::new(FixMissingEqualsStrategy {
+                inline_comments: false,
+            }),
+            Box::new(FixWhitespaceAroundEqualsStrategy {
+                inline_comments: false,
+            }),
             Box::new(FixEmptyKeysStrategy { prefix: "ENV_VAR" }),
             Box::new(FixMalformedCommentsStrategy),
             Box::new(FixQuotedValuesStrategy),
@@ -464,6 +850,38 @@ impl EnvRepairer {
             inner: crate::repairer_base::GenericRepairer::new(validator, strategies),
         }
     }
+
+    /// Start configuring an env repairer
+    pub fn builder() -> EnvRepairerBuilder {
+        EnvRepairerBuilder::default()
+    }
+}
+
+/// Builder combining any of the options of [`EnvRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct EnvRepairerBuilder {
+    base: BaseOptions,
+}
+
+impl EnvRepairerBuilder {
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Create the configured env repairer
+    pub fn build(self) -> EnvRepairer {
+        let mut repairer = EnvRepairer::new();
+        repairer.inner.apply_options(self.base);
+        repairer
+    }
 }
 
 impl Default for EnvRepairer {
@@ -477,6 +895,10 @@ impl Repair for EnvRepairer {
         self.inner.repair(content)
     }
 
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        self.inner.repair_cow(content)
+    }
+
     fn needs_repair(&self, content: &str) -> bool {
         self.inner.needs_repair(content)
     }
@@ -555,8 +977,12 @@ pub struct PropertiesRepairer {
 impl PropertiesRepairer {
     pub fn new() -> Self {
         let strategies: Vec<Box<dyn RepairStrategy>> = vec![
-            Box::new(FixMissingEqualsStrategy),
-            Box::new(FixWhitespaceAroundEqualsStrategy),
+            Box::new(FixMissingEqualsStrategy {
+                inline_comments: false,
+            }),
+            Box::new(FixWhitespaceAroundEqualsStrategy {
+                inline_comments: false,
+            }),
             Box::new(FixEmptyKeysStrategy { prefix: "key" }),
             Box::new(FixMalformedCommentsStrategy),
             Box::new(FixQuotedValuesStrategy),
@@ -566,6 +992,38 @@ impl PropertiesRepairer {
             inner: crate::repairer_base::GenericRepairer::new(validator, strategies),
         }
     }
+
+    /// Start configuring a properties repairer
+    pub fn builder() -> PropertiesRepairerBuilder {
+        PropertiesRepairerBuilder::default()
+    }
+}
+
+/// Builder combining any of the options of [`PropertiesRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct PropertiesRepairerBuilder {
+    base: BaseOptions,
+}
+
+impl PropertiesRepairerBuilder {
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Create the configured properties repairer
+    pub fn build(self) -> PropertiesRepairer {
+        let mut repairer = PropertiesRepairer::new();
+        repairer.inner.apply_options(self.base);
+        repairer
+    }
 }
 
 impl Default for PropertiesRepairer {
@@ -579,6 +1037,10 @@ impl Repair for PropertiesRepairer {
         self.inner.repair(content)
     }
 
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        self.inner.repair_cow(content)
+    }
+
     fn needs_repair(&self, content: &str) -> bool {
         self.inner.needs_repair(content)
     }
@@ -674,6 +1136,28 @@ mod tests {
         assert!(result.contains("key2=value2"));
     }
 
+    #[test]
+    fn test_ini_accepts_both_comment_markers() {
+        let mut r = IniRepairer::new();
+        let input = "; generated\n# by hand\n[s]\nkey = a;b";
+        assert_eq!(r.repair(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_ini_normalizes_comment_marker() {
+        let mut r = IniRepairer::with_comment_marker('#');
+        let result = r
+            .repair("; generated\n[s]\n  ;; nested note\nkey=a;b")
+            .unwrap();
+        assert_eq!(result, "# generated\n[s]\n  ## nested note\nkey=a;b");
+
+        let mut r = IniRepairer::with_comment_marker(';');
+        assert_eq!(
+            r.repair("# note\n[s]\nkey=\"#1\"").unwrap(),
+            "; note\n[s]\nkey=\"#1\""
+        );
+    }
+
     #[test]
     fn test_ini_validator() {
         let v = IniValidator;
@@ -753,10 +1237,10 @@ mod tests {
     #[test]
     fn test_malformed_comment_repair() {
         let mut r = IniRepairer::new();
-        // Invalid INI (space without =) triggers strategies; comment line is normalized
-        let result = r.repair("text # comment\n=val").unwrap();
-        // After FixMalformedKeysStrategy: "text = # comment", then FixWhitespaceAroundEqualsStrategy
-        assert!(result.contains("text=# comment") || result.contains("text = # comment"));
+        // Invalid INI (space without =) triggers strategies; the inline comment
+        // stays a comment rather than becoming the value
+        let result = r.repair("text more # comment\n=val").unwrap();
+        assert!(result.contains("text=more # comment"));
     }
 
     #[test]
@@ -828,4 +1312,45 @@ mod tests {
         let result = r.repair("key=value \\\n continued").unwrap();
         assert!(result.contains("key=value"));
     }
+
+    #[test]
+    fn test_ini_continuation_lines_joined() {
+        let mut r = IniRepairer::new();
+        let input = "[s]\npath = /usr/bin \\\n  /opt/bin\nnote = first line\n    second part\n  third part\nnext = 1";
+        assert_eq!(
+            r.repair(input).unwrap(),
+            "[s]\npath=/usr/bin /opt/bin\nnote=first line second part third part\nnext=1"
+        );
+
+        // Indented keys are entries of their own, not continuations
+        let nested = "[s]\nkey value\n  inner = 2";
+        assert_eq!(r.repair(nested).unwrap(), "[s]\nkey=value\ninner=2");
+    }
+
+    #[test]
+    fn test_ini_continuation_lines_quoted() {
+        let mut r = IniRepairer::with_continuation_policy(ContinuationPolicy::Quote);
+        assert_eq!(
+            r.repair("[s]\nmotd = Hello \"there\"\n  second line")
+                .unwrap(),
+            "[s]\nmotd=\"Hello \\\"there\\\"\\nsecond line\""
+        );
+    }
+
+    #[test]
+    fn test_ini_inline_comments() {
+        let input = "[s] # main\nkey = a ; note\nquoted = \"a;b\"";
+        assert!(IniValidator.is_valid(input));
+
+        let mut r = IniRepairer::new();
+        assert_eq!(
+            r.repair("[s ; main\nkey   =   a ; note\nhost localhost # dev\nquoted = \"a;b\"")
+                .unwrap(),
+            "[s] ; main\nkey=a ; note\nhost=localhost # dev\nquoted=\"a;b\""
+        );
+        assert_eq!(
+            split_inline_comment("url = http://x/#top"),
+            ("url = http://x/#top", None)
+        );
+    }
 }
@@ -3,27 +3,61 @@
 //! A Rust crate for repairing malformed structured data including JSON, YAML,
 //! XML, TOML, CSV, INI, Markdown, and Diff with format auto-detection.
 
+pub mod analytics;
+pub mod audit;
+pub mod cache;
+pub mod convert;
 pub mod csv;
 pub mod diff;
+pub mod dotenv;
+pub mod edit;
 pub mod error;
 pub mod json_util;
 pub mod format_detection;
+#[cfg(feature = "geojson")]
+pub mod geojson;
 pub mod json;
 pub mod key_value;
+pub mod lint;
+pub mod lsp;
 pub mod markdown;
 pub mod mcp_server;
+pub mod merge;
+pub mod paths;
+pub mod plugin_config;
+pub mod registry;
 pub mod repairer_base;
+pub mod report;
+pub mod schema;
+pub mod semantic;
 pub mod streaming;
 pub mod toml;
 pub mod traits;
 pub mod xml;
 pub mod yaml;
 
+pub use audit::{AuditEntry, AuditLogger};
+pub use cache::RepairCache;
+pub use convert::{
+    csv_to_json, json_to_csv, json_to_toml, json_to_yaml, repair_table_json, toml_to_json,
+    yaml_to_json,
+};
 pub use diff::DiffRepairer;
 pub use error::{RepairError, Result};
-pub use json::JsonRepairer;
+pub use dotenv::DotenvRepairer;
+pub use json::{JsonDialect, JsonProfile, JsonRepairer, KeyCase, UnicodeOutput};
 pub use key_value::{EnvRepairer, IniRepairer, PropertiesRepairer};
+pub use lint::{LintFinding, LintSeverity};
+pub use lsp::{LspDiagnostic, validate_lsp};
 pub use mcp_server::AnyrepairMcpServer;
+pub use merge::{MergeConflict, merge_json_fragments, merge_json_fragments_with};
+pub use paths::assemble_from_paths;
+pub use registry::{FormatDetector, register_detector, register_format, register_repairer};
+pub use report::{
+    Assessment, RemovedSpan, RepairReport, SafetyMode, StrategyInfo, Trivia, TriviaKind,
+};
+pub use schema::validate_schema;
+pub use semantic::{SemanticRepairProvider, SemanticRepairer};
 pub use streaming::StreamingRepair;
 pub use traits::Repair;
 
@@ -40,6 +74,7 @@ pub const SUPPORTED_FORMATS: &[&str] = &[
     "diff",
     "properties",
     "env",
+    "dotenv",
 ];
 
 /// Normalize a format alias to its canonical name.
@@ -60,7 +95,7 @@ pub fn normalize_format(format: &str) -> &str {
     format
 }
 
-fn parse_supported_format(format: &str) -> Result<&'static str> {
+pub(crate) fn parse_supported_format(format: &str) -> Result<&'static str> {
     let n = normalize_format(format);
     SUPPORTED_FORMATS
         .iter()
@@ -70,9 +105,17 @@ fn parse_supported_format(format: &str) -> Result<&'static str> {
 }
 
 /// Create a boxed [`Repair`] instance for the given format.
-/// Accepts canonical names and aliases (e.g. `yml`, `md`).
-/// Returns `RepairError::FormatDetection` if the format is unknown.
+/// Accepts canonical names, aliases (e.g. `yml`, `md`) and formats added with
+/// [`register_repairer`]. Returns `RepairError::FormatDetection` if the format is unknown.
 pub fn create_repairer(format: &str) -> Result<Box<dyn Repair>> {
+    match registry::custom_repairer(format) {
+        Some(repairer) => Ok(repairer),
+        None => create_builtin_repairer(format),
+    }
+}
+
+/// Like [`create_repairer`], ignoring repairers added with [`register_repairer`]
+pub(crate) fn create_builtin_repairer(format: &str) -> Result<Box<dyn Repair>> {
     match parse_supported_format(format)? {
         "json" => Ok(Box::new(json::JsonRepairer::new())),
         "yaml" => Ok(Box::new(yaml::YamlRepairer::new())),
@@ -84,6 +127,7 @@ pub fn create_repairer(format: &str) -> Result<Box<dyn Repair>> {
         "diff" => Ok(Box::new(diff::DiffRepairer::new())),
         "properties" => Ok(Box::new(key_value::PropertiesRepairer::new())),
         "env" => Ok(Box::new(key_value::EnvRepairer::new())),
+        "dotenv" => Ok(Box::new(dotenv::DotenvRepairer::new())),
         other => Err(RepairError::FormatDetection(format!(
             "Unknown format: {}",
             other
@@ -106,6 +150,7 @@ pub fn create_validator(format: &str) -> Result<Box<dyn traits::Validator>> {
         "diff" => Ok(Box::new(diff::DiffValidator)),
         "properties" => Ok(Box::new(key_value::PropertiesValidator)),
         "env" => Ok(Box::new(key_value::EnvValidator)),
+        "dotenv" => Ok(Box::new(dotenv::DotenvValidator)),
         other => Err(RepairError::FormatDetection(format!(
             "Unknown format: {}",
             other
@@ -113,6 +158,18 @@ pub fn create_validator(format: &str) -> Result<Box<dyn traits::Validator>> {
     }
 }
 
+/// Check whether `content` is already valid `format`, without building a repairer.
+/// Accepts the same names and aliases as [`create_validator`]; an unknown format is never valid.
+pub fn is_valid(content: &str, format: &str) -> bool {
+    create_validator(format).is_ok_and(|validator| validator.is_valid(content))
+}
+
+/// Detect the format of `content` like [`detect_format`] and check it is valid in
+/// that format. Returns the detected format if so, `None` if undetected or invalid.
+pub fn is_valid_auto(content: &str) -> Option<&'static str> {
+    detect_format(content).filter(|format| is_valid(content, format))
+}
+
 /// Repair content using an explicit format.
 /// Convenience wrapper around `create_repairer` + `Repair::repair`.
 pub fn repair_with_format(content: &str, format: &str) -> Result<String> {
@@ -123,14 +180,95 @@ pub fn repair_with_format(content: &str, format: &str) -> Result<String> {
 /// Repair content with automatic format detection.
 /// Falls back to the Markdown repairer if no format is detected.
 pub fn repair(content: &str) -> Result<String> {
-    let trimmed = content.trim();
-    if let Some(fmt) = detect_format(trimmed) {
+    repair_cow(content).map(std::borrow::Cow::into_owned)
+}
+
+/// Like [`repair`], but borrows `content` instead of allocating when it needs no change
+pub fn repair_cow(content: &str) -> Result<std::borrow::Cow<'_, str>> {
+    if let Some(fmt) = detect_format(content) {
         let mut repairer = create_repairer(fmt)?;
-        repairer.repair(trimmed)
+        repairer.repair_cow(content)
     } else {
         let mut repairer = markdown::MarkdownRepairer::new();
-        repairer.repair(trimmed)
+        repairer.repair_cow(content)
+    }
+}
+
+/// Read all of `reader`, repair it as `format` (detected like [`repair`] if `None`)
+/// and write the result to `writer`. Returns the number of bytes written.
+/// Returns `RepairError::Io` if reading, writing or UTF-8 decoding fails.
+pub fn repair_io<R: std::io::Read, W: std::io::Write>(
+    mut reader: R,
+    mut writer: W,
+    format: Option<&str>,
+) -> Result<usize> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+    let repaired = match format {
+        Some(format) => create_repairer(format)?.repair_cow(&content)?,
+        None => repair_cow(&content)?,
+    };
+    writer.write_all(repaired.as_bytes())?;
+    writer.flush()?;
+    Ok(repaired.len())
+}
+
+/// Read and repair a file, picking the format from its extension.
+/// Falls back to content detection (like [`repair`]) if the extension is unknown.
+pub fn repair_file(path: &std::path::Path) -> Result<String> {
+    let content = std::fs::read_to_string(path)?;
+    match format_detection::format_from_path(path) {
+        Some(format) => repair_with_format(&content, format),
+        None => repair(&content),
+    }
+}
+
+/// Repair a file and write the result back to it.
+/// Returns whether the file content changed; unchanged files are not rewritten.
+pub fn repair_file_in_place(path: &std::path::Path) -> Result<bool> {
+    let original = std::fs::read_to_string(path)?;
+    let repaired = repair_file(path)?;
+    if repaired == original {
+        return Ok(false);
+    }
+    std::fs::write(path, repaired)?;
+    Ok(true)
+}
+
+/// Repair each of `inputs` like [`repair`] on up to `jobs` threads, returning the
+/// results in input order. A `jobs` of 0 or 1 repairs serially on the calling thread.
+pub fn repair_batch(inputs: &[&str], jobs: usize) -> Vec<Result<String>> {
+    if jobs <= 1 || inputs.len() <= 1 {
+        return inputs.iter().map(|content| repair(content)).collect();
     }
+
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let mut indexed: Vec<(usize, Result<String>)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.min(inputs.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        let Some(content) = inputs.get(index) else {
+                            break done;
+                        };
+                        done.push((index, repair(content)));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    indexed.sort_by_key(|(index, _)| *index);
+    indexed.into_iter().map(|(_, result)| result).collect()
 }
 
 /// Detect the format of the given content.
@@ -158,23 +296,57 @@ pub fn jsonrepair(json_str: &str) -> Result<String> {
     repairer.repair(json_str)
 }
 
+/// Repair a JSON string and return it in its most compact form.
+///
+/// Unlike [`jsonrepair`], which keeps incidental whitespace, all whitespace outside
+/// string literals is removed. Returns `RepairError::JsonSyntax` if the input could
+/// not be repaired into valid JSON.
+pub fn jsonrepair_minified(json_str: &str) -> Result<String> {
+    let repaired = jsonrepair(json_str)?;
+    if let Some(e) = json_util::json_syntax_error(&repaired) {
+        return Err(e.into());
+    }
+    Ok(json_util::minify_json(&repaired))
+}
+
 /// Repair content with a specific format and return the list of strategies that changed it.
 /// Returns `(repaired_content, applied_strategy_names)`.
 pub fn repair_with_explanations(content: &str, format: &str) -> Result<(String, Vec<String>)> {
-    let trimmed = content.trim();
+    builtin_repairer(format)?.repair_with_explanations(content)
+}
+
+/// List the strategies of a built-in format in the order they run.
+/// Returns `RepairError::FormatDetection` if the format is unknown.
+pub fn list_strategies(format: &str) -> Result<Vec<StrategyInfo>> {
+    Ok(builtin_repairer(format)?
+        .strategies()
+        .iter()
+        .map(|strategy| StrategyInfo {
+            name: strategy.name().to_string(),
+            priority: strategy.priority(),
+            description: strategy.description().to_string(),
+            example: strategy.example().and_then(|input| {
+                let output = strategy.apply(input).ok()?;
+                Some((input.to_string(), output))
+            }),
+        })
+        .collect())
+}
+
+/// The default strategy pipeline of a built-in format
+fn builtin_repairer(format: &str) -> Result<repairer_base::GenericRepairer> {
     match parse_supported_format(format)? {
-        "json" => json::JsonRepairer::new().inner.repair_with_explanations(trimmed),
-        "yaml" => yaml::YamlRepairer::new().inner.repair_with_explanations(trimmed),
-        "markdown" => markdown::MarkdownRepairer::new().inner.repair_with_explanations(trimmed),
-        "xml" => xml::XmlRepairer::new().inner.repair_with_explanations(trimmed),
-        "toml" => toml::TomlRepairer::new().inner.repair_with_explanations(trimmed),
-        "csv" => csv::CsvRepairer::new().inner.repair_with_explanations(trimmed),
-        "ini" => key_value::IniRepairer::new().inner.repair_with_explanations(trimmed),
-        "diff" => diff::DiffRepairer::new().inner.repair_with_explanations(trimmed),
-        "properties" => {
-            key_value::PropertiesRepairer::new().inner.repair_with_explanations(trimmed)
-        }
-        "env" => key_value::EnvRepairer::new().inner.repair_with_explanations(trimmed),
+        "json" => Ok(json::JsonRepairer::new().inner),
+        "yaml" => Ok(yaml::YamlRepairer::new().inner),
+        "markdown" => Ok(markdown::MarkdownRepairer::new().inner),
+        "xml" => Ok(xml::XmlRepairer::new().inner),
+        "toml" => Ok(toml::TomlRepairer::new().inner),
+        "csv" => Ok(csv::CsvRepairer::new().inner),
+        "ini" => Ok(key_value::IniRepairer::new().inner),
+        "diff" => Ok(diff::DiffRepairer::new().inner),
+        "properties" => Ok(key_value::PropertiesRepairer::new().inner),
+        "env" => Ok(key_value::EnvRepairer::new().inner),
+        "dotenv" => Ok(dotenv::DotenvRepairer::new().inner),
         other => Err(RepairError::FormatDetection(format!(
             "Unknown format: {}",
             other
@@ -182,10 +354,62 @@ pub fn repair_with_explanations(content: &str, format: &str) -> Result<(String,
     }
 }
 
+/// Repair content with a specific format and report the applied strategies along
+/// with the repairer's confidence before and after and the content it deleted.
+/// Returns `RepairError::FormatDetection` if the format is unknown.
+pub fn repair_report(content: &str, format: &str) -> Result<RepairReport> {
+    let (repaired, applied_strategies, removed) =
+        builtin_repairer(format)?.repair_with_removals(content)?;
+    let repairer = create_repairer(format)?;
+    Ok(RepairReport {
+        confidence_before: repairer.confidence(content),
+        confidence_after: repairer.confidence(&repaired),
+        repaired,
+        applied_strategies,
+        removed,
+    })
+}
+
+/// Classify content as valid, repairable or unrepairable for `format` by
+/// validating it, then repairing and re-validating.
+/// Returns `RepairError::FormatDetection` if the format is unknown.
+pub fn assess(content: &str, format: &str) -> Result<Assessment> {
+    let validator = create_validator(format)?;
+    if validator.is_valid(content) {
+        return Ok(Assessment::Valid);
+    }
+    let mut repairer = create_repairer(format)?;
+    let repaired = match repairer.repair(content) {
+        Ok(repaired) => repaired,
+        Err(_) => return Ok(Assessment::Unrepairable),
+    };
+    if validator.is_valid(&repaired) {
+        Ok(Assessment::Repairable {
+            confidence: repairer.confidence(&repaired),
+        })
+    } else {
+        Ok(Assessment::Unrepairable)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_assess() {
+        assert_eq!(assess(r#"{"a": 1}"#, "json").unwrap(), Assessment::Valid);
+        assert!(matches!(
+            assess(r#"{"a": 1,}"#, "json").unwrap(),
+            Assessment::Repairable { confidence } if confidence > 0.9
+        ));
+        assert_eq!(
+            assess("\u{1}\u{7f}@@@ \u{fffd}", "json").unwrap(),
+            Assessment::Unrepairable
+        );
+        assert!(assess("{}", "nope").is_err());
+    }
+
     #[test]
     fn test_format_detection() {
         assert_eq!(detect_format(r#"{"key": "value"}"#), Some("json"));
@@ -220,9 +444,217 @@ mod tests {
         assert!(!repaired.ends_with(','));
     }
 
+    #[test]
+    fn test_jsonrepair_minified() {
+        let malformed =
+            "{\n  \"name\": \"John Smith\",\n  age: 30,\n  \"tags\": [ \"a b\", 'c' ],\n}\n";
+        let minified = jsonrepair_minified(malformed).unwrap();
+        assert_eq!(
+            minified,
+            r#"{"name":"John Smith","age":30,"tags":["a b","c"]}"#
+        );
+        assert!(json_util::is_valid_json(&minified));
+
+        let outside_strings = minified.replace("John Smith", "").replace("a b", "");
+        assert!(!outside_strings.contains(' '));
+        assert!(!minified.contains('\n'));
+    }
+
+    #[test]
+    fn test_jsonrepair_minified_keeps_escaped_quotes() {
+        let minified = jsonrepair_minified(r#"{ "q": "say \" hi \" " }"#).unwrap();
+        assert_eq!(minified, r#"{"q":"say \" hi \" "}"#);
+    }
+
+    #[test]
+    fn test_repair_report_confidence_improves() {
+        let report = repair_report("[1, 2, 3,]", "json").unwrap();
+        assert_eq!(report.repaired, "[1, 2, 3]");
+        assert!(report.confidence_after > report.confidence_before);
+        assert_eq!(report.confidence_after, 1.0);
+        assert!(!report.degraded());
+        assert!(report.changed());
+    }
+
+    #[test]
+    fn test_list_strategies() {
+        let strategies = list_strategies("JSON").unwrap();
+        let trailing_commas = strategies
+            .iter()
+            .find(|s| s.name == "FixTrailingCommas")
+            .unwrap();
+        assert_eq!(trailing_commas.priority, 90);
+        assert_eq!(trailing_commas.description, "Fix trailing commas");
+        assert_eq!(
+            trailing_commas.example,
+            Some((
+                r#"{"a": [1, 2,],}"#.to_string(),
+                r#"{"a": [1, 2]}"#.to_string()
+            ))
+        );
+        assert!(
+            strategies
+                .windows(2)
+                .all(|pair| pair[0].priority >= pair[1].priority)
+        );
+        assert!(list_strategies("nope").is_err());
+
+        // Every example demonstrates an actual change
+        for format in SUPPORTED_FORMATS {
+            for strategy in list_strategies(format).unwrap() {
+                if let Some((input, output)) = &strategy.example {
+                    assert_ne!(input, output, "{} ({})", strategy.name, format);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_repair_file_uses_extension() {
+        let dir =
+            std::env::temp_dir().join(format!("anyrepair_repair_file_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let json = dir.join("data.json");
+        std::fs::write(&json, "{\"a\": 1,}\n").unwrap();
+        assert_eq!(repair_file(&json).unwrap(), "{\"a\": 1}\n");
+
+        // Detected as YAML from content alone, but the extension says JSON
+        let fragment = dir.join("fragment.json");
+        std::fs::write(&fragment, "\"a\": 1").unwrap();
+        assert_eq!(repair_file(&fragment).unwrap(), "{\"a\": 1}");
+
+        let yaml = dir.join("config.YML");
+        std::fs::write(&yaml, "name John\nage: 30").unwrap();
+        assert_eq!(repair_file(&yaml).unwrap(), "---\nname: John\nage: 30");
+
+        let unknown = dir.join("notes.txt");
+        std::fs::write(&unknown, "[1, 2,]").unwrap();
+        assert_eq!(repair_file(&unknown).unwrap(), "[1, 2]");
+
+        assert!(matches!(
+            repair_file(&dir.join("missing.json")),
+            Err(RepairError::Io(_))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_repair_file_in_place() {
+        let dir = std::env::temp_dir().join(format!("anyrepair_in_place_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.json");
+
+        std::fs::write(&path, "[1, 2,]").unwrap();
+        assert!(repair_file_in_place(&path).unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[1, 2]");
+        assert!(!repair_file_in_place(&path).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_repair_io() {
+        let mut out = Vec::new();
+        let written = repair_io(std::io::Cursor::new("[1, 2,]\n"), &mut out, None).unwrap();
+        assert_eq!(out, b"[1, 2]\n");
+        assert_eq!(written, out.len());
+
+        let mut out = Vec::new();
+        repair_io("name John\nage: 30".as_bytes(), &mut out, Some("yml")).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "---\nname: John\nage: 30");
+
+        assert!(matches!(
+            repair_io(std::io::Cursor::new("{}"), Vec::new(), Some("nope")),
+            Err(RepairError::FormatDetection(_))
+        ));
+        assert!(matches!(
+            repair_io(&[0xff, 0xfe][..], Vec::new(), Some("json")),
+            Err(RepairError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        use format_detection::format_from_path;
+        use std::path::Path;
+        assert_eq!(format_from_path(Path::new("a/b.yaml")), Some("yaml"));
+        assert_eq!(format_from_path(Path::new("fix.patch")), Some("diff"));
+        assert_eq!(format_from_path(Path::new(".env.local")), Some("env"));
+        assert_eq!(format_from_path(Path::new("README")), None);
+        assert_eq!(format_from_path(Path::new("x.txt")), None);
+    }
+
+    #[test]
+    fn test_deep_nesting_errors_cleanly() {
+        let deep = format!("{}1{}", "[".repeat(50_000), "]".repeat(50_000));
+        for format in SUPPORTED_FORMATS {
+            let result = repair_with_format(&deep, format);
+            if matches!(*format, "json" | "yaml" | "toml") {
+                assert!(
+                    matches!(
+                        result,
+                        Err(RepairError::MaxDepthExceeded {
+                            depth: 50_000,
+                            limit: 512
+                        })
+                    ),
+                    "{}",
+                    format
+                );
+            } else {
+                assert!(result.is_ok(), "{}", format);
+            }
+        }
+        assert!(matches!(
+            validate_schema(&deep, "{}"),
+            Err(RepairError::MaxDepthExceeded { limit: 1024, .. })
+        ));
+    }
+
     #[test]
     fn test_repair_error_handling() {
         let result = repair("");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_repair_preserves_crlf_line_endings() {
+        let input = "{\r\n  \"a\": 1,\r\n  \"b\": 2,\r\n}\r\n";
+        let repaired = repair_with_format(input, "json").unwrap();
+        assert_eq!(repaired, "{\r\n  \"a\": 1,\r\n  \"b\": 2\r\n}\r\n");
+    }
+
+    #[test]
+    fn test_repair_preserves_final_newline() {
+        let repaired = repair_with_format("{\"a\": 1,}\n", "json").unwrap();
+        assert_eq!(repaired, "{\"a\": 1}\n");
+
+        let repaired = repair_with_format("{\"a\": 1,}", "json").unwrap();
+        assert_eq!(repaired, "{\"a\": 1}");
+
+        // Valid content round-trips unchanged
+        let valid = "name: John\nage: 30\n";
+        assert_eq!(repair_with_format(valid, "yaml").unwrap(), valid);
+    }
+
+    #[test]
+    fn test_bom_prefixed_json_detected_and_repaired() {
+        let input = "\u{FEFF}{\"a\": 1,}";
+        assert_eq!(detect_format(input), Some("json"));
+        assert_eq!(repair(input).unwrap(), "{\"a\": 1}");
+
+        // Valid content only loses the BOM
+        assert_eq!(jsonrepair("\u{FEFF}[1, 2]").unwrap(), "[1, 2]");
+    }
+
+    #[test]
+    fn test_bom_preserved_when_requested() {
+        let mut repairer = json::JsonRepairer::new();
+        repairer.inner.set_preserve_bom(true);
+        assert_eq!(
+            repairer.repair("\u{FEFF}{\"a\": 1,}\n").unwrap(),
+            "\u{FEFF}{\"a\": 1}\n"
+        );
+        assert_eq!(repairer.repair("{\"a\": 1,}").unwrap(), "{\"a\": 1}");
+    }
 }
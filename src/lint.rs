@@ -0,0 +1,110 @@
+//! Lint findings: what a repair would fix, without fixing it
+//!
+//! Findings are derived from the edits a repairer's `preview` proposes, so a
+//! linter reports exactly the problems `repair` would change, at their location
+//! in the original input.
+
+use crate::edit::{Edit, original_range};
+use std::ops::Range;
+
+/// How serious a [`LintFinding`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintSeverity {
+    /// The content is not valid without the fix
+    Error,
+    /// The fix drops content around the document, such as prose or comments
+    Warning,
+}
+
+/// A problem `repair` would fix, located in the original input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    /// Rule id derived from the strategy proposing the fix (`trailing-comma`)
+    pub rule: String,
+    pub severity: LintSeverity,
+    /// Byte range of the affected text in the original input
+    pub byte_range: Range<usize>,
+    /// One-based line of the start of `byte_range`
+    pub line: usize,
+    /// One-based column, in chars, of the start of `byte_range`
+    pub column: usize,
+    /// Text the fix would put in place of `byte_range` (empty for a deletion)
+    pub replacement: String,
+}
+
+/// Rule id for a strategy name: kebab case, without a leading verb and with a
+/// singular last word (`FixTrailingCommas` -> `trailing-comma`)
+pub fn rule_id(strategy: &str) -> String {
+    const VERBS: [&str; 9] = [
+        "Fix",
+        "Add",
+        "Strip",
+        "Remove",
+        "Insert",
+        "Normalize",
+        "Escape",
+        "Quote",
+        "Convert",
+    ];
+
+    let mut words: Vec<String> = Vec::new();
+    for ch in strategy.chars() {
+        match words.last_mut() {
+            Some(word) if !ch.is_uppercase() => word.push(ch),
+            _ => words.push(ch.to_string()),
+        }
+    }
+    if words.len() > 1 && VERBS.contains(&words[0].as_str()) {
+        words.remove(0);
+    }
+    if let Some(last) = words.last_mut()
+        && last.ends_with('s')
+        && !last.ends_with("ss")
+    {
+        last.pop();
+    }
+    words
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Turn sequential `edits` proposed for `content` into findings located in `content`
+pub fn findings_from_edits(
+    content: &str,
+    edits: &[Edit],
+    severity: impl Fn(&str) -> LintSeverity,
+) -> Vec<LintFinding> {
+    edits
+        .iter()
+        .enumerate()
+        .map(|(i, edit)| {
+            let byte_range = original_range(&edits[..i], edit.byte_range.clone());
+            let before = &content[..byte_range.start];
+            let line_start = before.rfind('\n').map_or(0, |at| at + 1);
+            LintFinding {
+                rule: rule_id(&edit.strategy),
+                severity: severity(&edit.strategy),
+                line: before.matches('\n').count() + 1,
+                column: before[line_start..].chars().count() + 1,
+                byte_range,
+                replacement: edit.replacement.clone(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_id() {
+        assert_eq!(rule_id("FixTrailingCommas"), "trailing-comma");
+        assert_eq!(rule_id("StripJsComments"), "js-comment");
+        assert_eq!(rule_id("FixBooleanNull"), "boolean-null");
+        assert_eq!(rule_id("UnwrapMarkup"), "unwrap-markup");
+        assert_eq!(rule_id("Fix"), "fix");
+    }
+}
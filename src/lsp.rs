@@ -0,0 +1,203 @@
+//! Validation diagnostics in the Language Server Protocol shape
+//!
+//! [`validate_lsp`] returns diagnostics whose JSON form matches LSP's
+//! `Diagnostic` (zero-based lines, UTF-16 character offsets), so editor
+//! extensions can forward them without conversion.
+
+use crate::error::Result;
+use crate::json_util::{json_string, json_syntax_error};
+
+/// Zero-based position; `character` counts UTF-16 code units as LSP requires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LspPosition {
+    pub line: u32,
+    pub character: u32,
+}
+
+/// Half-open range between two positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LspRange {
+    pub start: LspPosition,
+    pub end: LspPosition,
+}
+
+/// Diagnostic severity, numbered as in the LSP specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error = 1,
+    Warning = 2,
+    Information = 3,
+    Hint = 4,
+}
+
+/// A single validation problem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LspDiagnostic {
+    pub range: LspRange,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+}
+
+impl LspPosition {
+    /// Position of byte `offset` in `content` (clamped to the end of the content)
+    pub fn from_offset(content: &str, offset: usize) -> Self {
+        let mut offset = offset.min(content.len());
+        while !content.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &content[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            line: before.matches('\n').count() as u32,
+            character: before[line_start..].encode_utf16().count() as u32,
+        }
+    }
+
+    fn to_json(self) -> String {
+        format!(
+            "{{\"line\":{},\"character\":{}}}",
+            self.line, self.character
+        )
+    }
+}
+
+impl LspDiagnostic {
+    /// Serialize as an LSP `Diagnostic` object
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"range\":{{\"start\":{},\"end\":{}}},\"severity\":{},\"source\":\"anyrepair\",\"message\":{}}}",
+            self.range.start.to_json(),
+            self.range.end.to_json(),
+            self.severity as u8,
+            json_string(&self.message)
+        )
+    }
+}
+
+/// Serialize diagnostics as a JSON array
+pub fn diagnostics_to_json(diagnostics: &[LspDiagnostic]) -> String {
+    let items: Vec<String> = diagnostics.iter().map(LspDiagnostic::to_json).collect();
+    format!("[{}]", items.join(","))
+}
+
+/// Validate `content` as `format` and report problems as LSP diagnostics.
+///
+/// JSON errors point at the offending character. Other validators do not track
+/// positions yet, so their messages span the whole document.
+/// Returns `RepairError::FormatDetection` if the format is unknown.
+pub fn validate_lsp(content: &str, format: &str) -> Result<Vec<LspDiagnostic>> {
+    let validator = crate::create_validator(format)?;
+
+    if crate::normalize_format(format) == "json" {
+        return Ok(json_syntax_error(content)
+            .map(|e| {
+                let end = content[e.offset.min(content.len())..]
+                    .chars()
+                    .next()
+                    .map_or(e.offset, |c| e.offset + c.len_utf8());
+                error(content, e.offset, end, e.message)
+            })
+            .into_iter()
+            .collect());
+    }
+
+    Ok(validator
+        .validate(content)
+        .into_iter()
+        .map(|message| error(content, 0, content.len(), message))
+        .collect())
+}
+
+fn error(content: &str, start: usize, end: usize, message: String) -> LspDiagnostic {
+    LspDiagnostic {
+        range: LspRange {
+            start: LspPosition::from_offset(content, start),
+            end: LspPosition::from_offset(content, end),
+        },
+        severity: DiagnosticSeverity::Error,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trailing_comma_position() {
+        let content = "{\n  \"a\": 1,\n}";
+        let diagnostics = validate_lsp(content, "json").unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        let range = diagnostics[0].range;
+        assert_eq!(
+            range.start,
+            LspPosition {
+                line: 1,
+                character: 8
+            }
+        );
+        assert_eq!(
+            range.end,
+            LspPosition {
+                line: 1,
+                character: 9
+            }
+        );
+        assert_eq!(diagnostics[0].message, "trailing comma in object");
+    }
+
+    #[test]
+    fn test_position_counts_utf16_units() {
+        // The emoji is two UTF-16 code units
+        let content = "[\"😀\" 1]";
+        let diagnostics = validate_lsp(content, "json").unwrap();
+        assert_eq!(
+            diagnostics[0].range.start,
+            LspPosition {
+                line: 0,
+                character: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_valid_content_has_no_diagnostics() {
+        assert!(validate_lsp("{\"a\": [1, 2]}", "json").unwrap().is_empty());
+        assert!(validate_lsp("name: John", "yml").unwrap().is_empty());
+        assert!(validate_lsp("x", "nope").is_err());
+    }
+
+    #[test]
+    fn test_diagnostics_json_shape() {
+        let diagnostics = validate_lsp("[1,]", "json").unwrap();
+        assert_eq!(
+            diagnostics_to_json(&diagnostics),
+            "[{\"range\":{\"start\":{\"line\":0,\"character\":2},\"end\":{\"line\":0,\"character\":3}},\
+             \"severity\":1,\"source\":\"anyrepair\",\"message\":\"trailing comma in array\"}]"
+        );
+        assert!(crate::json_util::is_valid_json(&diagnostics_to_json(
+            &diagnostics
+        )));
+    }
+
+    #[test]
+    fn test_unpositioned_issue_spans_document() {
+        let content = "name John\nage: 30";
+        let diagnostics = validate_lsp(content, "yaml").unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].range.start,
+            LspPosition {
+                line: 0,
+                character: 0
+            }
+        );
+        assert_eq!(
+            diagnostics[0].range.end,
+            LspPosition {
+                line: 1,
+                character: 7
+            }
+        );
+    }
+}
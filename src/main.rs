@@ -18,6 +18,10 @@ pub struct Cli {
     #[arg(short, long)]
     quiet: bool,
 
+    /// Plugin configuration file; its `[plugins]` section selects the plugins to run
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -46,6 +50,10 @@ enum Commands {
         #[arg(short, long)]
         format: Option<String>,
 
+        /// Format of piped input, used only when reading stdin (--format takes precedence)
+        #[arg(long, value_name = "FORMAT")]
+        stdin_format: Option<String>,
+
         /// Show a diff of changes without writing output
         #[arg(long)]
         diff: bool,
@@ -66,6 +74,14 @@ enum Commands {
         #[arg(long)]
         explain: bool,
 
+        /// Print a repair report (confidence before/after, applied strategies) to stderr
+        #[arg(long)]
+        report: bool,
+
+        /// Validate the repaired JSON against this JSON Schema file; violations fail the run
+        #[arg(long, value_name = "FILE")]
+        schema: Option<String>,
+
         /// Color output: auto, always, never
         #[arg(long, value_name = "WHEN", default_value = "auto")]
         color: String,
@@ -97,6 +113,32 @@ enum Commands {
         /// Recursive directory processing
         #[arg(short, long)]
         recursive: bool,
+
+        /// Number of files to repair in parallel (defaults to the number of CPUs)
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+
+        /// Keep going when a file fails and list the failures at the end
+        #[arg(long)]
+        continue_on_error: bool,
+    },
+    /// Repair content and convert it between JSON, YAML, TOML and CSV
+    Convert {
+        /// Format of the input: json, yaml, toml, csv, or auto to detect it
+        #[arg(long, default_value = "auto")]
+        from: String,
+
+        /// Format to convert to: json, yaml, toml, csv
+        #[arg(long)]
+        to: String,
+
+        /// Input file (stdin if not provided)
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// Output file (stdout if not provided)
+        #[arg(short, long)]
+        output: Option<String>,
     },
     /// Generate shell completions
     Completions {
@@ -104,6 +146,11 @@ enum Commands {
         #[arg(value_name = "SHELL")]
         shell: String,
     },
+    /// List the repair strategies of each format
+    Rules {
+        #[command(subcommand)]
+        action: RulesAction,
+    },
     /// Stream repair for large files
     Stream {
         /// Input file (stdin if not provided)
@@ -118,31 +165,69 @@ enum Commands {
         #[arg(short, long)]
         format: Option<String>,
 
+        /// Format of piped input, used only when reading stdin (--format takes precedence)
+        #[arg(long, value_name = "FORMAT")]
+        stdin_format: Option<String>,
+
         /// Buffer size in bytes
         #[arg(long)]
         buffer_size: Option<usize>,
     },
 }
 
+#[derive(Subcommand)]
+enum RulesAction {
+    /// List strategies in the order they run
+    List {
+        /// Only list the strategies of this format
+        #[arg(short, long)]
+        format: Option<String>,
+    },
+    /// Show the priority and description of a strategy
+    Show {
+        /// Strategy name, e.g. FixTrailingCommas
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Only look in this format
+        #[arg(short, long)]
+        format: Option<String>,
+    },
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
     let start_time = Instant::now();
 
+    if let Some(path) = &cli.config {
+        let config = anyrepair::plugin_config::PluginConfig::from_file(path)?;
+        anyrepair::plugin_config::PluginRegistry::from_config(&config)?.install();
+    }
+
     match cli.command {
-        Commands::Repair { file, input, output, confidence, format, diff, dry_run, json, min_confidence, explain, color } => {
+        Commands::Repair { file, input, output, confidence, format, stdin_format, diff, dry_run, json, min_confidence, explain, report, schema, color } => {
             let input_path = file.as_deref().or(input.as_deref());
-            cli::repair_cmd::handle_repair(input_path, output.as_deref(), confidence, cli.verbose, format.as_deref(), diff, dry_run, json, min_confidence, explain, &color)?;
+            let format = cli::effective_format(format, stdin_format, input_path);
+            cli::repair_cmd::handle_repair(input_path, output.as_deref(), confidence, cli.verbose, format.as_deref(), diff, dry_run, json, min_confidence, explain, report, schema.as_deref(), &color)?;
         }
         Commands::Validate { input, format } => {
             cli::validate_cmd::handle_validate(input.as_deref(), format.as_deref(), cli.verbose)?;
         }
-        Commands::Batch { input, output, pattern, recursive } => {
-            cli::batch_cmd::handle_batch(&input, &output, pattern.as_deref(), recursive, cli.verbose)?;
+        Commands::Batch { input, output, pattern, recursive, jobs, continue_on_error } => {
+            cli::batch_cmd::handle_batch(&input, &output, pattern.as_deref(), recursive, jobs, continue_on_error, cli.verbose)?;
         }
-        Commands::Stream { input, output, format, buffer_size } => {
+        Commands::Stream { input, output, format, stdin_format, buffer_size } => {
+            let format = cli::effective_format(format, stdin_format, input.as_deref());
             let fmt = format.as_deref().unwrap_or("auto");
             cli::stream_cmd::handle_stream(input.as_deref(), output.as_deref(), fmt, buffer_size, cli.verbose)?;
         }
+        Commands::Rules { action } => match action {
+            RulesAction::List { format } => cli::rules_cmd::handle_rules(None, format.as_deref())?,
+            RulesAction::Show { name, format } => cli::rules_cmd::handle_rules(Some(&name), format.as_deref())?,
+        },
+        Commands::Convert { from, to, input, output } => {
+            cli::convert_cmd::handle_convert(&from, &to, input.as_deref(), output.as_deref(), cli.verbose)?;
+        }
         Commands::Completions { shell } => {
             cli::completions_cmd::handle_completions(&shell)?;
         }
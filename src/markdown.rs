@@ -4,8 +4,10 @@
 //! for fixing common Markdown issues from LLM outputs.
 
 use crate::error::Result;
+use crate::repairer_base::{BaseOptions, restore_line_endings};
 use crate::traits::{Repair, RepairStrategy, Validator};
 use regex::Regex;
+use std::borrow::Cow;
 use std::sync::OnceLock;
 
 // ============================================================================
@@ -53,8 +55,21 @@ impl Validator for MarkdownValidator {
             }
         }
 
-        // Basic structure check
-        
+        if lines_outside_code(content)
+            .any(|(outside, line)| outside && fix_task_list_item(line).is_some())
+        {
+            return false;
+        }
+
+        if !missing_reference_definitions(content).is_empty() {
+            return false;
+        }
+
+        if lines_outside_code(content)
+            .any(|(outside, line)| outside && encode_link_spaces(line).is_some())
+        {
+            return false;
+        }
 
         !content.contains("[[") && !content.contains("]]")
     }
@@ -83,6 +98,25 @@ impl Validator for MarkdownValidator {
             errors.push("Malformed link syntax".to_string());
         }
 
+        for (i, (outside, line)) in lines_outside_code(content).enumerate() {
+            if outside && fix_task_list_item(line).is_some() {
+                errors.push(format!("Line {}: Malformed task list item", i + 1));
+            }
+        }
+
+        for (i, (outside, line)) in lines_outside_code(content).enumerate() {
+            if outside && encode_link_spaces(line).is_some() {
+                errors.push(format!("Line {}: Space in link destination", i + 1));
+            }
+        }
+
+        for definition in missing_reference_definitions(content) {
+            let label = definition
+                .split_once("]:")
+                .map_or("", |(label, _)| &label[1..]);
+            errors.push(format!("Undefined reference [{}]", label));
+        }
+
         errors
     }
 }
@@ -128,6 +162,10 @@ pub struct MarkdownRegexCache {
     pub list_items: Regex,
     pub link_formatting: Regex,
     pub bold_italic: Regex,
+    pub task_list_item: Regex,
+    pub image_reference: Regex,
+    pub footnote_reference: Regex,
+    pub reference_definition: Regex,
 }
 
 impl MarkdownRegexCache {
@@ -138,6 +176,10 @@ impl MarkdownRegexCache {
             list_items: Regex::new(r#"(?m)^(\s*)(\d+\.)([^ ])"#)?,
             link_formatting: Regex::new(r#"\[([^\]]+)\]\(([^)]+)\)"#)?,
             bold_italic: Regex::new(r#"\*\*([^*]+)\*\*|\*([^*]+)\*"#)?,
+            task_list_item: Regex::new(r#"^(\s*)([-*+]|\d+[.)])\s*\[\s*([xX]?)\s*\](.*)$"#)?,
+            image_reference: Regex::new(r#"!\[([^\]]*)\]\[([^\]]*)\]"#)?,
+            footnote_reference: Regex::new(r#"\[\^([^\]\s]+)\]"#)?,
+            reference_definition: Regex::new(r#"^ {0,3}\[([^\]]+)\]:"#)?,
         })
     }
 }
@@ -162,6 +204,14 @@ impl RepairStrategy for FixHeaderSpacingStrategy {
         "FixHeaderSpacing"
     }
 
+    fn description(&self) -> &str {
+        "Fix header spacing"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("#Title\n##Section")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_markdown_regex_cache();
         Ok(cache
@@ -183,6 +233,10 @@ impl RepairStrategy for FixCodeBlockFencesStrategy {
         "FixCodeBlockFences"
     }
 
+    fn description(&self) -> &str {
+        "Fix code block fences"
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let lines: Vec<&str> = content.lines().collect();
         let mut result = String::new();
@@ -214,6 +268,14 @@ impl RepairStrategy for FixListFormattingStrategy {
         "FixListFormatting"
     }
 
+    fn description(&self) -> &str {
+        "Fix list formatting"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("1.one\n2.two")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_markdown_regex_cache();
         Ok(cache.list_items.replace_all(content, "$1$2 $3").to_string())
@@ -232,6 +294,14 @@ impl RepairStrategy for FixLinkFormattingStrategy {
         "FixLinkFormatting"
     }
 
+    fn description(&self) -> &str {
+        "Fix link formatting"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("[ docs ]( https://example.com )")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         // Validate and fix link syntax
         let mut result = content.to_string();
@@ -242,6 +312,20 @@ impl RepairStrategy for FixLinkFormattingStrategy {
         result = result.replace("( ", "(");
         result = result.replace(" )", ")");
 
+        let needs_encoding = lines_outside_code(&result)
+            .any(|(outside, line)| outside && encode_link_spaces(line).is_some());
+        if needs_encoding {
+            let lines: Vec<String> = lines_outside_code(&result)
+                .map(|(outside, line)| {
+                    outside
+                        .then(|| encode_link_spaces(line))
+                        .flatten()
+                        .unwrap_or_else(|| line.to_string())
+                })
+                .collect();
+            result = lines.join("\n");
+        }
+
         Ok(result)
     }
 
@@ -250,6 +334,40 @@ impl RepairStrategy for FixLinkFormattingStrategy {
     }
 }
 
+/// Split a link destination into its URL and its title (`a.html "Title"`), the
+/// title keeping its leading whitespace
+fn split_link_title(destination: &str) -> (&str, &str) {
+    for quote in ['"', '\''] {
+        if let Some(body) = destination.strip_suffix(quote)
+            && let Some(open) = body.rfind(quote)
+            && body[..open].ends_with(char::is_whitespace)
+        {
+            let url = body[..open].trim_end();
+            return (url, &destination[url.len()..]);
+        }
+    }
+    (destination, "")
+}
+
+/// Return `line` with spaces in link and image URLs encoded as `%20`
+/// (`[x](a b.html)` -> `[x](a%20b.html)`), or `None` if there are none.
+/// `<...>` destinations and link titles are left as written.
+fn encode_link_spaces(line: &str) -> Option<String> {
+    let cache = get_markdown_regex_cache();
+    let mut changed = false;
+    let fixed = cache
+        .link_formatting
+        .replace_all(line, |caps: &regex::Captures| {
+            let (url, title) = split_link_title(caps[2].trim());
+            if url.starts_with('<') || !url.contains(' ') {
+                return caps[0].to_string();
+            }
+            changed = true;
+            format!("[{}]({}{})", &caps[1], url.replace(' ', "%20"), title)
+        });
+    changed.then(|| fixed.into_owned())
+}
+
 /// Strategy to fix bold and italic formatting
 pub struct FixBoldItalicStrategy;
 
@@ -258,6 +376,14 @@ impl RepairStrategy for FixBoldItalicStrategy {
         "FixBoldItalic"
     }
 
+    fn description(&self) -> &str {
+        "Fix bold and italic formatting"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("**bold text")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let mut result = content.to_string();
 
@@ -289,6 +415,14 @@ impl RepairStrategy for AddMissingNewlinesStrategy {
         "AddMissingNewlines"
     }
 
+    fn description(&self) -> &str {
+        "Add missing newlines"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("# Title\nText\n## Next")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let lines: Vec<&str> = content.lines().collect();
         let mut result = String::new();
@@ -321,6 +455,10 @@ impl RepairStrategy for FixTableFormattingStrategy {
         "FixTableFormatting"
     }
 
+    fn description(&self) -> &str {
+        "Fix table formatting"
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let lines: Vec<&str> = content.lines().collect();
         let mut result = String::new();
@@ -353,24 +491,32 @@ impl RepairStrategy for FixNestedListsStrategy {
         "FixNestedLists"
     }
 
+    fn description(&self) -> &str {
+        "Fix nested lists"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("-one\n  -nested")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let lines: Vec<&str> = content.lines().collect();
         let mut result = String::new();
 
         for line in lines {
             let trimmed = line.trim_start();
-            let indent = line.len() - trimmed.len();
+            let indent = &line[..line.len() - trimmed.len()];
 
             // Fix list item formatting
-            if trimmed.starts_with('-') || trimmed.starts_with('*') || trimmed.starts_with('+') {
-                let marker = trimmed.chars().next().unwrap();
+            if let Some(marker) = trimmed
+                .chars()
+                .next()
+                .filter(|c| matches!(c, '-' | '*' | '+'))
+                // Leave thematic breaks and setext underlines like `---` alone
+                .filter(|&c| !trimmed.chars().all(|ch| ch == c || ch == ' '))
+            {
                 let content_part = trimmed.trim_start_matches([marker, ' ']);
-                result.push_str(&format!(
-                    "{}{} {}",
-                    " ".repeat(indent),
-                    marker,
-                    content_part
-                ));
+                result.push_str(&format!("{}{} {}", indent, marker, content_part));
             } else {
                 result.push_str(line);
             }
@@ -385,6 +531,55 @@ impl RepairStrategy for FixNestedListsStrategy {
     }
 }
 
+/// Return the normalized form of a malformed task list item (`- [] foo`, `-[x]bar`),
+/// or `None` if the line is not a task item or is already well-formed
+fn fix_task_list_item(line: &str) -> Option<String> {
+    let caps = get_markdown_regex_cache().task_list_item.captures(line)?;
+    let text = caps[4].trim_start();
+    // `- [x](url)` is a link, not a checkbox
+    if text.starts_with('(') {
+        return None;
+    }
+    let check = if caps[3].is_empty() { ' ' } else { 'x' };
+    let fixed = format!("{}{} [{}] {}", &caps[1], &caps[2], check, text);
+    let fixed = fixed.trim_end();
+    (fixed != line.trim_end()).then(|| fixed.to_string())
+}
+
+/// Strategy to normalize task list checkboxes to `- [ ] ` / `- [x] `
+pub struct FixTaskListStrategy;
+
+impl RepairStrategy for FixTaskListStrategy {
+    fn name(&self) -> &str {
+        "FixTaskList"
+    }
+
+    fn description(&self) -> &str {
+        "Normalize task list checkboxes to `- [ ] ` / `- [x] `"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("- [] todo\n-[X] done")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let lines: Vec<String> = lines_outside_code(content)
+            .map(|(outside, line)| {
+                outside
+                    .then(|| fix_task_list_item(line))
+                    .flatten()
+                    .unwrap_or_else(|| line.to_string())
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    // Runs after FixImageSyntax, which would collapse `[ ]` back to `[]`
+    fn priority(&self) -> u8 {
+        54
+    }
+}
+
 /// Strategy to fix image syntax
 pub struct FixImageSyntaxStrategy;
 
@@ -393,6 +588,14 @@ impl RepairStrategy for FixImageSyntaxStrategy {
         "FixImageSyntax"
     }
 
+    fn description(&self) -> &str {
+        "Fix image syntax"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("![ logo ]( logo.png )")
+    }
+
     fn apply(&self, content: &str) -> Result<String> {
         let mut result = content.to_string();
 
@@ -410,6 +613,534 @@ impl RepairStrategy for FixImageSyntaxStrategy {
     }
 }
 
+/// Return the ATX heading level (1-6) of a line, if it is a well-formed heading
+fn heading_level(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    match trimmed[level..].chars().next() {
+        None | Some(' ') | Some('\t') => Some(level),
+        _ => None,
+    }
+}
+
+/// Iterate over lines that are outside fenced code blocks
+fn lines_outside_code(content: &str) -> impl Iterator<Item = (bool, &str)> {
+    let mut in_code_block = false;
+    content.lines().map(move |line| {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            return (false, line);
+        }
+        (!in_code_block, line)
+    })
+}
+
+/// Block-level tags that open a raw HTML block
+const HTML_BLOCK_TAGS: [&str; 38] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "iframe",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "script",
+    "section",
+    "style",
+    "summary",
+    "table",
+    "tbody",
+    "textarea",
+    "thead",
+    "tr",
+    "ul",
+];
+
+/// Tags whose block runs to the closing tag rather than the next blank line
+const HTML_RAW_TAGS: [&str; 4] = ["pre", "script", "style", "textarea"];
+
+/// The block-level tag a line opens or closes (`<div class="x">`, `</table>`),
+/// lowercased
+fn html_block_tag(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let rest = trimmed.strip_prefix('<')?;
+    let rest = rest.strip_prefix('/').unwrap_or(rest);
+    let name_len = rest
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(rest.len());
+    let name = rest[..name_len].to_ascii_lowercase();
+    let ends_name = rest[name_len..]
+        .chars()
+        .next()
+        .is_none_or(|c| c.is_whitespace() || c == '>' || c == '/');
+    (ends_name && HTML_BLOCK_TAGS.contains(&name.as_str())).then_some(name)
+}
+
+/// For each line of `content`, whether it belongs to a raw HTML block. A block
+/// starts with a block-level tag outside fenced code and ends at the next blank
+/// line, or for `<pre>`, `<script>`, `<style>` and `<textarea>` at the closing tag.
+fn html_block_lines(content: &str) -> Vec<bool> {
+    let mut flags = Vec::new();
+    // `Some(None)` ends at a blank line, `Some(Some(tag))` at `</tag>`
+    let mut block: Option<Option<String>> = None;
+
+    for (outside, line) in lines_outside_code(content) {
+        let closes = |tag: &str| line.to_ascii_lowercase().contains(&format!("</{}>", tag));
+        match &block {
+            Some(None) if line.trim().is_empty() => {
+                block = None;
+                flags.push(false);
+            }
+            Some(end) => {
+                if end.as_deref().is_some_and(closes) {
+                    block = None;
+                }
+                flags.push(true);
+            }
+            None => match html_block_tag(line).filter(|_| outside) {
+                Some(tag) => {
+                    block = if !HTML_RAW_TAGS.contains(&tag.as_str()) {
+                        Some(None)
+                    } else if closes(&tag) {
+                        None
+                    } else {
+                        Some(Some(tag))
+                    };
+                    flags.push(true);
+                }
+                None => flags.push(false),
+            },
+        }
+    }
+
+    flags
+}
+
+/// Placeholder definitions (`[ref]: #`, `[^note]: (missing)`) for the labels of
+/// `![alt][ref]` images and `[^note]` footnotes that are never defined, in order of
+/// first use. Labels match case-insensitively; fenced code is ignored.
+fn missing_reference_definitions(content: &str) -> Vec<String> {
+    let cache = get_markdown_regex_cache();
+    let lines: Vec<&str> = lines_outside_code(content)
+        .filter_map(|(outside, line)| outside.then_some(line))
+        .collect();
+
+    let defined: Vec<String> = lines
+        .iter()
+        .filter_map(|line| cache.reference_definition.captures(line))
+        .map(|caps| caps[1].to_lowercase())
+        .collect();
+
+    let mut missing: Vec<(String, String)> = Vec::new();
+    let mut use_label = |label: &str, definition: String| {
+        let key = label.to_lowercase();
+        if !defined.contains(&key) && !missing.iter().any(|(k, _)| *k == key) {
+            missing.push((key, definition));
+        }
+    };
+    for line in &lines {
+        for caps in cache.image_reference.captures_iter(line) {
+            // `![alt][]` is a collapsed reference to `alt`
+            let label = if caps[2].trim().is_empty() {
+                &caps[1]
+            } else {
+                &caps[2]
+            };
+            if !label.trim().is_empty() {
+                use_label(label, format!("[{}]: #", label));
+            }
+        }
+        for m in cache.footnote_reference.find_iter(line) {
+            // `[^note]:` at the start of a line is the definition itself
+            let is_definition =
+                line[m.end()..].starts_with(':') && line[..m.start()].trim().is_empty();
+            if !is_definition {
+                let label = &m.as_str()[1..m.as_str().len() - 1];
+                use_label(label, format!("[{}]: (missing)", label));
+            }
+        }
+    }
+
+    missing
+        .into_iter()
+        .map(|(_, definition)| definition)
+        .collect()
+}
+
+/// Strategy to append placeholder definitions for image references and footnotes
+/// that are used but never defined
+pub struct AddMissingReferenceDefinitionsStrategy;
+
+impl RepairStrategy for AddMissingReferenceDefinitionsStrategy {
+    fn name(&self) -> &str {
+        "AddMissingReferenceDefinitions"
+    }
+
+    fn description(&self) -> &str {
+        "Append placeholder definitions for undefined image references and footnotes"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("![Logo][logo]\n\nSee the note.[^1]")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let missing = missing_reference_definitions(content);
+        if missing.is_empty() {
+            return Ok(content.to_string());
+        }
+        Ok(format!("{}\n\n{}", content.trim_end(), missing.join("\n")))
+    }
+
+    // Runs last, once image and link syntax has been tidied
+    fn priority(&self) -> u8 {
+        10
+    }
+}
+
+/// Opt-in style normalizations applied on top of the default Markdown repairs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MarkdownOptions {
+    /// Remove heading-level skips (e.g. `#` followed by `###`)
+    pub normalize_headings: bool,
+    /// Rewrite every bullet with this marker and renumber ordered lists
+    pub list_marker: Option<char>,
+    /// Convert setext headings (`Title` underlined with `===`/`---`) to ATX `#`/`##`
+    pub atx_headings: bool,
+}
+
+/// Validator that also enforces the style rules enabled in [`MarkdownOptions`]
+pub struct MarkdownStyleValidator {
+    pub options: MarkdownOptions,
+}
+
+impl Validator for MarkdownStyleValidator {
+    fn is_valid(&self, content: &str) -> bool {
+        MarkdownValidator.is_valid(content) && self.validate(content).is_empty()
+    }
+
+    fn validate(&self, content: &str) -> Vec<String> {
+        let mut errors = MarkdownValidator.validate(content);
+
+        if self.options.normalize_headings {
+            let mut previous: Option<usize> = None;
+            for (i, (outside, line)) in lines_outside_code(content).enumerate() {
+                let Some(level) = heading_level(line).filter(|_| outside) else {
+                    continue;
+                };
+                if let Some(prev) = previous
+                    && level > prev + 1
+                {
+                    errors.push(format!(
+                        "Line {}: Heading level skips from {} to {}",
+                        i + 1,
+                        prev,
+                        level
+                    ));
+                }
+                previous = Some(level);
+            }
+        }
+
+        if self.options.atx_headings {
+            for (underline, level) in setext_headings(content) {
+                errors.push(format!(
+                    "Line {}: Setext heading (level {}) instead of ATX",
+                    underline + 1,
+                    level
+                ));
+            }
+        }
+
+        if let Some(marker) = self.options.list_marker {
+            let normalized = normalize_list_markers(content, marker);
+            for (i, (line, expected)) in content.lines().zip(normalized.lines()).enumerate() {
+                if line != expected {
+                    errors.push(format!("Line {}: Inconsistent list marker", i + 1));
+                }
+            }
+        }
+
+        errors
+    }
+}
+
+/// Strategy to promote headings so that no heading level is skipped
+pub struct NormalizeHeadingLevelsStrategy;
+
+impl RepairStrategy for NormalizeHeadingLevelsStrategy {
+    fn name(&self) -> &str {
+        "NormalizeHeadingLevels"
+    }
+
+    fn description(&self) -> &str {
+        "Promote headings so that no heading level is skipped"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("# Title\n### Skipped")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        // Stack of (original level, normalized level) for the enclosing sections
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        let mut result = Vec::new();
+
+        for (outside, line) in lines_outside_code(content) {
+            let Some(level) = heading_level(line).filter(|_| outside) else {
+                result.push(line.to_string());
+                continue;
+            };
+
+            while stack.last().is_some_and(|(orig, _)| *orig >= level) {
+                stack.pop();
+            }
+            let normalized = match stack.last() {
+                Some((_, parent)) => parent + 1,
+                None => level,
+            };
+            stack.push((level, normalized));
+
+            let trimmed = line.trim_start();
+            let indent = &line[..line.len() - trimmed.len()];
+            result.push(format!("{}{}{}", indent, "#".repeat(normalized), &trimmed[level..]));
+        }
+
+        Ok(result.join("\n"))
+    }
+
+    fn priority(&self) -> u8 {
+        50
+    }
+}
+
+/// Heading level of a setext underline (`===` or `---`), if the line is one
+fn setext_underline_level(line: &str) -> Option<usize> {
+    let trimmed = line.trim();
+    let indent = line.len() - line.trim_start().len();
+    if indent > 3 || trimmed.len() < 2 {
+        return None;
+    }
+    if trimmed.bytes().all(|b| b == b'=') {
+        Some(1)
+    } else if trimmed.bytes().all(|b| b == b'-') {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Find setext headings as (underline line index, level).
+///
+/// Only a single-line paragraph directly above the underline counts, so a `---`
+/// after a blank line stays a thematic break and leading YAML front matter is skipped.
+fn setext_headings(content: &str) -> Vec<(usize, usize)> {
+    let lines: Vec<(bool, &str)> = lines_outside_code(content).collect();
+    let mut start = 0;
+    if lines.first().is_some_and(|(_, l)| l.trim_end() == "---")
+        && let Some(close) = lines
+            .iter()
+            .skip(1)
+            .position(|(_, l)| matches!(l.trim_end(), "---" | "..."))
+    {
+        start = close + 2;
+    }
+
+    let mut headings = Vec::new();
+    for i in start.max(1)..lines.len() {
+        let (outside, line) = lines[i];
+        let (text_outside, text) = lines[i - 1];
+        let Some(level) = setext_underline_level(line).filter(|_| outside && text_outside) else {
+            continue;
+        };
+        let trimmed = text.trim();
+        let starts_paragraph = i - 1 == start || lines[i - 2].1.trim().is_empty();
+        let is_block = heading_level(text).is_some()
+            || parse_list_item(text).is_some()
+            || setext_underline_level(text).is_some()
+            || trimmed.starts_with(['>', '|', '<'])
+            || text.len() - text.trim_start().len() > 3;
+        if !trimmed.is_empty() && starts_paragraph && !is_block {
+            headings.push((i, level));
+        }
+    }
+    headings
+}
+
+/// Strategy to rewrite setext headings as ATX headings
+pub struct ConvertSetextHeadingsStrategy;
+
+impl RepairStrategy for ConvertSetextHeadingsStrategy {
+    fn name(&self) -> &str {
+        "ConvertSetextHeadings"
+    }
+
+    fn description(&self) -> &str {
+        "Rewrite setext headings (`===`/`---` underlines) as ATX `#`/`##` headings"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("Title\n=====\n\nSection\n-------")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        let headings = setext_headings(content);
+        if headings.is_empty() {
+            return Ok(content.to_string());
+        }
+        let mut result: Vec<String> = Vec::new();
+        let mut next = headings.iter().peekable();
+        for (i, line) in content.lines().enumerate() {
+            match next.next_if(|(underline, _)| *underline == i) {
+                Some((_, level)) => {
+                    let text = result.pop().unwrap_or_default();
+                    result.push(format!("{} {}", "#".repeat(*level), text.trim()));
+                }
+                None => result.push(line.to_string()),
+            }
+        }
+        Ok(result.join("\n"))
+    }
+
+    // Runs before NormalizeHeadingLevels so converted headings are normalized too
+    fn priority(&self) -> u8 {
+        55
+    }
+}
+
+/// A parsed list item marker
+enum ListMarker {
+    Bullet,
+    Ordered(usize),
+}
+
+/// Split a list item line into (indent, marker, text after the marker)
+fn parse_list_item(line: &str) -> Option<(usize, ListMarker, &str)> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+
+    if let Some(first) = trimmed.chars().next()
+        && matches!(first, '-' | '*' | '+')
+    {
+        let rest = &trimmed[1..];
+        // Require a space after the marker and skip thematic breaks like `* * *`
+        if rest.starts_with(' ') && !rest.chars().all(|c| c == first || c == ' ') {
+            return Some((indent, ListMarker::Bullet, rest));
+        }
+        return None;
+    }
+
+    let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 || digits > 9 {
+        return None;
+    }
+    let rest = &trimmed[digits..];
+    let rest = rest.strip_prefix('.').or_else(|| rest.strip_prefix(')'))?;
+    if !rest.starts_with(' ') {
+        return None;
+    }
+    let number = trimmed[..digits].parse().ok()?;
+    Some((indent, ListMarker::Ordered(number), rest))
+}
+
+/// Use one bullet marker per list and renumber ordered lists sequentially.
+/// Each nesting level is numbered independently; a non-indented paragraph ends the list.
+fn normalize_list_markers(content: &str, marker: char) -> String {
+    // Stack of (indent, next ordinal) for the open list levels
+    let mut levels: Vec<(usize, Option<usize>)> = Vec::new();
+    let mut result = Vec::new();
+
+    for (outside, line) in lines_outside_code(content) {
+        let item = parse_list_item(line).filter(|_| outside);
+        let Some((indent, kind, rest)) = item else {
+            let is_continuation = line.trim().is_empty() || line.starts_with([' ', '\t']);
+            if !outside || !is_continuation {
+                levels.clear();
+            }
+            result.push(line.to_string());
+            continue;
+        };
+
+        while levels.last().is_some_and(|(i, _)| *i > indent) {
+            levels.pop();
+        }
+        if levels.last().is_none_or(|(i, _)| *i != indent) {
+            levels.push((indent, None));
+        }
+        let level = levels.last_mut().expect("level was just pushed");
+
+        let prefix = &line[..indent];
+        match kind {
+            ListMarker::Bullet => {
+                result.push(format!("{}{}{}", prefix, marker, rest));
+            }
+            ListMarker::Ordered(number) => {
+                let ordinal = level.1.unwrap_or(number);
+                level.1 = Some(ordinal + 1);
+                result.push(format!("{}{}.{}", prefix, ordinal, rest));
+            }
+        }
+    }
+
+    result.join("\n")
+}
+
+/// Strategy to make bullet markers consistent and renumber ordered lists
+pub struct NormalizeListMarkersStrategy {
+    /// Bullet marker to use (`-`, `*` or `+`)
+    pub marker: char,
+}
+
+impl RepairStrategy for NormalizeListMarkersStrategy {
+    fn name(&self) -> &str {
+        "NormalizeListMarkers"
+    }
+
+    fn description(&self) -> &str {
+        "Make bullet markers consistent and renumber ordered lists"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("- one\n* two\n+ three")
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        Ok(normalize_list_markers(content, self.marker))
+    }
+
+    // Runs before FixBoldItalic so `*` bullets are not mistaken for emphasis
+    fn priority(&self) -> u8 {
+        84
+    }
+}
+
 // ============================================================================
 // Markdown Repairer
 // ============================================================================
@@ -419,12 +1150,41 @@ impl RepairStrategy for FixImageSyntaxStrategy {
 /// Uses trait-based composition with GenericRepairer for better modularity
 pub struct MarkdownRepairer {
     pub inner: crate::repairer_base::GenericRepairer,
+    strip_html: bool,
 }
 
 impl MarkdownRepairer {
     /// Create a new Markdown repairer
     pub fn new() -> Self {
-        let strategies: Vec<Box<dyn RepairStrategy>> = vec![
+        Self::with_options(MarkdownOptions::default())
+    }
+
+    /// Start configuring a Markdown repairer
+    pub fn builder() -> MarkdownRepairerBuilder {
+        MarkdownRepairerBuilder::default()
+    }
+
+    /// Create a Markdown repairer that optionally removes heading-level skips
+    /// (e.g. `#` followed by `###`) while preserving the relative heading structure
+    pub fn with_heading_normalization(enabled: bool) -> Self {
+        Self::builder().heading_normalization(enabled).build()
+    }
+
+    /// Create a Markdown repairer that rewrites every bullet with `marker`
+    /// and renumbers ordered lists to `1. 2. 3.`
+    pub fn with_list_marker(marker: char) -> Self {
+        Self::builder().list_marker(marker).build()
+    }
+
+    /// Create a Markdown repairer that optionally rewrites setext headings
+    /// (`===`/`---` underlines) as ATX `#`/`##` headings
+    pub fn with_atx_headings(enabled: bool) -> Self {
+        Self::builder().atx_headings(enabled).build()
+    }
+
+    /// Create a Markdown repairer with the given style normalizations enabled
+    pub fn with_options(options: MarkdownOptions) -> Self {
+        let mut strategies: Vec<Box<dyn RepairStrategy>> = vec![
             Box::new(FixHeaderSpacingStrategy),
             Box::new(FixCodeBlockFencesStrategy),
             Box::new(FixListFormattingStrategy),
@@ -434,12 +1194,139 @@ impl MarkdownRepairer {
             Box::new(FixTableFormattingStrategy),
             Box::new(FixNestedListsStrategy),
             Box::new(FixImageSyntaxStrategy),
+            Box::new(FixTaskListStrategy),
+            Box::new(AddMissingReferenceDefinitionsStrategy),
         ];
+        if options.normalize_headings {
+            strategies.push(Box::new(NormalizeHeadingLevelsStrategy));
+        }
+        if let Some(marker) = options.list_marker {
+            strategies.push(Box::new(NormalizeListMarkersStrategy { marker }));
+        }
+        if options.atx_headings {
+            strategies.push(Box::new(ConvertSetextHeadingsStrategy));
+        }
 
-        let validator: Box<dyn Validator> = Box::new(MarkdownValidator);
+        let validator: Box<dyn Validator> = if options == MarkdownOptions::default() {
+            Box::new(MarkdownValidator)
+        } else {
+            Box::new(MarkdownStyleValidator { options })
+        };
         let inner = crate::repairer_base::GenericRepairer::new(validator, strategies);
 
-        Self { inner }
+        Self {
+            inner,
+            strip_html: false,
+        }
+    }
+
+    /// Create a Markdown repairer that, when `strip` is true, removes raw HTML
+    /// blocks instead of passing them through verbatim
+    pub fn with_strip_html(strip: bool) -> Self {
+        Self::builder().strip_html(strip).build()
+    }
+
+    /// Repair the Markdown around raw HTML blocks, which are kept verbatim or,
+    /// with `strip_html`, dropped
+    fn repair_around_html(&mut self, content: &str) -> Result<String> {
+        let html = html_block_lines(content);
+        if !html.contains(&true) {
+            return self.inner.repair(content);
+        }
+        let lines: Vec<&str> = content.lines().collect();
+
+        if self.strip_html {
+            let mut kept: Vec<&str> = Vec::new();
+            for (line, is_html) in lines.iter().zip(&html) {
+                // Don't leave a double blank line where a block was
+                let blank_after_blank =
+                    line.trim().is_empty() && kept.last().is_none_or(|l| l.trim().is_empty());
+                if !is_html && !blank_after_blank {
+                    kept.push(line);
+                }
+            }
+            let repaired = self.inner.repair(&kept.join("\n"))?;
+            return Ok(restore_line_endings(content, &repaired));
+        }
+
+        let mut out: Vec<String> = Vec::new();
+        let mut start = 0;
+        while start < lines.len() {
+            let is_html = html[start];
+            let end = (start..lines.len())
+                .find(|&i| html[i] != is_html)
+                .unwrap_or(lines.len());
+            let run = &lines[start..end];
+            start = end;
+
+            let first = run.iter().position(|l| !l.trim().is_empty());
+            let last = run.iter().rposition(|l| !l.trim().is_empty());
+            let (Some(first), Some(last), false) = (first, last, is_html) else {
+                out.extend(run.iter().map(|l| l.to_string()));
+                continue;
+            };
+            // Blank lines around the Markdown are kept as they separate it from the HTML
+            out.extend(run[..first].iter().map(|l| l.to_string()));
+            let repaired = self.inner.repair(&run[first..=last].join("\n"))?;
+            out.extend(repaired.lines().map(String::from));
+            out.extend(run[last + 1..].iter().map(|l| l.to_string()));
+        }
+
+        Ok(restore_line_endings(content, &out.join("\n")))
+    }
+}
+
+/// Builder combining any of the options of [`MarkdownRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownRepairerBuilder {
+    base: BaseOptions,
+    options: MarkdownOptions,
+    strip_html: bool,
+}
+
+impl MarkdownRepairerBuilder {
+    /// Remove heading-level skips (e.g. `#` followed by `###`)
+    pub fn heading_normalization(mut self, enabled: bool) -> Self {
+        self.options.normalize_headings = enabled;
+        self
+    }
+
+    /// Rewrite every bullet with `marker` and renumber ordered lists
+    pub fn list_marker(mut self, marker: char) -> Self {
+        self.options.list_marker = Some(marker);
+        self
+    }
+
+    /// Convert setext headings to ATX `#`/`##` headings
+    pub fn atx_headings(mut self, enabled: bool) -> Self {
+        self.options.atx_headings = enabled;
+        self
+    }
+
+    /// Remove raw HTML blocks instead of passing them through verbatim
+    pub fn strip_html(mut self, strip: bool) -> Self {
+        self.strip_html = strip;
+        self
+    }
+
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Create the configured Markdown repairer
+    pub fn build(self) -> MarkdownRepairer {
+        let mut repairer = MarkdownRepairer::with_options(self.options);
+        repairer.strip_html = self.strip_html;
+        repairer.inner.apply_options(self.base);
+        repairer
     }
 }
 
@@ -451,7 +1338,19 @@ impl Default for MarkdownRepairer {
 
 impl Repair for MarkdownRepairer {
     fn repair(&mut self, content: &str) -> Result<String> {
-        self.inner.repair(content)
+        self.repair_around_html(content)
+    }
+
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        if !html_block_lines(content).contains(&true) {
+            return self.inner.repair_cow(content);
+        }
+        let repaired = self.repair_around_html(content)?;
+        Ok(if repaired == content {
+            Cow::Borrowed(content)
+        } else {
+            Cow::Owned(repaired)
+        })
     }
 
     fn needs_repair(&self, content: &str) -> bool {
@@ -521,6 +1420,210 @@ mod tests {
         assert!(confidence > 0.0);
     }
 
+    #[test]
+    fn test_heading_normalization_fills_gap() {
+        let mut repairer = MarkdownRepairer::with_heading_normalization(true);
+        let result = repairer
+            .repair("# Title\n\n### Section\n\n#### Detail\n\n# Next")
+            .unwrap();
+        assert_eq!(result, "# Title\n\n## Section\n\n### Detail\n\n# Next");
+    }
+
+    #[test]
+    fn test_heading_normalization_leaves_valid_document() {
+        let input = "# Title\n\n## Section\n\n### Detail\n\n## Other";
+        let mut repairer = MarkdownRepairer::with_heading_normalization(true);
+        assert_eq!(repairer.repair(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_heading_normalization_ignores_code_blocks() {
+        let mut repairer = MarkdownRepairer::with_heading_normalization(true);
+        let result = repairer.repair("# A\n```\n### not a heading\n```\n### B").unwrap();
+        assert!(result.contains("### not a heading"));
+        assert!(result.ends_with("\n## B"));
+    }
+
+    #[test]
+    fn test_heading_normalization_disabled_by_default() {
+        let mut repairer = MarkdownRepairer::new();
+        assert_eq!(repairer.repair("# A\n\n### B").unwrap(), "# A\n\n### B");
+    }
+
+    #[test]
+    fn test_list_markers_mixed_bullets() {
+        let mut repairer = MarkdownRepairer::with_list_marker('-');
+        let result = repairer.repair("- one\n* two\n+ three").unwrap();
+        assert_eq!(result, "- one\n- two\n- three");
+    }
+
+    #[test]
+    fn test_list_markers_renumber_ordered() {
+        let mut repairer = MarkdownRepairer::with_list_marker('-');
+        let result = repairer.repair("1. first\n1. second\n1) third").unwrap();
+        assert_eq!(result, "1. first\n2. second\n3. third");
+    }
+
+    #[test]
+    fn test_list_markers_nested_levels_independent() {
+        let mut repairer = MarkdownRepairer::with_list_marker('*');
+        let input = "1. a\n   - x\n   + y\n1. b\n   1. p\n   1. q\n1. c";
+        let result = repairer.repair(input).unwrap();
+        assert_eq!(result, "1. a\n   * x\n   * y\n2. b\n   1. p\n   2. q\n3. c");
+    }
+
+    #[test]
+    fn test_list_markers_separate_lists_restart() {
+        let mut repairer = MarkdownRepairer::with_list_marker('-');
+        let result = repairer
+            .repair("1. a\n1. b\n\nParagraph\n\n1. c\n1. d")
+            .unwrap();
+        assert_eq!(result, "1. a\n2. b\n\nParagraph\n\n1. c\n2. d");
+    }
+
+    #[test]
+    fn test_task_list_empty_checkbox() {
+        let mut repairer = MarkdownRepairer::new();
+        assert_eq!(repairer.repair("- [] foo").unwrap(), "- [ ] foo");
+    }
+
+    #[test]
+    fn test_task_list_missing_spaces() {
+        let mut repairer = MarkdownRepairer::new();
+        assert_eq!(repairer.repair("-[x]bar").unwrap(), "- [x] bar");
+        assert_eq!(repairer.repair("1.[X] done").unwrap(), "1. [x] done");
+    }
+
+    #[test]
+    fn test_task_list_regular_items_untouched() {
+        let mut repairer = MarkdownRepairer::new();
+        let input = "- [ ] todo\n- [x] done\n- plain item\n- [link](http://example.com)";
+        assert_eq!(repairer.repair(input).unwrap(), input);
+        assert!(MarkdownValidator.is_valid(input));
+    }
+
+    #[test]
+    fn test_task_list_in_fenced_code_untouched() {
+        let input = "```\n- [] x\n-[X]y\n```";
+        assert_eq!(FixTaskListStrategy.apply(input).unwrap(), input);
+        assert!(MarkdownValidator.is_valid(input));
+        assert!(MarkdownValidator.validate(input).is_empty());
+        assert_eq!(MarkdownRepairer::new().repair(input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_orphaned_image_reference_gets_definition() {
+        let mut repairer = MarkdownRepairer::new();
+        let input = "# Docs\n\n![Logo][logo] and ![Diagram][]\n\n[diagram]: diagram.png";
+        assert_eq!(
+            repairer.repair(input).unwrap(),
+            format!("{}\n\n[logo]: #", input)
+        );
+        assert!(!MarkdownValidator.is_valid(input));
+    }
+
+    #[test]
+    fn test_orphaned_footnote_gets_definition() {
+        let mut repairer = MarkdownRepairer::new();
+        let input = "# Notes\n\nFirst claim.[^1] Second claim.[^2]\n\n[^2]: Source.";
+        assert_eq!(
+            repairer.repair(input).unwrap(),
+            format!("{}\n\n[^1]: (missing)", input)
+        );
+
+        let fenced = "# Code\n\n```\nx[^1]\n```";
+        assert!(MarkdownValidator.is_valid(fenced));
+    }
+
+    #[test]
+    fn test_builder_combines_style_options() {
+        let mut repairer = MarkdownRepairer::builder()
+            .heading_normalization(true)
+            .list_marker('-')
+            .build();
+        assert_eq!(
+            repairer
+                .repair("# Title\n\n### Part\n\n* one\n+ two")
+                .unwrap(),
+            "# Title\n\n## Part\n\n- one\n- two"
+        );
+    }
+
+    #[test]
+    fn test_setext_headings_converted_to_atx() {
+        let mut repairer = MarkdownRepairer::with_atx_headings(true);
+        assert_eq!(
+            repairer
+                .repair("Title\n=====\n\nSome text.\n\nSection\n-------\n\nMore text.")
+                .unwrap(),
+            "# Title\n\nSome text.\n\n## Section\n\nMore text."
+        );
+    }
+
+    #[test]
+    fn test_thematic_break_and_front_matter_not_converted() {
+        let mut repairer = MarkdownRepairer::with_atx_headings(true);
+        let input = "---\ntitle: Notes\n---\n\nFirst part.\n\n---\n\nSecond part.";
+        assert_eq!(repairer.repair(input).unwrap(), input);
+
+        // The rule survives when other repairs run too
+        assert_eq!(
+            repairer.repair("##Intro\n\n---\n\nText").unwrap(),
+            "## Intro\n\n---\n\nText"
+        );
+    }
+
+    #[test]
+    fn test_link_destination_spaces_encoded() {
+        let mut repairer = MarkdownRepairer::new();
+        assert_eq!(repairer.repair("[x](a b.html)").unwrap(), "[x](a%20b.html)");
+        assert_eq!(
+            repairer
+                .repair("See ![logo](my logo.png \"The logo\") here")
+                .unwrap(),
+            "See ![logo](my%20logo.png \"The logo\") here"
+        );
+    }
+
+    #[test]
+    fn test_valid_link_destinations_unchanged() {
+        let validator = MarkdownValidator;
+        for content in [
+            "[x](a%20b.html)",
+            "[x](<a b.html>)",
+            "[x](https://example.com \"Title\")",
+            "```\n[x](a b.html)\n```",
+        ] {
+            assert!(validator.is_valid(content), "{}", content);
+            let mut repairer = MarkdownRepairer::new();
+            assert_eq!(repairer.repair(content).unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn test_html_block_preserved_verbatim() {
+        let input = "##Intro\n\n<table>\n  <tr><td>*a*</td></tr>\n1.item\n</table>\n\n##Outro\n";
+        let mut repairer = MarkdownRepairer::new();
+        assert_eq!(
+            repairer.repair(input).unwrap(),
+            "## Intro\n\n<table>\n  <tr><td>*a*</td></tr>\n1.item\n</table>\n\n## Outro\n"
+        );
+
+        let mut stripping = MarkdownRepairer::with_strip_html(true);
+        assert_eq!(stripping.repair(input).unwrap(), "## Intro\n\n## Outro\n");
+    }
+
+    #[test]
+    fn test_nested_list_marker_before_multibyte_text() {
+        let strategy = FixNestedListsStrategy;
+        assert_eq!(strategy.apply("- 日本語").unwrap(), "- 日本語");
+        assert_eq!(strategy.apply("-日本語").unwrap(), "- 日本語");
+        assert_eq!(
+            strategy.apply("*中文\n\u{a0}+é").unwrap(),
+            "* 中文\n\u{a0}+ é"
+        );
+    }
+
     #[test]
     fn test_markdown_needs_repair() {
         let repairer = MarkdownRepairer::new();
@@ -3,10 +3,12 @@
 //! Exposes anyrepair repair functionality as an MCP server for integration
 //! with Claude and other MCP-compatible clients.
 
+use crate::analytics::AnalyticsTracker;
 use crate::json_util::{
     parse_tool_call_input, repair_format_response, repair_success_response, validate_response,
 };
 use std::collections::HashMap;
+use std::time::Instant;
 
 /// Tool definition for MCP
 #[derive(Clone, Debug)]
@@ -37,6 +39,7 @@ fn validate_tool_schema() -> String {
 /// MCP Server for anyrepair
 pub struct AnyrepairMcpServer {
     tools: HashMap<String, Tool>,
+    analytics: AnalyticsTracker,
 }
 
 impl AnyrepairMcpServer {
@@ -76,7 +79,15 @@ impl AnyrepairMcpServer {
             },
         );
 
-        Self { tools }
+        Self {
+            tools,
+            analytics: AnalyticsTracker::new(),
+        }
+    }
+
+    /// Repair metrics collected across tool calls
+    pub fn analytics(&self) -> &AnalyticsTracker {
+        &self.analytics
     }
 
     /// Get available tools
@@ -105,7 +116,18 @@ impl AnyrepairMcpServer {
             .as_deref()
             .ok_or("Missing 'content' parameter")?;
 
-        let repaired = crate::repair(content).map_err(|e| format!("Repair failed: {}", e))?;
+        let start = Instant::now();
+        let format = crate::detect_format(content).unwrap_or("markdown");
+        let repaired = match crate::repair(content) {
+            Ok(repaired) => repaired,
+            Err(e) => {
+                self.analytics.record_failure(format, start.elapsed());
+                return Err(format!("Repair failed: {}", e));
+            }
+        };
+        let confidence = crate::create_repairer(format).map_or(1.0, |r| r.confidence(&repaired));
+        self.analytics
+            .record_success(format, confidence, start.elapsed());
 
         Ok(repair_success_response(&repaired))
     }
@@ -120,13 +142,20 @@ impl AnyrepairMcpServer {
             .as_deref()
             .ok_or("Missing 'content' parameter")?;
 
+        let start = Instant::now();
         let mut repairer = crate::create_repairer(format)
             .map_err(|e| format!("{} repair failed: {}", format, e))?;
-        let repaired = repairer
-            .repair(content)
-            .map_err(|e| format!("{} repair failed: {}", format, e))?;
+        let repaired = match repairer.repair(content) {
+            Ok(repaired) => repaired,
+            Err(e) => {
+                self.analytics.record_failure(format, start.elapsed());
+                return Err(format!("{} repair failed: {}", format, e));
+            }
+        };
 
         let confidence = repairer.confidence(&repaired);
+        self.analytics
+            .record_success(format, confidence, start.elapsed());
 
         Ok(repair_format_response(&repaired, confidence))
     }
@@ -184,6 +213,20 @@ mod tests {
         get_json_number_field(s, "confidence")
     }
 
+    // ===== Analytics Tests =====
+
+    #[test]
+    fn test_mcp_server_records_metrics() {
+        let server = AnyrepairMcpServer::new();
+        call(&server, "repair_json", &tool_input_json(r#"{"a": 1,}"#)).unwrap();
+        call(&server, "repair", &tool_input_json(r#"{"b": 2,}"#)).unwrap();
+
+        let metrics = server.analytics().metrics();
+        assert_eq!(metrics.total_repairs, 2);
+        assert_eq!(metrics.failures, 0);
+        assert_eq!(metrics.per_format["json"], 2);
+    }
+
     // ===== Server Creation Tests =====
 
     #[test]
@@ -203,8 +246,9 @@ mod tests {
         let server = AnyrepairMcpServer::new();
         let tools = server.get_tools();
         // Should have: repair, repair_json, repair_yaml, repair_markdown, repair_xml,
-        // repair_toml, repair_csv, repair_ini, repair_diff, repair_properties, repair_env, validate = 12 tools
-        assert_eq!(tools.len(), 12);
+        // repair_toml, repair_csv, repair_ini, repair_diff, repair_properties, repair_env,
+        // repair_dotenv, validate = 13 tools
+        assert_eq!(tools.len(), 13);
     }
 
     #[test]
@@ -710,7 +754,7 @@ mod tests {
             
             let confidence = response_confidence(&result.unwrap()).unwrap();
             assert!(
-                confidence >= 0.0 && confidence <= 1.0,
+                (0.0..=1.0).contains(&confidence),
                 "tool {} confidence {} out of range",
                 tool,
                 confidence
@@ -0,0 +1,116 @@
+//! Merge JSON objects emitted in fragments
+//!
+//! Agent tools sometimes stream one object as several, `{"a": 1}` then
+//! `{"b": 2}`. [`merge_json_fragments`] repairs each fragment and deep-merges
+//! them into a single object.
+
+use crate::convert::{Value, parse_json};
+use crate::error::{RepairError, Result};
+use crate::json::JsonRepairer;
+use crate::traits::Repair;
+
+/// What [`merge_json_fragments_with`] does when fragments set the same key to
+/// values that can't be merged (anything but two objects)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeConflict {
+    /// The later fragment's value replaces the earlier one
+    #[default]
+    LastWins,
+    /// The first value set is kept
+    FirstWins,
+    /// Fail with `RepairError::Conversion` naming the key
+    Error,
+}
+
+/// Repair each fragment as JSON and deep-merge them into one object, later
+/// fragments winning conflicts. See [`merge_json_fragments_with`].
+pub fn merge_json_fragments(fragments: &[&str]) -> Result<Value> {
+    merge_json_fragments_with(fragments, MergeConflict::default())
+}
+
+/// Repair each fragment as JSON and deep-merge them into one object.
+///
+/// Nested objects are merged key by key, keeping the order keys first appear in;
+/// any other pair of values for the same key is a conflict settled by `conflict`.
+/// Returns `RepairError::Conversion` if a fragment is not an object after repair.
+pub fn merge_json_fragments_with(fragments: &[&str], conflict: MergeConflict) -> Result<Value> {
+    let mut merged = Vec::new();
+    let mut repairer = JsonRepairer::new();
+    for (i, fragment) in fragments.iter().enumerate() {
+        if fragment.trim().is_empty() {
+            continue;
+        }
+        let repaired = repairer.repair(fragment)?;
+        match parse_json(&repaired)? {
+            Value::Object(entries) => merge_into(&mut merged, entries, conflict, "")?,
+            _ => {
+                return Err(RepairError::Conversion(format!(
+                    "fragment {} is not a JSON object",
+                    i + 1
+                )));
+            }
+        }
+    }
+    Ok(Value::Object(merged))
+}
+
+/// Merge `entries` into `target`; `path` is the JSON Pointer of `target`
+fn merge_into(
+    target: &mut Vec<(String, Value)>,
+    entries: Vec<(String, Value)>,
+    conflict: MergeConflict,
+    path: &str,
+) -> Result<()> {
+    for (key, value) in entries {
+        let Some(slot) = target.iter_mut().find(|(k, _)| *k == key) else {
+            target.push((key, value));
+            continue;
+        };
+        let member = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+        match (&mut slot.1, value) {
+            (Value::Object(existing), Value::Object(incoming)) => {
+                merge_into(existing, incoming, conflict, &member)?;
+            }
+            (existing, incoming) => match conflict {
+                MergeConflict::LastWins => *existing = incoming,
+                MergeConflict::FirstWins => {}
+                MergeConflict::Error => {
+                    return Err(RepairError::Conversion(format!(
+                        "conflicting values for {}",
+                        member
+                    )));
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_non_overlapping_fragments() {
+        let merged =
+            merge_json_fragments(&[r#"{"a": 1, "n": {"x": true}}"#, "{'b': 2, n: {y: null},}"])
+                .unwrap();
+        let expected = r#"{"a": 1, "n": {"x": true, "y": null}, "b": 2}"#;
+        assert_eq!(merged, parse_json(expected).unwrap());
+    }
+
+    #[test]
+    fn test_merge_conflicting_key() {
+        let fragments = [r#"{"a": 1, "n": {"x": 1}}"#, r#"{"n": {"x": [2]}}"#];
+
+        let last = merge_json_fragments(&fragments).unwrap();
+        assert_eq!(last, parse_json(r#"{"a": 1, "n": {"x": [2]}}"#).unwrap());
+
+        let first = merge_json_fragments_with(&fragments, MergeConflict::FirstWins).unwrap();
+        assert_eq!(first, parse_json(r#"{"a": 1, "n": {"x": 1}}"#).unwrap());
+
+        let error = merge_json_fragments_with(&fragments, MergeConflict::Error).unwrap_err();
+        assert!(error.to_string().contains("/n/x"));
+        assert!(merge_json_fragments(&["[1, 2]"]).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Assemble JSON from path-annotated fragments
+//!
+//! Some tools emit a document as one `path: value` line per leaf, with the path
+//! written as a JSON Pointer (`/users/0/name: "Alice"`) or a JSONPath
+//! (`$.users[0].name: "Alice"`). [`assemble_from_paths`] repairs each value as
+//! JSON and builds the nested structure the paths describe.
+
+use crate::convert::{Value, parse_json};
+use crate::error::{RepairError, Result};
+use crate::json::JsonRepairer;
+use crate::traits::Repair;
+
+/// One step of a path: an object member or an array index
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Build a document from `path: value` lines.
+///
+/// Paths are JSON Pointers (`/a/0/b`, with `~1` for `/` and `~0` for `~`) or
+/// JSONPaths (`$.a[0].b`, `$['a b']`). Numeric segments index arrays, so
+/// `/items/0` creates an array under `items`; a gap in the indices is filled with
+/// `null`. Each value is repaired as JSON, and kept as a string if it still isn't
+/// JSON (`/name: Alice`). Later lines overwrite earlier ones; blank lines are skipped.
+///
+/// Returns `RepairError::Conversion` for a line without a path, a path that runs
+/// through a scalar or uses a member name on an array, or an index more than
+/// [`MAX_INDEX_GAP`] past the end of its array.
+pub fn assemble_from_paths(lines: &[&str]) -> Result<Value> {
+    let mut root: Option<Value> = None;
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let error = |message: &str| {
+            RepairError::Conversion(format!("line {}: {}: {}", i + 1, message, line.trim()))
+        };
+        let (segments, raw) = match line.trim_start().strip_prefix('$') {
+            // A JSONPath key may contain `:`, so the value starts after the parsed path
+            Some(json_path) => {
+                let (segments, rest) =
+                    parse_json_path(json_path).ok_or_else(|| error("invalid path"))?;
+                let raw = rest
+                    .trim_start()
+                    .strip_prefix(':')
+                    .ok_or_else(|| error("missing ':'"))?;
+                (segments, raw)
+            }
+            None => {
+                let (path, raw) = line.split_once(':').ok_or_else(|| error("missing ':'"))?;
+                let segments = parse_pointer(path.trim()).ok_or_else(|| error("invalid path"))?;
+                (segments, raw)
+            }
+        };
+        let value = repair_value(raw.trim())?;
+        insert(&mut root, &segments, value).map_err(error)?;
+    }
+    Ok(root.unwrap_or(Value::Object(Vec::new())))
+}
+
+/// Parse a JSON Pointer (or an empty path, for the root) into its segments
+fn parse_pointer(path: &str) -> Option<Vec<Segment>> {
+    if path.is_empty() {
+        return Some(Vec::new());
+    }
+    let pointer = path.strip_prefix('/')?;
+    Some(
+        pointer
+            .split('/')
+            .map(|token| segment(token.replace("~1", "/").replace("~0", "~")))
+            .collect(),
+    )
+}
+
+/// Parse the `.name`, `[0]` and `['name']` steps of a JSONPath after its `$`,
+/// returning the segments and the text after the last step
+fn parse_json_path(mut rest: &str) -> Option<(Vec<Segment>, &str)> {
+    let mut segments = Vec::new();
+    loop {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after
+                .find(|c: char| matches!(c, '.' | '[' | ':') || c.is_whitespace())
+                .unwrap_or(after.len());
+            if end == 0 {
+                return None;
+            }
+            segments.push(Segment::Key(after[..end].to_string()));
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let after = after.trim_start();
+            match after.chars().next() {
+                Some(quote @ ('\'' | '"')) => {
+                    let end = after[1..].find(quote)? + 1;
+                    segments.push(Segment::Key(after[1..end].to_string()));
+                    rest = after[end + 1..].trim_start().strip_prefix(']')?;
+                }
+                _ => {
+                    let end = after.find(']')?;
+                    segments.push(Segment::Index(after[..end].trim().parse().ok()?));
+                    rest = &after[end + 1..];
+                }
+            }
+        } else {
+            return Some((segments, rest));
+        }
+    }
+}
+
+/// A pointer token is an index if it is a non-negative integer
+fn segment(token: String) -> Segment {
+    match token.parse() {
+        Ok(index) if token.bytes().all(|b| b.is_ascii_digit()) => Segment::Index(index),
+        _ => Segment::Key(token),
+    }
+}
+
+/// Repair `raw` as JSON, falling back to a string if it still isn't JSON
+fn repair_value(raw: &str) -> Result<Value> {
+    if raw.is_empty() {
+        return Ok(Value::Null);
+    }
+    if let Ok(value) = parse_json(raw) {
+        return Ok(value);
+    }
+    let repaired = JsonRepairer::new().repair(raw)?;
+    Ok(parse_json(&repaired).unwrap_or_else(|_| Value::String(raw.to_string())))
+}
+
+/// Furthest past the end of an array an index may reach; the gap is filled with
+/// `null`, so this bounds what an untrusted path can make us allocate
+pub const MAX_INDEX_GAP: usize = 1024;
+
+/// Set the value at `segments` under `slot`, creating containers on the way
+fn insert(
+    slot: &mut Option<Value>,
+    segments: &[Segment],
+    value: Value,
+) -> std::result::Result<(), &'static str> {
+    let Some((first, rest)) = segments.split_first() else {
+        *slot = Some(value);
+        return Ok(());
+    };
+    let container = slot.get_or_insert_with(|| match first {
+        Segment::Index(_) => Value::Array(Vec::new()),
+        Segment::Key(_) => Value::Object(Vec::new()),
+    });
+
+    let child = match (container, first) {
+        (Value::Array(items), Segment::Index(index)) => {
+            if *index > items.len() + MAX_INDEX_GAP {
+                return Err("array index too far past the end");
+            }
+            if *index >= items.len() {
+                items.resize(*index + 1, Value::Null);
+            }
+            &mut items[*index]
+        }
+        (Value::Object(entries), segment) => {
+            let key = match segment {
+                Segment::Key(key) => key.clone(),
+                Segment::Index(index) => index.to_string(),
+            };
+            let position = match entries.iter().position(|(k, _)| *k == key) {
+                Some(position) => position,
+                None => {
+                    entries.push((key, Value::Null));
+                    entries.len() - 1
+                }
+            };
+            &mut entries[position].1
+        }
+        (Value::Array(_), Segment::Key(_)) => return Err("member name used on an array"),
+        _ => return Err("path runs through a scalar"),
+    };
+
+    // A `null` placeholder becomes whatever container the rest of the path needs
+    let mut nested = match std::mem::replace(child, Value::Null) {
+        Value::Null => None,
+        existing => Some(existing),
+    };
+    let result = insert(&mut nested, rest, value);
+    *child = nested.unwrap_or(Value::Null);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pointer_lines_build_nested_object() {
+        let value = assemble_from_paths(&[
+            "/users/0/name: \"Alice\"",
+            "/users/0/roles: ['admin', 'dev',]",
+            "/users/1/name: Bob",
+            "/meta/a~1b: True",
+        ])
+        .unwrap();
+        let expected = r#"{"users": [{"name": "Alice", "roles": ["admin", "dev"]}, {"name": "Bob"}], "meta": {"a/b": true}}"#;
+        assert_eq!(value, parse_json(expected).unwrap());
+    }
+
+    #[test]
+    fn test_json_path_lines_and_errors() {
+        let value =
+            assemble_from_paths(&["$.server.ports[1]: 443", "$['server'].host: 'example.com'"])
+                .unwrap();
+        let expected = r#"{"server": {"ports": [null, 443], "host": "example.com"}}"#;
+        assert_eq!(value, parse_json(expected).unwrap());
+
+        assert!(assemble_from_paths(&["/a: 1", "/a/b: 2"]).is_err());
+        assert!(assemble_from_paths(&["/items/0: 1", "$.items.name: 2"]).is_err());
+        assert!(assemble_from_paths(&["no path here"]).is_err());
+    }
+
+    #[test]
+    fn test_json_path_keys_with_colons() {
+        let value = assemble_from_paths(&["$['a:b']: 1", "$.c[\"x: y\"]:2"]).unwrap();
+        let expected = r#"{"a:b": 1, "c": {"x: y": 2}}"#;
+        assert_eq!(value, parse_json(expected).unwrap());
+    }
+
+    #[test]
+    fn test_huge_indices_rejected() {
+        for line in [
+            "/a/18446744073709551615: 1",
+            "/a/100000000000: 1",
+            "$.a[1025]: 1",
+        ] {
+            assert!(
+                matches!(
+                    assemble_from_paths(&[line]),
+                    Err(RepairError::Conversion(_))
+                ),
+                "{}",
+                line
+            );
+        }
+        let value = assemble_from_paths(&["$.a[1024]: 1"]).unwrap();
+        assert!(matches!(value, Value::Object(ref entries) if entries.len() == 1));
+    }
+}
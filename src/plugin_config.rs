@@ -0,0 +1,360 @@
+//! Plugins activated from a configuration file
+//!
+//! A plugin adds a repair strategy to the repairer of a built-in format. Which
+//! plugins run, and with what settings, is read from the `[plugins]` section of
+//! a TOML file, one table per plugin (other sections are ignored):
+//!
+//! ```toml
+//! [plugins.replace]
+//! format = "json"
+//! from = "N/A"
+//! to = "null"
+//! ```
+//!
+//! [`PluginRegistry::from_config`] activates the enabled tables against the
+//! [`builtin_plugins`], and [`PluginRegistry::install`] registers the extended
+//! repairers with [`crate::register_repairer`], so [`crate::repair`] and
+//! [`crate::create_repairer`] use them. A table with `enabled = false` is skipped.
+
+use crate::convert::Value;
+use crate::error::{RepairError, Result};
+use crate::traits::{Repair, RepairStrategy};
+use std::path::Path;
+use std::sync::Arc;
+
+/// A plugin strategy, shared by every repairer built from the same registry
+pub type SharedStrategy = Arc<dyn RepairStrategy + Send + Sync>;
+
+/// A named extension that adds a strategy to one format's repairer
+pub trait Plugin: Send + Sync {
+    /// Name of the plugin's `[plugins.<name>]` table
+    fn name(&self) -> &str;
+
+    /// Build the plugin's strategy from its settings, along with the format it
+    /// repairs. Returns `RepairError::Plugin` if a setting is missing or invalid.
+    fn activate(&self, settings: &PluginSettings) -> Result<(String, SharedStrategy)>;
+}
+
+/// One `[plugins.<name>]` table of a [`PluginConfig`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginSettings {
+    /// Plugin name, the key of the table
+    pub name: String,
+    /// `false` if the table sets `enabled = false`
+    pub enabled: bool,
+    /// Every other key of the table, in file order
+    pub values: Vec<(String, Value)>,
+}
+
+impl PluginSettings {
+    /// The setting `key`, if present
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// The string setting `key`, if present.
+    /// Returns `RepairError::Plugin` if it is set to anything but a string.
+    pub fn get_str(&self, key: &str) -> Result<Option<&str>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s)),
+            Some(_) => Err(plugin_error(format!(
+                "plugins.{}.{} must be a string",
+                self.name, key
+            ))),
+        }
+    }
+}
+
+/// Plugins listed in the `[plugins]` section of a configuration file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PluginConfig {
+    /// The `[plugins.<name>]` tables, in file order
+    pub plugins: Vec<PluginSettings>,
+}
+
+impl PluginConfig {
+    /// Read the plugin configuration from the TOML file at `path`.
+    /// Returns `RepairError::Io` if it cannot be read, and `RepairError::Plugin`
+    /// if it is not valid TOML or `[plugins]` is malformed.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content)
+    }
+
+    /// Read the plugin configuration from TOML `content`, like [`from_file`](Self::from_file)
+    pub fn parse(content: &str) -> Result<Self> {
+        let root = crate::convert::parse_toml(content).map_err(|e| match e {
+            RepairError::Conversion(message) => plugin_error(format!("invalid TOML: {}", message)),
+            other => other,
+        })?;
+        let tables = match root {
+            Value::Object(mut sections) => {
+                match sections.iter().position(|(key, _)| key == "plugins") {
+                    Some(i) => sections.swap_remove(i).1,
+                    None => return Ok(Self::default()),
+                }
+            }
+            _ => return Ok(Self::default()),
+        };
+        let Value::Object(tables) = tables else {
+            return Err(plugin_error("[plugins] must be a table"));
+        };
+
+        let mut plugins = Vec::with_capacity(tables.len());
+        for (name, table) in tables {
+            let Value::Object(entries) = table else {
+                return Err(plugin_error(format!("plugins.{} must be a table", name)));
+            };
+            let mut enabled = true;
+            let mut values = Vec::new();
+            for (key, value) in entries {
+                match (key.as_str(), value) {
+                    ("enabled", Value::Bool(on)) => enabled = on,
+                    ("enabled", _) => {
+                        return Err(plugin_error(format!(
+                            "plugins.{}.enabled must be a boolean",
+                            name
+                        )));
+                    }
+                    (_, value) => values.push((key, value)),
+                }
+            }
+            plugins.push(PluginSettings {
+                name,
+                enabled,
+                values,
+            });
+        }
+        Ok(Self { plugins })
+    }
+}
+
+/// The plugins enabled by a [`PluginConfig`], activated with their settings
+#[derive(Clone, Default)]
+pub struct PluginRegistry {
+    /// Activated strategies with the format each repairs, in config order
+    strategies: Vec<(&'static str, SharedStrategy)>,
+}
+
+impl PluginRegistry {
+    /// Activate the enabled plugins of `config`, looked up by name among the
+    /// [`builtin_plugins`]. Returns `RepairError::Plugin` for an unknown plugin,
+    /// invalid settings, or a plugin targeting a format that is not built in.
+    pub fn from_config(config: &PluginConfig) -> Result<Self> {
+        let available = builtin_plugins();
+        let mut strategies = Vec::new();
+        for settings in config.plugins.iter().filter(|s| s.enabled) {
+            let plugin = available
+                .iter()
+                .find(|p| p.name() == settings.name)
+                .ok_or_else(|| plugin_error(format!("unknown plugin {}", settings.name)))?;
+            let (format, strategy) = plugin.activate(settings)?;
+            let format = crate::parse_supported_format(&format).map_err(|_| {
+                plugin_error(format!(
+                    "plugins.{}: unsupported format {}",
+                    settings.name, format
+                ))
+            })?;
+            strategies.push((format, strategy));
+        }
+        Ok(Self { strategies })
+    }
+
+    /// Formats that have at least one plugin strategy
+    pub fn formats(&self) -> Vec<&'static str> {
+        let mut formats: Vec<&'static str> = Vec::new();
+        for (format, _) in &self.strategies {
+            if !formats.contains(format) {
+                formats.push(format);
+            }
+        }
+        formats
+    }
+
+    /// Build the repairer of `format`: its plugin strategies, highest priority
+    /// first, followed by the format's built-in repairer.
+    /// Returns `RepairError::FormatDetection` if the format is unknown.
+    pub fn create_repairer(&self, format: &str) -> Result<Box<dyn Repair>> {
+        let base = crate::create_builtin_repairer(format)?;
+        let format = crate::parse_supported_format(format)?;
+        let mut strategies: Vec<SharedStrategy> = self
+            .strategies
+            .iter()
+            .filter(|(f, _)| *f == format)
+            .map(|(_, strategy)| Arc::clone(strategy))
+            .collect();
+        strategies.sort_by_key(|s| std::cmp::Reverse(s.priority()));
+        Ok(Box::new(PluginRepairer { strategies, base }))
+    }
+
+    /// Register the repairer of every format in [`formats`](Self::formats) with
+    /// [`crate::register_repairer`], replacing its built-in repairer
+    pub fn install(&self) {
+        for format in self.formats() {
+            let registry = self.clone();
+            crate::register_repairer(format, move || {
+                registry
+                    .create_repairer(format)
+                    .expect("plugin formats are built in")
+            });
+        }
+    }
+}
+
+/// A built-in repairer with plugin strategies run on its input first
+struct PluginRepairer {
+    strategies: Vec<SharedStrategy>,
+    base: Box<dyn Repair>,
+}
+
+impl Repair for PluginRepairer {
+    fn repair(&mut self, content: &str) -> Result<String> {
+        let mut current = content.to_string();
+        for strategy in &self.strategies {
+            current = strategy.apply(&current)?;
+        }
+        self.base.repair(&current)
+    }
+
+    fn needs_repair(&self, content: &str) -> bool {
+        self.strategies
+            .iter()
+            .any(|s| s.apply(content).is_ok_and(|result| result != content))
+            || self.base.needs_repair(content)
+    }
+
+    fn confidence(&self, content: &str) -> f64 {
+        self.base.confidence(content)
+    }
+}
+
+/// Plugins that ship with the crate and can be enabled by name
+pub fn builtin_plugins() -> Vec<Arc<dyn Plugin>> {
+    vec![Arc::new(ReplacePlugin)]
+}
+
+/// `[plugins.replace]`: replace every `from` with `to` (default empty) in input
+/// of `format` (default `json`) before it is repaired
+pub struct ReplacePlugin;
+
+impl Plugin for ReplacePlugin {
+    fn name(&self) -> &str {
+        "replace"
+    }
+
+    fn activate(&self, settings: &PluginSettings) -> Result<(String, SharedStrategy)> {
+        let from = match settings.get_str("from")? {
+            Some(from) if !from.is_empty() => from.to_string(),
+            _ => {
+                return Err(plugin_error(
+                    "plugins.replace.from must be a non-empty string",
+                ));
+            }
+        };
+        let to = settings.get_str("to")?.unwrap_or_default().to_string();
+        let format = settings.get_str("format")?.unwrap_or("json").to_string();
+        Ok((format, Arc::new(ReplaceStrategy { from, to })))
+    }
+}
+
+/// Strategy of [`ReplacePlugin`]
+struct ReplaceStrategy {
+    from: String,
+    to: String,
+}
+
+impl RepairStrategy for ReplaceStrategy {
+    fn name(&self) -> &str {
+        "Replace"
+    }
+
+    fn description(&self) -> &str {
+        "Replace configured text before repair"
+    }
+
+    fn apply(&self, content: &str) -> Result<String> {
+        Ok(content.replace(&self.from, &self.to))
+    }
+
+    fn priority(&self) -> u8 {
+        100
+    }
+}
+
+fn plugin_error(message: impl Into<String>) -> RepairError {
+    RepairError::Plugin(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[global]
+verbose = false
+
+[plugins.replace]
+format = "json"
+from = "N/A"
+to = "null"
+"#;
+
+    #[test]
+    fn test_config_enables_replace_plugin() {
+        let path =
+            std::env::temp_dir().join(format!("anyrepair-plugins-{}.toml", std::process::id()));
+        std::fs::write(&path, CONFIG).unwrap();
+        let config = PluginConfig::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.plugins.len(), 1);
+        assert_eq!(config.plugins[0].get_str("from").unwrap(), Some("N/A"));
+        let registry = PluginRegistry::from_config(&config).unwrap();
+        assert_eq!(registry.formats(), vec!["json"]);
+
+        let mut repairer = registry.create_repairer("json").unwrap();
+        assert!(repairer.needs_repair(r#"{"a": N/A}"#));
+        assert_eq!(
+            repairer.repair(r#"{"a": N/A, "b": [1, 2,]}"#).unwrap(),
+            r#"{"a": null, "b": [1, 2]}"#
+        );
+    }
+
+    #[test]
+    fn test_disabled_and_invalid_plugins() {
+        let disabled =
+            PluginConfig::parse("[plugins.replace]\nenabled = false\nfrom = \"x\"").unwrap();
+        assert!(
+            PluginRegistry::from_config(&disabled)
+                .unwrap()
+                .formats()
+                .is_empty()
+        );
+        assert_eq!(
+            PluginConfig::parse("[global]\nverbose = true").unwrap(),
+            PluginConfig::default()
+        );
+
+        for config in [
+            "[plugins.missing]\nfrom = \"x\"",
+            "[plugins.replace]\nto = \"y\"",
+            "[plugins.replace]\nfrom = \"x\"\nformat = \"nope\"",
+            "[plugins.replace]\nfrom = 1",
+        ] {
+            let config = PluginConfig::parse(config).unwrap();
+            assert!(
+                matches!(
+                    PluginRegistry::from_config(&config),
+                    Err(RepairError::Plugin(_))
+                ),
+                "{:?}",
+                config
+            );
+        }
+        assert!(matches!(
+            PluginConfig::parse("[plugins]\nreplace = 1"),
+            Err(RepairError::Plugin(_))
+        ));
+    }
+}
@@ -0,0 +1,116 @@
+//! Registry for user-supplied formats
+//!
+//! Lets applications with proprietary formats plug a [`FormatDetector`] into
+//! [`crate::detect_format`] and a repairer factory into [`crate::create_repairer`],
+//! so that [`crate::repair`] routes matching content to their own repairer.
+
+use crate::traits::Repair;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Detects a custom format and reports how confident the match is
+pub trait FormatDetector: Send + Sync {
+    /// Return `(format_name, confidence)` if the content looks like this format.
+    /// Confidence is in `0.0..=1.0`; it must beat the built-in detection to win.
+    fn detect(&self, content: &str) -> Option<(String, f64)>;
+}
+
+/// Constructor for a custom format's repairer
+pub type RepairerFactory = Arc<dyn Fn() -> Box<dyn Repair> + Send + Sync>;
+
+#[derive(Default)]
+struct Registry {
+    detectors: Vec<Arc<dyn FormatDetector>>,
+    repairers: HashMap<String, RepairerFactory>,
+    /// Interned format names, so detection can keep returning `&'static str`
+    names: HashMap<String, &'static str>,
+}
+
+static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<Registry> {
+    REGISTRY.get_or_init(|| RwLock::new(Registry::default()))
+}
+
+/// Register a detector consulted by [`crate::detect_format`] after the built-in heuristics
+///
+/// A detection naming a format with no repairer (built-in or registered) is ignored.
+pub fn register_detector(detector: Box<dyn FormatDetector>) {
+    registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .detectors
+        .push(Arc::from(detector));
+}
+
+/// Register the repairer used by [`crate::create_repairer`] for a custom format name
+pub fn register_repairer(
+    format: &str,
+    factory: impl Fn() -> Box<dyn Repair> + Send + Sync + 'static,
+) {
+    registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .repairers
+        .insert(format.to_ascii_lowercase(), Arc::new(factory));
+}
+
+/// Register a detector and the repairer for the format it detects in one call
+pub fn register_format(
+    format: &str,
+    detector: Box<dyn FormatDetector>,
+    factory: impl Fn() -> Box<dyn Repair> + Send + Sync + 'static,
+) {
+    register_repairer(format, factory);
+    register_detector(detector);
+}
+
+/// Build the repairer registered for `format`, if any
+pub(crate) fn custom_repairer(format: &str) -> Option<Box<dyn Repair>> {
+    // Like detectors, factories run without the lock held
+    let factory = registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .repairers
+        .get(&format.to_ascii_lowercase())
+        .cloned()?;
+    Some(factory())
+}
+
+/// Highest-scoring custom detection for already-trimmed content, among formats
+/// that [`crate::create_repairer`] can build a repairer for
+pub(crate) fn best_custom_detection(trimmed: &str) -> Option<(&'static str, f64)> {
+    // Detectors run without the lock held, so they may register formats themselves
+    let detectors = registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .detectors
+        .clone();
+    let best = detectors
+        .iter()
+        .filter_map(|d| d.detect(trimmed))
+        .filter(|(format, _)| has_repairer(format))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    Some((intern(best.0), best.1.clamp(0.0, 1.0)))
+}
+
+/// Whether `format` is a built-in format or has a registered repairer
+fn has_repairer(format: &str) -> bool {
+    crate::parse_supported_format(format).is_ok()
+        || registry()
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .repairers
+            .contains_key(&format.to_ascii_lowercase())
+}
+
+/// Leak each distinct custom format name once so it can be handed out as `&'static str`
+fn intern(name: String) -> &'static str {
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    if let Some(&interned) = registry.names.get(&name) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(name.clone().into_boxed_str());
+    registry.names.insert(name, interned);
+    interned
+}
@@ -1,12 +1,37 @@
 //! Generic repair loop: validator gate + ordered `RepairStrategy` pipeline.
 
-use crate::error::Result;
+use crate::edit::{Edit, diff_edits};
+use crate::error::{RepairError, Result};
+use crate::report::RemovedSpan;
 use crate::traits::{Repair, RepairStrategy, Validator};
+use std::borrow::Cow;
 
 /// Composes a `Validator` with strategy objects (sorted by `priority`, high first).
+///
+/// Input is trimmed and a leading UTF-8 BOM is stripped before repair; the
+/// original line endings (LF vs CRLF) and final newline are restored on the
+/// output. The BOM is only re-emitted if [`set_preserve_bom`](Self::set_preserve_bom) is enabled.
+///
+/// With [`set_max_depth`](Self::set_max_depth), input nested deeper than the limit
+/// is rejected with `RepairError::MaxDepthExceeded`. There is no limit by default;
+/// the JSON, YAML and TOML repairers, where `{`/`[` are structure rather than
+/// text, start at [`DEFAULT_MAX_DEPTH`].
 pub struct GenericRepairer {
     strategies: Vec<Box<dyn RepairStrategy>>,
     validator: Box<dyn Validator>,
+    preserve_bom: bool,
+    max_depth: Option<usize>,
+}
+
+/// Options shared by every repairer built on [`GenericRepairer`], set through the
+/// format builders such as [`JsonRepairer::builder`](crate::json::JsonRepairer::builder)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BaseOptions {
+    /// Keep a leading UTF-8 BOM of the input on the repaired output
+    pub preserve_bom: bool,
+    /// Deepest `{`/`[` nesting accepted in the input: `Some(None)` disables the
+    /// check, `None` keeps the repairer's default
+    pub max_depth: Option<Option<usize>>,
 }
 
 impl GenericRepairer {
@@ -21,24 +46,82 @@ impl GenericRepairer {
         Self {
             strategies,
             validator,
+            preserve_bom: false,
+            max_depth: None,
         }
     }
 
-    /// Apply all repair strategies to the content, tracking which ones changed it.
-    fn apply_strategies_with_explanations(&mut self, content: &str) -> Result<(String, Vec<String>)> {
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn set_preserve_bom(&mut self, preserve: bool) {
+        self.preserve_bom = preserve;
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.max_depth = max_depth;
+    }
+
+    /// Apply every option in `options`
+    pub fn apply_options(&mut self, options: BaseOptions) {
+        self.set_preserve_bom(options.preserve_bom);
+        if let Some(max_depth) = options.max_depth {
+            self.set_max_depth(max_depth);
+        }
+    }
+
+    /// Reject input nested deeper than the configured limit
+    pub(crate) fn check_depth(&self, content: &str) -> Result<()> {
+        match self.max_depth {
+            Some(limit) => {
+                let depth = nesting_depth(content);
+                if depth > limit {
+                    return Err(RepairError::MaxDepthExceeded { depth, limit });
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Restore the formatting of `original` (line endings, final newline, optional BOM)
+    fn finish(&self, original: &str, repaired: &str) -> String {
+        let restored = restore_line_endings(original, repaired);
+        if self.preserve_bom && original.starts_with(BOM) {
+            format!("{}{}", BOM, restored)
+        } else {
+            restored
+        }
+    }
+
+    /// Apply all repair strategies to the content, logging `(name, description)` for
+    /// each one that changed it and, if given a `removals` log, what it deleted.
+    fn apply_strategies_with_log(
+        &mut self,
+        content: &str,
+        mut removals: Option<&mut RemovalLog>,
+    ) -> Result<(String, Vec<(String, String)>)> {
         let mut repaired = content.to_string();
-        let mut applied = Vec::new();
+        let mut log = Vec::new();
 
         for strategy in self.strategies.iter() {
-            if let Ok(result) = strategy.apply(&repaired) {
-                if result != repaired {
-                    applied.push(strategy.name().to_string());
-                    repaired = result;
+            if let Ok((result, Some(description))) = strategy.apply_with_log(&repaired)
+                && result != repaired
+            {
+                if let Some(removals) = removals.as_deref_mut() {
+                    removals.record(&repaired, strategy.removals(&repaired));
                 }
+                log.push((strategy.name().to_string(), description));
+                repaired = result;
             }
         }
 
-        Ok((repaired, applied))
+        Ok((repaired, log))
+    }
+
+    /// Apply all repair strategies to the content, tracking which ones changed it.
+    fn apply_strategies_with_explanations(&mut self, content: &str) -> Result<(String, Vec<String>)> {
+        let (repaired, log) = self.apply_strategies_with_log(content, None)?;
+        Ok((repaired, log.into_iter().map(|(name, _)| name).collect()))
     }
 
     /// Apply all repair strategies to the content
@@ -51,17 +134,100 @@ impl GenericRepairer {
     /// Returns `(repaired_content, applied_strategy_names)`.
     /// If the content is already valid, returns `(content, [])`.
     pub fn repair_with_explanations(&mut self, content: &str) -> Result<(String, Vec<String>)> {
-        let trimmed = content.trim();
+        let trimmed = repair_input(content);
 
         if trimmed.is_empty() {
             return Ok((String::new(), Vec::new()));
         }
+        self.check_depth(trimmed)?;
 
         if self.validator.is_valid(trimmed) {
-            return Ok((trimmed.to_string(), Vec::new()));
+            return Ok((self.finish(content, trimmed), Vec::new()));
         }
 
-        self.apply_strategies_with_explanations(trimmed)
+        let (repaired, applied) = self.apply_strategies_with_explanations(trimmed)?;
+        Ok((self.finish(content, &repaired), applied))
+    }
+
+    /// Like [`repair_with_explanations`](Self::repair_with_explanations), also returning
+    /// the text strategies deleted outright, with byte ranges into `content`.
+    pub fn repair_with_removals(
+        &mut self,
+        content: &str,
+    ) -> Result<(String, Vec<String>, Vec<RemovedSpan>)> {
+        let trimmed = repair_input(content);
+
+        if trimmed.is_empty() {
+            return Ok((String::new(), Vec::new(), Vec::new()));
+        }
+        self.check_depth(trimmed)?;
+
+        if self.validator.is_valid(trimmed) {
+            return Ok((self.finish(content, trimmed), Vec::new(), Vec::new()));
+        }
+
+        let base = offset_in(content, trimmed);
+        let mut removals = RemovalLog::new(trimmed);
+        let (repaired, log) = self.apply_strategies_with_log(trimmed, Some(&mut removals))?;
+        let applied = log.into_iter().map(|(name, _)| name).collect();
+        let removed = removals
+            .spans
+            .into_iter()
+            .map(|span| RemovedSpan {
+                byte_range: span.byte_range.start + base..span.byte_range.end + base,
+                ..span
+            })
+            .collect();
+        Ok((self.finish(content, &repaired), applied, removed))
+    }
+
+    /// Repair content and describe each change made, in application order.
+    /// If the content is already valid, returns `(content, [])`.
+    pub fn repair_with_log(&mut self, content: &str) -> Result<(String, Vec<String>)> {
+        let trimmed = repair_input(content);
+
+        if trimmed.is_empty() {
+            return Ok((String::new(), Vec::new()));
+        }
+        self.check_depth(trimmed)?;
+
+        if self.validator.is_valid(trimmed) {
+            return Ok((self.finish(content, trimmed), Vec::new()));
+        }
+
+        let (repaired, log) = self.apply_strategies_with_log(trimmed, None)?;
+        let descriptions = log
+            .into_iter()
+            .map(|(_, description)| description)
+            .collect();
+        Ok((self.finish(content, &repaired), descriptions))
+    }
+
+    /// Propose the edits `repair` would make, without producing the final string.
+    /// Ranges are byte offsets into `content` (see [`Edit`] for ordering).
+    /// Returns no edits if the content is already valid.
+    pub fn preview(&self, content: &str) -> Result<Vec<Edit>> {
+        let trimmed = repair_input(content);
+        if trimmed.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.check_depth(trimmed)?;
+        if self.validator.is_valid(trimmed) {
+            return Ok(Vec::new());
+        }
+
+        let base = offset_in(content, trimmed);
+        let mut current = trimmed.to_string();
+        let mut edits = Vec::new();
+        for strategy in self.strategies.iter() {
+            if let Ok(result) = strategy.apply(&current)
+                && result != current
+            {
+                edits.extend(diff_edits(&current, &result, strategy.name(), base));
+                current = result;
+            }
+        }
+        Ok(edits)
     }
 
     /// Get the validator
@@ -77,33 +243,204 @@ impl GenericRepairer {
 
 impl Repair for GenericRepairer {
     fn repair(&mut self, content: &str) -> Result<String> {
-        let trimmed = content.trim();
+        self.repair_cow(content).map(Cow::into_owned)
+    }
+
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        let trimmed = repair_input(content);
 
         // Handle empty content
         if trimmed.is_empty() {
-            return Ok(String::new());
+            return Ok(Cow::Borrowed(trimmed));
         }
+        self.check_depth(trimmed)?;
 
         // If already valid, return as-is
-        if self.validator.is_valid(trimmed) {
-            return Ok(trimmed.to_string());
-        }
-
-        // Apply repair strategies
-        let repaired = self.apply_strategies_internal(trimmed)?;
+        let repaired = if self.validator.is_valid(trimmed) {
+            // Nothing was trimmed and there are no line endings to restore
+            if trimmed.len() == content.len() && !content.contains('\r') {
+                return Ok(Cow::Borrowed(content));
+            }
+            self.finish(content, trimmed)
+        } else {
+            // Apply repair strategies
+            let repaired = self.apply_strategies_internal(trimmed)?;
+            self.finish(content, &repaired)
+        };
 
-        Ok(repaired)
+        Ok(if repaired == content {
+            Cow::Borrowed(content)
+        } else {
+            Cow::Owned(repaired)
+        })
     }
 
     fn needs_repair(&self, content: &str) -> bool {
-        !self.validator.is_valid(content)
+        !self.validator.is_valid(strip_bom(content))
     }
 
     fn confidence(&self, content: &str) -> f64 {
-        if self.validator.is_valid(content) {
+        if self.validator.is_valid(strip_bom(content)) {
             1.0
         } else {
             0.0
         }
     }
 }
+
+/// Default `{`/`[` nesting limit of the JSON, YAML and TOML repairers
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
+/// Deepest `{`/`[` nesting in `content`, ignoring brackets inside `"` strings.
+/// Unbalanced closers are ignored rather than driving the count negative.
+pub fn nesting_depth(content: &str) -> usize {
+    let mut depth = 0usize;
+    let mut max = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in content.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => {
+                depth += 1;
+                max = max.max(depth);
+            }
+            '}' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    max
+}
+
+/// UTF-8 byte order mark, as written at the start of files by some Windows tools
+pub const BOM: char = '\u{FEFF}';
+
+/// Strip a leading UTF-8 byte order mark, if present
+pub fn strip_bom(content: &str) -> &str {
+    content.strip_prefix(BOM).unwrap_or(content)
+}
+
+/// Collects the [`RemovedSpan`]s of a repair, mapping each strategy's byte ranges
+/// back to the input through the edits made before it ran
+struct RemovalLog<'a> {
+    input: &'a str,
+    /// Text reached from `input` by applying `edits`
+    mapped: String,
+    edits: Vec<Edit>,
+    spans: Vec<RemovedSpan>,
+}
+
+impl<'a> RemovalLog<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            mapped: input.to_string(),
+            edits: Vec::new(),
+            spans: Vec::new(),
+        }
+    }
+
+    /// Record `spans`, removed by a strategy from `current`
+    fn record(&mut self, current: &str, spans: Vec<RemovedSpan>) {
+        if spans.is_empty() {
+            return;
+        }
+        // Only diff when needed; edits of several strategies are recovered at once
+        if self.mapped != current {
+            self.edits.extend(diff_edits(&self.mapped, current, "", 0));
+            self.mapped = current.to_string();
+        }
+        for span in spans {
+            let byte_range = crate::edit::original_range(&self.edits, span.byte_range);
+            self.spans.push(RemovedSpan {
+                text: self.input[byte_range.clone()].to_string(),
+                byte_range,
+                reason: span.reason,
+            });
+        }
+    }
+}
+
+/// The part of `content` that strategies see: BOM and outer whitespace stripped.
+/// Content that is nothing but a BOM is left alone rather than emptied.
+fn repair_input(content: &str) -> &str {
+    match strip_bom(content).trim() {
+        "" => content.trim(),
+        trimmed => trimmed,
+    }
+}
+
+/// Byte offset of `part`, a subslice of `content`, within `content`
+fn offset_in(content: &str, part: &str) -> usize {
+    part.as_ptr() as usize - content.as_ptr() as usize
+}
+
+/// Line-ending style of a document: the dominant terminator and whether it ends with one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineEndings {
+    /// `"\r\n"` when CRLF terminators outnumber bare LF ones, otherwise `"\n"`
+    pub newline: &'static str,
+    /// Whether the document ends with a line terminator
+    pub trailing_newline: bool,
+}
+
+impl LineEndings {
+    /// Detect the line-ending style of `content`
+    pub fn detect(content: &str) -> Self {
+        let crlf = content.matches("\r\n").count();
+        let lf = content.matches('\n').count() - crlf;
+        Self {
+            newline: if crlf > lf { "\r\n" } else { "\n" },
+            trailing_newline: content.trim_end_matches([' ', '\t']).ends_with('\n'),
+        }
+    }
+
+    /// Rewrite `content` to use this line-ending style
+    pub fn apply(&self, content: &str) -> String {
+        let normalized = content.replace("\r\n", "\n");
+        let body = normalized.trim_end_matches('\n');
+        let mut result = if self.newline == "\n" {
+            body.to_string()
+        } else {
+            body.replace('\n', self.newline)
+        };
+        if self.trailing_newline {
+            result.push_str(self.newline);
+        }
+        result
+    }
+}
+
+/// Give `repaired` the line endings and final newline of `original`.
+pub fn restore_line_endings(original: &str, repaired: &str) -> String {
+    LineEndings::detect(original).apply(repaired)
+}
+
+/// Rewrite a full-line comment that starts with `from` markers to use `to` instead,
+/// keeping the indentation and the number of markers (`;; note` -> `## note`).
+/// Returns `None` if the line is not such a comment.
+pub(crate) fn swap_comment_marker(line: &str, from: char, to: char) -> Option<String> {
+    let body = line.trim_start();
+    let markers = body.chars().take_while(|&c| c == from).count();
+    if markers == 0 {
+        return None;
+    }
+    let indent = &line[..line.len() - body.len()];
+    Some(format!(
+        "{}{}{}",
+        indent,
+        to.to_string().repeat(markers),
+        &body[markers * from.len_utf8()..]
+    ))
+}
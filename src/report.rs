@@ -0,0 +1,124 @@
+//! Detailed repair results
+
+use crate::error::{RepairError, Result};
+use std::ops::Range;
+
+/// Outcome of a repair: the repaired content, the strategies that produced it,
+/// and the repairer's confidence in the content before and after
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairReport {
+    /// Repaired content (identical to the input if nothing needed fixing)
+    pub repaired: String,
+    /// Names of the strategies that changed the content, in application order
+    pub applied_strategies: Vec<String>,
+    /// Confidence (0.0–1.0) that the input was already well-formed
+    pub confidence_before: f64,
+    /// Confidence (0.0–1.0) that the repaired content is well-formed
+    pub confidence_after: f64,
+    /// Content strategies deleted outright (comments, prose, trailing junk), in
+    /// application order
+    pub removed: Vec<RemovedSpan>,
+}
+
+/// Text a strategy deleted, as listed in [`RepairReport::removed`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedSpan {
+    /// Byte range of the removed text in the original input
+    pub byte_range: Range<usize>,
+    /// The removed text
+    pub text: String,
+    /// What kind of content it was (`comment`, `prose`, `trailing content`)
+    pub reason: String,
+}
+
+/// Comment or whitespace that repair stripped from the input, as returned by
+/// [`crate::json::JsonRepairer::repair_with_trivia`] so it can be reattached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia {
+    /// Byte offset of the text in the original input
+    pub byte_offset: usize,
+    /// Whether the text was a comment or whitespace
+    pub kind: TriviaKind,
+    /// The stripped text, including comment markers
+    pub text: String,
+}
+
+/// What a [`Trivia`] entry holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    /// A `//` or `/* */` comment
+    Comment,
+    /// Leading or trailing whitespace around the document
+    Whitespace,
+}
+
+/// A repair strategy as listed by [`crate::list_strategies`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyInfo {
+    /// Name reported in [`RepairReport::applied_strategies`]
+    pub name: String,
+    /// Higher priorities run first
+    pub priority: u8,
+    /// One-line summary of what the strategy fixes
+    pub description: String,
+    /// Sample input and what the strategy alone turns it into
+    pub example: Option<(String, String)>,
+}
+
+impl RepairReport {
+    /// Whether any strategy changed the content
+    pub fn changed(&self) -> bool {
+        !self.applied_strategies.is_empty()
+    }
+
+    /// Whether the repair lowered confidence, i.e. made the content look worse
+    pub fn degraded(&self) -> bool {
+        self.confidence_after < self.confidence_before
+    }
+}
+
+/// What a repairer does when its output scores lower confidence than its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SafetyMode {
+    /// Return the repaired output regardless (the default)
+    #[default]
+    Allow,
+    /// Return the original input unchanged instead of the worse output
+    RejectIfWorse,
+    /// Fail with `RepairError::ConfidenceDropped`
+    ErrorIfWorse,
+}
+
+impl SafetyMode {
+    /// Apply this mode to `report`, a repair of `original`
+    pub(crate) fn check(self, original: &str, report: RepairReport) -> Result<RepairReport> {
+        if !report.degraded() {
+            return Ok(report);
+        }
+        match self {
+            SafetyMode::Allow => Ok(report),
+            SafetyMode::RejectIfWorse => Ok(RepairReport {
+                repaired: original.to_string(),
+                applied_strategies: Vec::new(),
+                confidence_before: report.confidence_before,
+                confidence_after: report.confidence_before,
+                removed: Vec::new(),
+            }),
+            SafetyMode::ErrorIfWorse => Err(RepairError::ConfidenceDropped {
+                before: report.confidence_before,
+                after: report.confidence_after,
+            }),
+        }
+    }
+}
+
+/// Whether content is usable as-is, usable after repair, or beyond repair
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Assessment {
+    /// Already passes validation
+    Valid,
+    /// Passes validation once repaired; `confidence` is the repairer's score for the result
+    Repairable { confidence: f64 },
+    /// Still fails validation after repair
+    Unrepairable,
+}
@@ -0,0 +1,449 @@
+//! JSON Schema validation of repaired output
+//!
+//! A std-only subset of JSON Schema covering what is typically used to describe
+//! LLM output: `type`, `enum`, `const`, `required`, `properties`,
+//! `additionalProperties`, `items`, `minItems`/`maxItems`, `minimum`/`maximum`
+//! (and their exclusive forms), `minLength`/`maxLength`, `pattern`, and
+//! `allOf`/`anyOf`/`oneOf`/`not`. A schema using a keyword that would narrow
+//! what conforms outside this subset, such as `$ref`, `if` or
+//! `patternProperties`, is rejected with `RepairError::Schema` rather than
+//! passing every document. Annotations such as `title` and `format` are ignored.
+
+use crate::convert::{Value, parse_json};
+use crate::error::{RepairError, Result};
+use regex::Regex;
+
+/// Validate the `repaired` JSON document against a JSON `schema`.
+///
+/// Returns one message per violation, each prefixed with the JSON Pointer of the
+/// offending value (`/` for the root); an empty list means the document conforms.
+/// Returns `RepairError::Schema` if either input is not valid JSON or the schema
+/// is malformed or uses an unsupported keyword, and `RepairError::MaxDepthExceeded` if either nests more than
+/// 1024 levels deep.
+pub fn validate_schema(repaired: &str, schema: &str) -> Result<Vec<String>> {
+    let document = parse(repaired, "repaired output")?;
+    let schema = parse(schema, "schema")?;
+    check_supported(&schema)?;
+    let mut violations = Vec::new();
+    check(&document, &schema, "", &mut violations)?;
+    Ok(violations)
+}
+
+fn parse(s: &str, what: &str) -> Result<Value> {
+    parse_json(s).map_err(|e| match e {
+        RepairError::Conversion(message) => {
+            RepairError::Schema(format!("{} is not valid JSON: {}", what, message))
+        }
+        other => other,
+    })
+}
+
+/// Validation keywords outside the supported subset
+const UNSUPPORTED_KEYWORDS: [&str; 18] = [
+    "$ref",
+    "$dynamicRef",
+    "$recursiveRef",
+    "if",
+    "dependencies",
+    "dependentRequired",
+    "dependentSchemas",
+    "patternProperties",
+    "propertyNames",
+    "prefixItems",
+    "additionalItems",
+    "contains",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    "uniqueItems",
+    "minProperties",
+    "maxProperties",
+    "multipleOf",
+];
+
+/// Reject `schema` if it or a subschema uses one of [`UNSUPPORTED_KEYWORDS`], even
+/// where no value reaches it, so support does not depend on the document
+fn check_supported(schema: &Value) -> Result<()> {
+    let Value::Object(keywords) = schema else {
+        return Ok(());
+    };
+    for (keyword, arg) in keywords {
+        if UNSUPPORTED_KEYWORDS.contains(&keyword.as_str()) {
+            return Err(RepairError::Schema(format!(
+                "unsupported keyword {}",
+                keyword
+            )));
+        }
+        match (keyword.as_str(), arg) {
+            ("properties", Value::Object(properties)) => {
+                for (_, property) in properties {
+                    check_supported(property)?;
+                }
+            }
+            ("allOf" | "anyOf" | "oneOf", Value::Array(subschemas)) => {
+                for subschema in subschemas {
+                    check_supported(subschema)?;
+                }
+            }
+            ("additionalProperties" | "items" | "not", subschema) => check_supported(subschema)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Append the violations of `value` (found at `path`) against `schema` to `out`
+fn check(value: &Value, schema: &Value, path: &str, out: &mut Vec<String>) -> Result<()> {
+    let keywords = match schema {
+        Value::Bool(true) => return Ok(()),
+        Value::Bool(false) => {
+            out.push(violation(path, "no value is allowed here"));
+            return Ok(());
+        }
+        Value::Object(keywords) => keywords,
+        _ => return Err(malformed("schema", "an object or boolean")),
+    };
+
+    for (keyword, arg) in keywords {
+        match keyword.as_str() {
+            "type" => {
+                let allowed = match arg {
+                    Value::String(name) => vec![name.as_str()],
+                    Value::Array(names) => names
+                        .iter()
+                        .map(|name| as_str(name, keyword))
+                        .collect::<Result<_>>()?,
+                    _ => return Err(malformed(keyword, "a string or array")),
+                };
+                if !allowed.iter().any(|name| has_type(value, name)) {
+                    out.push(violation(
+                        path,
+                        &format!(
+                            "expected type {}, found {}",
+                            allowed.join(" or "),
+                            type_name(value)
+                        ),
+                    ));
+                }
+            }
+            "enum" => {
+                let options = as_array(arg, keyword)?;
+                if !options.iter().any(|v| json_eq(v, value)) {
+                    out.push(violation(path, "value is not one of the allowed values"));
+                }
+            }
+            "const" if !json_eq(arg, value) => {
+                out.push(violation(
+                    path,
+                    "value does not equal the required constant",
+                ));
+            }
+            "required" => {
+                if let Value::Object(entries) = value {
+                    for name in as_array(arg, keyword)? {
+                        let name = as_str(name, keyword)?;
+                        if !entries.iter().any(|(key, _)| key == name) {
+                            out.push(violation(
+                                path,
+                                &format!("missing required property \"{}\"", name),
+                            ));
+                        }
+                    }
+                }
+            }
+            "properties" => {
+                if let Value::Object(entries) = value {
+                    for (name, property) in as_object(arg, keyword)? {
+                        if let Some((_, v)) = entries.iter().find(|(key, _)| key == name) {
+                            check(v, property, &child(path, name), out)?;
+                        }
+                    }
+                }
+            }
+            "additionalProperties" => {
+                if let Value::Object(entries) = value {
+                    let declared = match keywords.iter().find(|(k, _)| k == "properties") {
+                        Some((k, properties)) => as_object(properties, k)?,
+                        None => &[],
+                    };
+                    for (key, v) in entries {
+                        if declared.iter().any(|(name, _)| name == key) {
+                            continue;
+                        }
+                        if *arg == Value::Bool(false) {
+                            out.push(violation(&child(path, key), "property is not allowed"));
+                        } else {
+                            check(v, arg, &child(path, key), out)?;
+                        }
+                    }
+                }
+            }
+            "items" => {
+                if let Value::Array(items) = value {
+                    for (i, item) in items.iter().enumerate() {
+                        check(item, arg, &child(path, &i.to_string()), out)?;
+                    }
+                }
+            }
+            "minItems" | "maxItems" => {
+                if let Value::Array(items) = value {
+                    check_count(items.len(), keyword, arg, "item(s)", path, out)?;
+                }
+            }
+            "minLength" | "maxLength" => {
+                if let Value::String(s) = value {
+                    check_count(s.chars().count(), keyword, arg, "character(s)", path, out)?;
+                }
+            }
+            "minimum" | "maximum" | "exclusiveMinimum" | "exclusiveMaximum" => {
+                if let Some(n) = as_f64(value) {
+                    let limit = as_f64(arg).ok_or_else(|| malformed(keyword, "a number"))?;
+                    let (ok, relation) = match keyword.as_str() {
+                        "minimum" => (n >= limit, ">="),
+                        "maximum" => (n <= limit, "<="),
+                        "exclusiveMinimum" => (n > limit, ">"),
+                        _ => (n < limit, "<"),
+                    };
+                    if !ok {
+                        out.push(violation(
+                            path,
+                            &format!("{} is not {} {}", n, relation, limit),
+                        ));
+                    }
+                }
+            }
+            "pattern" => {
+                if let Value::String(s) = value {
+                    let pattern = as_str(arg, keyword)?;
+                    if !Regex::new(pattern)?.is_match(s) {
+                        out.push(violation(
+                            path,
+                            &format!("\"{}\" does not match pattern {}", s, pattern),
+                        ));
+                    }
+                }
+            }
+            "allOf" => {
+                for subschema in as_array(arg, keyword)? {
+                    check(value, subschema, path, out)?;
+                }
+            }
+            "anyOf" | "oneOf" => {
+                let mut matched = 0;
+                for subschema in as_array(arg, keyword)? {
+                    if conforms(value, subschema, path)? {
+                        matched += 1;
+                    }
+                }
+                if keyword == "anyOf" && matched == 0 {
+                    out.push(violation(path, "value does not match any schema in anyOf"));
+                } else if keyword == "oneOf" && matched != 1 {
+                    out.push(violation(
+                        path,
+                        &format!("value matches {} schemas in oneOf, expected 1", matched),
+                    ));
+                }
+            }
+            "not" => {
+                let matched = conforms(value, arg, path)?;
+                if matched {
+                    out.push(violation(path, "value matches the schema in not"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn conforms(value: &Value, schema: &Value, path: &str) -> Result<bool> {
+    let mut violations = Vec::new();
+    check(value, schema, path, &mut violations)?;
+    Ok(violations.is_empty())
+}
+
+/// Check a `min*`/`max*` keyword against a length or item count
+fn check_count(
+    actual: usize,
+    keyword: &str,
+    arg: &Value,
+    unit: &str,
+    path: &str,
+    out: &mut Vec<String>,
+) -> Result<()> {
+    let limit = as_f64(arg).ok_or_else(|| malformed(keyword, "a number"))?;
+    let actual_f64 = actual as f64;
+    let (ok, bound) = if keyword.starts_with("min") {
+        (actual_f64 >= limit, "at least")
+    } else {
+        (actual_f64 <= limit, "at most")
+    };
+    if !ok {
+        out.push(violation(
+            path,
+            &format!("expected {} {} {}, found {}", bound, limit, unit, actual),
+        ));
+    }
+    Ok(())
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => as_f64(value).is_some_and(|n| n.fract() == 0.0),
+        _ => type_name(value) == name || (name == "number" && type_name(value) == "integer"),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) if as_f64(value).is_some_and(|n| n.fract() == 0.0) => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Structural equality, comparing numbers by value (`1` equals `1.0`)
+/// and objects regardless of key order
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(_), Value::Number(_)) => as_f64(a) == as_f64(b),
+        (Value::Array(x), Value::Array(y)) => {
+            x.len() == y.len() && x.iter().zip(y).all(|(x, y)| json_eq(x, y))
+        }
+        (Value::Object(x), Value::Object(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|(key, xv)| y.iter().any(|(other, yv)| key == other && json_eq(xv, yv)))
+        }
+        _ => a == b,
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.parse().ok(),
+        _ => None,
+    }
+}
+
+fn as_str<'a>(value: &'a Value, keyword: &str) -> Result<&'a str> {
+    match value {
+        Value::String(s) => Ok(s),
+        _ => Err(malformed(keyword, "a string")),
+    }
+}
+
+fn as_array<'a>(value: &'a Value, keyword: &str) -> Result<&'a [Value]> {
+    match value {
+        Value::Array(items) => Ok(items),
+        _ => Err(malformed(keyword, "an array")),
+    }
+}
+
+fn as_object<'a>(value: &'a Value, keyword: &str) -> Result<&'a [(String, Value)]> {
+    match value {
+        Value::Object(entries) => Ok(entries),
+        _ => Err(malformed(keyword, "an object")),
+    }
+}
+
+fn malformed(keyword: &str, expected: &str) -> RepairError {
+    RepairError::Schema(format!("\"{}\" must be {}", keyword, expected))
+}
+
+/// JSON Pointer to the member `name` of the value at `path`
+fn child(path: &str, name: &str) -> String {
+    format!("{}/{}", path, name.replace('~', "~0").replace('/', "~1"))
+}
+
+fn violation(path: &str, message: &str) -> String {
+    format!("{}: {}", if path.is_empty() { "/" } else { path }, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PERSON: &str = r#"{
+        "type": "object",
+        "required": ["name"],
+        "properties": {
+            "name": {"type": "string", "minLength": 1},
+            "age": {"type": "integer", "minimum": 0},
+            "tags": {"type": "array", "items": {"enum": ["a", "b"]}}
+        },
+        "additionalProperties": false
+    }"#;
+
+    #[test]
+    fn test_repaired_document_with_required_field_passes() {
+        let repaired = crate::jsonrepair(r#"{name: 'Ann', "age": 30, tags: ["a",],}"#).unwrap();
+        assert_eq!(
+            validate_schema(&repaired, PERSON).unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_missing_required_field_reported() {
+        let repaired = crate::jsonrepair(r#"{"age": -1, "tags": ["c"], "x/y": 1,}"#).unwrap();
+        assert_eq!(
+            validate_schema(&repaired, PERSON).unwrap(),
+            vec![
+                "/: missing required property \"name\"",
+                "/age: -1 is not >= 0",
+                "/tags/0: value is not one of the allowed values",
+                "/x~1y: property is not allowed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_and_combinators() {
+        let schema = r#"{"anyOf": [{"type": "string"}, {"type": ["integer", "null"]}]}"#;
+        assert!(validate_schema("null", schema).unwrap().is_empty());
+        assert!(validate_schema("3.0", schema).unwrap().is_empty());
+        assert_eq!(
+            validate_schema("3.5", schema).unwrap(),
+            vec!["/: value does not match any schema in anyOf"]
+        );
+        assert_eq!(
+            validate_schema(r#"{"a": 1}"#, r#"{"const": {"a": 1.0}}"#).unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(matches!(
+            validate_schema("{", "{}"),
+            Err(RepairError::Schema(_))
+        ));
+        assert!(matches!(
+            validate_schema("{}", r#"{"required": "name"}"#),
+            Err(RepairError::Schema(_))
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_keywords_rejected() {
+        let schema = r##"{"$ref": "#/$defs/person", "$defs": {"person": {"required": ["name"]}}}"##;
+        assert_eq!(
+            validate_schema("{}", schema).unwrap_err().to_string(),
+            "Schema validation failed: unsupported keyword $ref"
+        );
+        // Also inside a subschema that no value reaches
+        let nested = r#"{"items": {"if": {"type": "string"}, "then": {"minLength": 2}}}"#;
+        assert!(matches!(
+            validate_schema("[]", nested),
+            Err(RepairError::Schema(_))
+        ));
+        // Annotations and unknown keywords are still ignored
+        let annotated = r#"{"title": "T", "format": "email", "x-extra": 1, "type": "string"}"#;
+        assert!(validate_schema(r#""a""#, annotated).unwrap().is_empty());
+    }
+}
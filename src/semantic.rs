@@ -0,0 +1,130 @@
+//! Optional "semantic" fallback for content syntactic repair cannot fix
+//!
+//! anyrepair itself never calls out to a model. Applications that want an LLM
+//! (or any other service) to take a second pass implement
+//! [`SemanticRepairProvider`] and wrap a format's repairer in
+//! [`SemanticRepairer`], which consults the provider only when the syntactic
+//! result still scores below a confidence threshold.
+
+use crate::error::Result;
+use crate::traits::Repair;
+
+/// External repair service consulted after syntactic repair has done its best
+pub trait SemanticRepairProvider: Send + Sync {
+    /// Repair `content` (already syntactically repaired) as `format`
+    fn repair(&self, content: &str, format: &str) -> Result<String>;
+}
+
+/// Default confidence below which [`SemanticRepairer`] calls its provider
+pub const DEFAULT_SEMANTIC_THRESHOLD: f64 = 0.9;
+
+/// Repairer that runs a format's syntactic repair and falls back to a
+/// [`SemanticRepairProvider`] when confidence stays low
+pub struct SemanticRepairer {
+    inner: Box<dyn Repair>,
+    format: String,
+    provider: Box<dyn SemanticRepairProvider>,
+    threshold: f64,
+}
+
+impl SemanticRepairer {
+    /// Wrap the built-in (or registered) repairer for `format`.
+    /// Returns `RepairError::FormatDetection` if the format is unknown.
+    pub fn new(format: &str, provider: Box<dyn SemanticRepairProvider>) -> Result<Self> {
+        Ok(Self {
+            inner: crate::create_repairer(format)?,
+            format: crate::normalize_format(format).to_string(),
+            provider,
+            threshold: DEFAULT_SEMANTIC_THRESHOLD,
+        })
+    }
+
+    /// Wrap the repairer for `format`, consulting the provider below `threshold` (0.0–1.0)
+    pub fn with_threshold(
+        format: &str,
+        provider: Box<dyn SemanticRepairProvider>,
+        threshold: f64,
+    ) -> Result<Self> {
+        Ok(Self {
+            threshold,
+            ..Self::new(format, provider)?
+        })
+    }
+}
+
+impl Repair for SemanticRepairer {
+    fn repair(&mut self, content: &str) -> Result<String> {
+        let repaired = self.inner.repair(content)?;
+        if self.inner.confidence(&repaired) >= self.threshold {
+            return Ok(repaired);
+        }
+
+        // Tidy the provider's answer with the same syntactic pass
+        let suggestion = self.provider.repair(&repaired, &self.format)?;
+        self.inner.repair(&suggestion)
+    }
+
+    fn needs_repair(&self, content: &str) -> bool {
+        self.inner.needs_repair(content)
+    }
+
+    fn confidence(&self, content: &str) -> f64 {
+        self.inner.confidence(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Provider that records its calls and answers with a fixed document
+    struct MockProvider {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl SemanticRepairProvider for MockProvider {
+        fn repair(&self, _content: &str, format: &str) -> Result<String> {
+            assert_eq!(format, "json");
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(r#"{"recovered": true,}"#.to_string())
+        }
+    }
+
+    fn repairer() -> (SemanticRepairer, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = MockProvider {
+            calls: Arc::clone(&calls),
+        };
+        (
+            SemanticRepairer::new("JSON", Box::new(provider)).unwrap(),
+            calls,
+        )
+    }
+
+    #[test]
+    fn test_provider_skipped_when_syntactic_repair_suffices() {
+        let (mut repairer, calls) = repairer();
+        assert_eq!(repairer.repair(r#"{"a": 1,}"#).unwrap(), r#"{"a": 1}"#);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_provider_invoked_on_low_confidence() {
+        let (mut repairer, calls) = repairer();
+        assert_eq!(
+            repairer.repair("@@@ no data here").unwrap(),
+            r#"{"recovered": true}"#
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_unknown_format() {
+        let provider = MockProvider {
+            calls: Arc::new(AtomicUsize::new(0)),
+        };
+        assert!(SemanticRepairer::new("nope", Box::new(provider)).is_err());
+    }
+}
@@ -3,8 +3,13 @@
 //! This module provides streaming repair capabilities for processing large files
 //! without loading entire content into memory.
 
-use crate::error::Result;
-use std::io::{BufRead, Write};
+use crate::error::{RepairError, Result};
+use crate::json::JsonRepairer;
+use crate::traits::Repair;
+use std::io::{BufRead, Cursor, Read, Write};
+
+/// Number of leading bytes sampled to detect the format of an `auto` stream
+pub const DETECTION_SAMPLE_SIZE: usize = 4096;
 
 /// Streaming repair processor for large files
 pub struct StreamingRepair {
@@ -24,47 +29,172 @@ impl StreamingRepair {
         Self { buffer_size }
     }
 
+    /// Detect the format of a stream from its first [`DETECTION_SAMPLE_SIZE`] bytes.
+    ///
+    /// Returns the detected format (if any) together with a reader that yields the
+    /// whole stream again, sampled bytes included.
+    pub fn detect_format<R: BufRead>(
+        mut reader: R,
+    ) -> Result<(Option<&'static str>, impl BufRead)> {
+        let mut sample = Vec::with_capacity(DETECTION_SAMPLE_SIZE);
+        (&mut reader)
+            .take(DETECTION_SAMPLE_SIZE as u64)
+            .read_to_end(&mut sample)
+            .map_err(|e| RepairError::Generic(format!("IO error: {}", e)))?;
+
+        // The sample may end in the middle of a multi-byte character
+        let text = match std::str::from_utf8(&sample) {
+            Ok(text) => text,
+            Err(e) => std::str::from_utf8(&sample[..e.valid_up_to()]).unwrap_or_default(),
+        };
+        let format = crate::detect_format(text);
+        Ok((format, Cursor::new(sample).chain(reader)))
+    }
+
     /// Process a reader and write repaired content to writer
     /// Returns number of bytes processed
+    ///
+    /// With format `auto` the format is detected from the leading bytes. JSON
+    /// streams are only split between top-level values, so each chunk handed to
+    /// the repairer is a complete document (one per line for NDJSON).
     pub fn process<R: BufRead, W: Write>(
         &self,
         reader: R,
         writer: &mut W,
         format: &str,
+    ) -> Result<usize> {
+        let normalized = crate::normalize_format(format);
+        if normalized == "auto" {
+            let (detected, reader) = Self::detect_format(reader)?;
+            return self.process_format(reader, writer, detected.unwrap_or("auto"));
+        }
+        self.process_format(reader, writer, normalized)
+    }
+
+    fn process_format<R: BufRead, W: Write>(
+        &self,
+        reader: R,
+        writer: &mut W,
+        format: &str,
     ) -> Result<usize> {
         let mut total_bytes = 0;
         let mut buffer = String::with_capacity(self.buffer_size);
+        let mut boundary = (format == "json").then(JsonBoundary::default);
+        // Whether a quoted CSV field is open, so a chunk never splits a record
+        let mut csv_in_quotes = false;
 
         for line_result in reader.lines() {
-            let line = line_result
-                .map_err(|e| crate::error::RepairError::Generic(format!("IO error: {}", e)))?;
+            let line = line_result.map_err(|e| RepairError::Generic(format!("IO error: {}", e)))?;
 
             buffer.push_str(&line);
             buffer.push('\n');
 
-            // Process buffer when it reaches size threshold
-            if buffer.len() >= self.buffer_size {
-                let repaired = self.repair_chunk(&buffer, format)?;
-                writer.write_all(repaired.as_bytes()).map_err(|e| {
-                    crate::error::RepairError::Generic(format!("Write error: {}", e))
-                })?;
-                total_bytes += repaired.len();
+            // Process buffer when it reaches size threshold, or for JSON
+            // whenever a top-level value is complete
+            let flush = match boundary.as_mut() {
+                Some(boundary) => boundary.feed_line(&line),
+                None if format == "csv" => {
+                    csv_in_quotes = crate::csv::quote_state_after(&line, csv_in_quotes).0;
+                    !csv_in_quotes && buffer.len() >= self.buffer_size
+                }
+                None => buffer.len() >= self.buffer_size,
+            };
+            if flush {
+                total_bytes += self.write_chunk(&buffer, format, writer)?;
                 buffer.clear();
             }
         }
 
         // Process remaining buffer
         if !buffer.is_empty() {
-            let repaired = self.repair_chunk(&buffer, format)?;
-            writer
-                .write_all(repaired.as_bytes())
-                .map_err(|e| crate::error::RepairError::Generic(format!("Write error: {}", e)))?;
-            total_bytes += repaired.len();
+            total_bytes += self.write_chunk(&buffer, format, writer)?;
         }
 
         Ok(total_bytes)
     }
 
+    /// Repair a single top-level JSON array one element at a time.
+    ///
+    /// Writes `[`, each repaired element separated by `, `, and `]`, holding no
+    /// more than one element in memory, so arrays far larger than RAM can be
+    /// repaired. Empty elements (trailing or doubled commas) are dropped, a
+    /// truncated array is closed, and anything after the closing `]` is ignored.
+    /// Returns the number of bytes written, or an error if the stream does not
+    /// start with `[`.
+    pub fn process_json_array<R: BufRead, W: Write>(
+        &self,
+        mut reader: R,
+        writer: &mut W,
+    ) -> Result<usize> {
+        let mut repairer = JsonRepairer::new();
+        let mut element = Vec::with_capacity(self.buffer_size);
+        let mut scanner = JsonArrayScanner::default();
+        let mut started = false;
+        let mut closed = false;
+        let mut elements = 0;
+        let mut total_bytes = 0;
+
+        while !closed {
+            let buf = reader
+                .fill_buf()
+                .map_err(|e| RepairError::Generic(format!("IO error: {}", e)))?;
+            if buf.is_empty() {
+                break;
+            }
+            let mut used = buf.len();
+            for (i, &b) in buf.iter().enumerate() {
+                if !started {
+                    match b {
+                        b'[' => {
+                            total_bytes += write_bytes(writer, b"[")?;
+                            started = true;
+                        }
+                        _ if b.is_ascii_whitespace() => {}
+                        _ => {
+                            return Err(RepairError::Generic(
+                                "Expected a top-level JSON array".to_string(),
+                            ));
+                        }
+                    }
+                    continue;
+                }
+                match scanner.feed(b) {
+                    ArrayByte::Element => element.push(b),
+                    ArrayByte::Separator => {
+                        total_bytes += write_element(&mut repairer, &element, elements, writer)?;
+                        elements += usize::from(!is_blank(&element));
+                        element.clear();
+                    }
+                    ArrayByte::End => {
+                        closed = true;
+                        used = i + 1;
+                        break;
+                    }
+                }
+            }
+            reader.consume(used);
+        }
+
+        if !started {
+            return Ok(0);
+        }
+        total_bytes += write_element(&mut repairer, &element, elements, writer)?;
+        Ok(total_bytes + write_bytes(writer, b"]")?)
+    }
+
+    fn write_chunk<W: Write>(&self, chunk: &str, format: &str, writer: &mut W) -> Result<usize> {
+        // Blank lines between JSON values have nothing to repair
+        let repaired = if chunk.trim().is_empty() {
+            chunk.to_string()
+        } else {
+            self.repair_chunk(chunk, format)?
+        };
+        writer
+            .write_all(repaired.as_bytes())
+            .map_err(|e| RepairError::Generic(format!("Write error: {}", e)))?;
+        Ok(repaired.len())
+    }
+
     /// Repair a chunk of content
     fn repair_chunk(&self, chunk: &str, format: &str) -> Result<String> {
         let normalized = crate::normalize_format(format);
@@ -76,6 +206,109 @@ impl StreamingRepair {
     }
 }
 
+/// Tracks JSON nesting across lines to find the ends of top-level values
+#[derive(Default)]
+struct JsonBoundary {
+    depth: usize,
+}
+
+impl JsonBoundary {
+    /// Account for `line`; returns true when it ends outside any container
+    fn feed_line(&mut self, line: &str) -> bool {
+        // JSON strings cannot span lines, so an unterminated quote ends here
+        let mut in_string = false;
+        let mut escaped = false;
+        for b in line.bytes() {
+            if in_string {
+                match b {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match b {
+                b'"' => in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        self.depth == 0
+    }
+}
+
+/// What a byte inside a top-level JSON array is to [`StreamingRepair::process_json_array`]
+enum ArrayByte {
+    /// Part of the current element
+    Element,
+    /// A comma between elements
+    Separator,
+    /// The `]` closing the array
+    End,
+}
+
+/// Tracks nesting and strings within one element of a top-level JSON array
+#[derive(Default)]
+struct JsonArrayScanner {
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl JsonArrayScanner {
+    fn feed(&mut self, b: u8) -> ArrayByte {
+        if self.in_string {
+            match b {
+                _ if self.escaped => self.escaped = false,
+                b'\\' => self.escaped = true,
+                b'"' => self.in_string = false,
+                _ => {}
+            }
+            return ArrayByte::Element;
+        }
+        match b {
+            b'"' => self.in_string = true,
+            b'{' | b'[' => self.depth += 1,
+            b',' if self.depth == 0 => return ArrayByte::Separator,
+            b']' if self.depth == 0 => return ArrayByte::End,
+            b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+            _ => {}
+        }
+        ArrayByte::Element
+    }
+}
+
+/// Repair and write one array element, preceded by a separator unless it is
+/// the first; blank elements are skipped
+fn write_element<W: Write>(
+    repairer: &mut JsonRepairer,
+    element: &[u8],
+    index: usize,
+    writer: &mut W,
+) -> Result<usize> {
+    if is_blank(element) {
+        return Ok(0);
+    }
+    let text = std::str::from_utf8(element)
+        .map_err(|e| RepairError::Generic(format!("Invalid UTF-8: {}", e)))?;
+    let repaired = repairer.repair(text.trim())?;
+    let separator: &[u8] = if index == 0 { b"" } else { b", " };
+    Ok(write_bytes(writer, separator)? + write_bytes(writer, repaired.as_bytes())?)
+}
+
+fn is_blank(bytes: &[u8]) -> bool {
+    bytes.iter().all(u8::is_ascii_whitespace)
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<usize> {
+    writer
+        .write_all(bytes)
+        .map_err(|e| RepairError::Generic(format!("Write error: {}", e)))?;
+    Ok(bytes.len())
+}
+
 impl Default for StreamingRepair {
     fn default() -> Self {
         Self::new()
@@ -179,6 +412,78 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_streaming_json_chunks_at_value_boundaries() {
+        // Values longer than the buffer are never split, and NDJSON lines are
+        // repaired one by one
+        let input = "{\"a\": [1,\n2,\n3,],\n\"b\": \"x\",}\n\n{'c': 1}\n[true,]\n";
+
+        let mut output = Vec::new();
+        let processor = StreamingRepair::with_buffer_size(4);
+        processor
+            .process(Cursor::new(input), &mut output, "auto")
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"a\": [1,\n2,\n3],\n\"b\": \"x\"}\n\n{\"c\": 1}\n[true]\n"
+        );
+    }
+
+    #[test]
+    fn test_json_array_repaired_element_by_element() {
+        let count = 10_000;
+        let mut input = String::from("  [\n");
+        for i in 0..count {
+            input.push_str(&format!(
+                "  {{\"id\": {i}, \"name\": \"item, [{i}]\", \"tags\": [\"a\",],}},\n"
+            ));
+        }
+        input.push_str("]\ntrailing prose");
+
+        // A tiny read buffer splits elements across reads
+        let reader = std::io::BufReader::with_capacity(7, Cursor::new(input));
+        let mut output = Vec::new();
+        let written = StreamingRepair::new()
+            .process_json_array(reader, &mut output)
+            .unwrap();
+
+        assert_eq!(written, output.len());
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with(r#"[{"id": 0, "name": "item, [0]", "tags": ["a"]}, {"id": 1"#));
+        match crate::convert::parse_json(&output).unwrap() {
+            crate::convert::Value::Array(items) => assert_eq!(items.len(), count),
+            other => panic!("expected an array, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_array_truncated_and_invalid() {
+        let mut output = Vec::new();
+        StreamingRepair::new()
+            .process_json_array(Cursor::new("[1, 2,, {\"a\": 3"), &mut output)
+            .unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), r#"[1, 2, {"a": 3}]"#);
+
+        let mut output = Vec::new();
+        assert!(
+            StreamingRepair::new()
+                .process_json_array(Cursor::new("{\"a\": 1}"), &mut output)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_detect_format_replays_sample() {
+        let input = format!("[{}1]", "1, ".repeat(DETECTION_SAMPLE_SIZE));
+        let (format, mut reader) = StreamingRepair::detect_format(Cursor::new(&input)).unwrap();
+        assert_eq!(format, Some("json"));
+
+        let mut replayed = String::new();
+        reader.read_to_string(&mut replayed).unwrap();
+        assert_eq!(replayed, input);
+    }
+
     #[test]
     fn test_streaming_empty_input() {
         let input = "";
@@ -1,8 +1,10 @@
 //! TOML repair module
 
 use crate::error::Result;
+use crate::repairer_base::{BaseOptions, DEFAULT_MAX_DEPTH, swap_comment_marker};
 use crate::traits::{Repair, RepairStrategy, Validator};
 use regex::Regex;
+use std::borrow::Cow;
 use std::sync::OnceLock;
 
 /// Cached regex patterns for TOML performance optimization
@@ -53,6 +55,7 @@ impl TomlRepairer {
     /// Create a new TOML repairer
     pub fn new() -> Self {
         let strategies: Vec<Box<dyn RepairStrategy>> = vec![
+            Box::new(QuoteInvalidKeysStrategy),
             Box::new(FixMissingQuotesStrategy),
             Box::new(FixMalformedArraysStrategy),
             Box::new(FixMalformedTablesStrategy),
@@ -60,13 +63,47 @@ impl TomlRepairer {
             Box::new(FixMalformedNumbersStrategy),
             Box::new(FixMalformedDatesStrategy),
             Box::new(AddTableHeadersStrategy),
+            Box::new(ConvertSemicolonCommentsStrategy),
         ];
 
         let validator: Box<dyn Validator> = Box::new(TomlValidator);
-        let inner = crate::repairer_base::GenericRepairer::new(validator, strategies);
+        let mut inner = crate::repairer_base::GenericRepairer::new(validator, strategies);
+        inner.set_max_depth(Some(DEFAULT_MAX_DEPTH));
 
         Self { inner }
     }
+
+    /// Start configuring a TOML repairer
+    pub fn builder() -> TomlRepairerBuilder {
+        TomlRepairerBuilder::default()
+    }
+}
+
+/// Builder combining any of the options of [`TomlRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct TomlRepairerBuilder {
+    base: BaseOptions,
+}
+
+impl TomlRepairerBuilder {
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Create the configured TOML repairer
+    pub fn build(self) -> TomlRepairer {
+        let mut repairer = TomlRepairer::new();
+        repairer.inner.apply_options(self.base);
+        repairer
+    }
 }
 
 impl Default for TomlRepairer {
@@ -80,44 +117,20 @@ impl Repair for TomlRepairer {
         self.inner.repair(content)
     }
 
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        self.inner.repair_cow(content)
+    }
+
     fn needs_repair(&self, content: &str) -> bool {
         self.inner.needs_repair(content)
     }
 
     fn confidence(&self, content: &str) -> f64 {
-        if content.trim().is_empty() {
-            return 0.0;
-        }
-
-        // Calculate confidence based on TOML-like patterns
-        let mut score: f64 = 0.0;
-
-        // Check for table headers
-        if content.contains('[') && content.contains(']') {
-            score += 0.3;
+        if self.inner.validator().is_valid(content) {
+            return 1.0;
         }
-
-        // Check for key-value pairs
-        if content.contains('=') {
-            score += 0.3;
-        }
-
-        // Check for arrays
-        if content.contains('[') && content.contains(',') {
-            score += 0.2;
-        }
-
-        // Check for strings
-        if content.contains('"') || content.contains("'") {
-            score += 0.1;
-        }
-
-        // Check for numbers
-        if content.chars().any(|c| c.is_ascii_digit()) {
-            score += 0.1;
-        }
-
-        score.min(1.0)
+        // Graded by the share of well-formed lines, never reaching a valid score
+        0.9 * toml_completeness(content)
     }
 }
 
@@ -146,32 +159,205 @@ fn toml_structure_valid(content: &str) -> bool {
         return false;
     }
 
-    for line in content.lines() {
+    lines_outside_multiline_strings(content).all(|(outside, line)| {
         let trimmed = line.trim();
         if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('[') {
-            continue;
+            return true;
         }
 
-        if trimmed.contains('=') {
-            let parts: Vec<&str> = trimmed.splitn(2, '=').collect();
-            if parts.len() == 2 {
-                let value = parts[1].trim();
-                if !value.starts_with('"')
-                    && !value.starts_with('\'')
-                    && !value.starts_with('[')
-                    && !value.starts_with('{')
-                    && value.parse::<i64>().is_err()
-                    && value.parse::<f64>().is_err()
-                    && value != "true"
-                    && value != "false"
-                {
-                    return false;
-                }
+        // `;` comments are INI syntax, not TOML
+        if trimmed.starts_with(';') {
+            return false;
+        }
+
+        match split_key_value(trimmed) {
+            Some((key, value)) => {
+                (!outside || toml_key_valid(key)) && toml_value_valid(value.trim())
+            }
+            None => true,
+        }
+    })
+}
+
+/// Iterate over lines, flagging whether each starts outside a `"""`/`'''` string
+fn lines_outside_multiline_strings(content: &str) -> impl Iterator<Item = (bool, &str)> {
+    let mut open_delimiter: Option<&str> = None;
+    content.lines().map(move |line| {
+        let outside = open_delimiter.is_none();
+        for delimiter in ["\"\"\"", "'''"] {
+            if line.matches(delimiter).count() % 2 == 1 {
+                open_delimiter = match open_delimiter {
+                    Some(open) if open == delimiter => None,
+                    None => Some(delimiter),
+                    other => other,
+                };
+            }
+        }
+        (outside, line)
+    })
+}
+
+/// Split a `key = value` line at the first `=` outside a quoted key
+fn split_key_value(line: &str) -> Option<(&str, &str)> {
+    let mut quote: Option<char> = None;
+    for (i, ch) in line.char_indices() {
+        match (quote, ch) {
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(q), _) if ch == q => quote = None,
+            (None, '=') => return Some((&line[..i], &line[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split a dotted key into its segments at the dots outside quotes
+fn key_segments(key: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, ch) in key.char_indices() {
+        match (quote, ch) {
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(q), _) if ch == q => quote = None,
+            (None, '.') => {
+                segments.push(&key[start..i]);
+                start = i + 1;
             }
+            _ => {}
+        }
+    }
+    segments.push(&key[start..]);
+    segments
+}
+
+/// Whether `segment` (already trimmed) is a bare key or a quoted key
+fn key_segment_valid(segment: &str) -> bool {
+    let is_bare = !segment.is_empty()
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let quoted_by = |q: char| {
+        segment.len() >= 2
+            && segment.starts_with(q)
+            && segment.ends_with(q)
+            && !segment[1..segment.len() - 1].contains(q)
+    };
+    is_bare || quoted_by('"') || quoted_by('\'')
+}
+
+/// Whether `key` is a bare, quoted or dotted TOML key (`a.\"b c\".d`)
+fn toml_key_valid(key: &str) -> bool {
+    key_segments(key.trim())
+        .into_iter()
+        .all(|segment| key_segment_valid(segment.trim()))
+}
+
+/// Whether `value` starts like a TOML string, array or inline table, or is a
+/// number or boolean
+fn toml_value_valid(value: &str) -> bool {
+    value.starts_with('"')
+        || value.starts_with('\'')
+        || value.starts_with('[')
+        || value.starts_with('{')
+        || value.parse::<i64>().is_ok()
+        || value.parse::<f64>().is_ok()
+        || value == "true"
+        || value == "false"
+}
+
+/// Share (0.0–1.0) of the non-blank, non-comment lines of `content` that are a
+/// complete table header or a `key = value` pair with a well-formed value
+fn toml_completeness(content: &str) -> f64 {
+    let mut lines = 0usize;
+    let mut good = 0usize;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        lines += 1;
+        let well_formed = if trimmed.starts_with('[') {
+            trimmed.ends_with(']') && trimmed.len() > 2
+        } else {
+            trimmed.split_once('=').is_some_and(|(key, value)| {
+                !key.trim().is_empty() && toml_value_valid(value.trim())
+            })
+        };
+        if well_formed {
+            good += 1;
         }
     }
 
-    true
+    if lines == 0 {
+        0.0
+    } else {
+        good as f64 / lines as f64
+    }
+}
+
+/// Quote the segments of `key` that are not valid bare keys (`my key` ->
+/// `"my key"`, `server.host name` -> `server."host name"`). Returns `None` if
+/// the key is valid, or broken in a way quoting can't fix (e.g. `a..b`).
+fn quote_invalid_key(key: &str) -> Option<String> {
+    let segments = key_segments(key.trim());
+    let fixable = |segment: &str| {
+        !segment.is_empty() && !segment.contains(['"', '\'', '[', ']', '{', '}', ',', '#', '\\'])
+    };
+    let mut changed = false;
+    let mut quoted = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let segment = segment.trim();
+        if key_segment_valid(segment) {
+            quoted.push(segment.to_string());
+        } else if fixable(segment) {
+            quoted.push(format!("\"{}\"", segment));
+            changed = true;
+        } else {
+            return None;
+        }
+    }
+    changed.then(|| quoted.join("."))
+}
+
+/// Strategy to quote keys containing spaces or other characters bare keys can't
+/// hold. Valid dotted keys are kept; only their invalid segments are quoted.
+struct QuoteInvalidKeysStrategy;
+
+impl RepairStrategy for QuoteInvalidKeysStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = Vec::new();
+        for (outside, line) in lines_outside_multiline_strings(content) {
+            let trimmed = line.trim_start();
+            let fixed = split_key_value(trimmed)
+                .filter(|_| outside && !trimmed.starts_with(['#', ';', '[']))
+                .and_then(|(key, value)| {
+                    let quoted = quote_invalid_key(key)?;
+                    let indent = &line[..line.len() - trimmed.len()];
+                    let padding = &key[key.trim_end().len()..];
+                    Some(format!("{}{}{}={}", indent, quoted, padding, value))
+                });
+            result.push(fixed.unwrap_or_else(|| line.to_string()));
+        }
+
+        Ok(result.join("\n"))
+    }
+
+    fn priority(&self) -> u8 {
+        8
+    }
+
+    fn name(&self) -> &str {
+        "QuoteInvalidKeysStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Quote keys containing spaces or other characters bare keys can't hold"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("my key = 1")
+    }
 }
 
 /// Strategy to fix missing quotes around string values
@@ -199,6 +385,14 @@ impl RepairStrategy for FixMissingQuotesStrategy {
     fn name(&self) -> &str {
         "FixMissingQuotesStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix missing quotes around string values"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("name = John")
+    }
 }
 
 /// Strategy to fix malformed arrays
@@ -224,6 +418,14 @@ impl RepairStrategy for FixMalformedArraysStrategy {
     fn name(&self) -> &str {
         "FixMalformedArraysStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix malformed arrays"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("ports = [80,]")
+    }
 }
 
 /// Strategy to fix malformed table headers
@@ -250,6 +452,10 @@ impl RepairStrategy for FixMalformedTablesStrategy {
     fn name(&self) -> &str {
         "FixMalformedTablesStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix malformed table headers"
+    }
 }
 
 /// Strategy to fix malformed strings
@@ -277,6 +483,14 @@ impl RepairStrategy for FixMalformedStringsStrategy {
     fn name(&self) -> &str {
         "FixMalformedStringsStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix malformed strings"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("name = 'John'")
+    }
 }
 
 /// Strategy to fix malformed numbers
@@ -306,6 +520,14 @@ impl RepairStrategy for FixMalformedNumbersStrategy {
     fn name(&self) -> &str {
         "FixMalformedNumbersStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix malformed numbers"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("ratio = 1..5")
+    }
 }
 
 /// Strategy to fix malformed dates
@@ -333,6 +555,63 @@ impl RepairStrategy for FixMalformedDatesStrategy {
     fn name(&self) -> &str {
         "FixMalformedDatesStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix malformed dates"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("created = 2024-01-05T10:00:00")
+    }
+}
+
+/// Strategy to convert INI-style `;` comment lines to TOML `#` comments.
+/// Lines inside multi-line strings are left alone.
+struct ConvertSemicolonCommentsStrategy;
+
+impl RepairStrategy for ConvertSemicolonCommentsStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = Vec::new();
+        let mut open_delimiter: Option<&str> = None;
+
+        for line in content.lines() {
+            if open_delimiter.is_none()
+                && let Some(fixed) = swap_comment_marker(line, ';', '#')
+            {
+                result.push(fixed);
+                continue;
+            }
+
+            for delimiter in ["\"\"\"", "'''"] {
+                if line.matches(delimiter).count() % 2 == 1 {
+                    open_delimiter = match open_delimiter {
+                        Some(open) if open == delimiter => None,
+                        None => Some(delimiter),
+                        other => other,
+                    };
+                }
+            }
+            result.push(line.to_string());
+        }
+
+        Ok(result.join("\n"))
+    }
+
+    fn priority(&self) -> u8 {
+        7
+    }
+
+    fn name(&self) -> &str {
+        "ConvertSemicolonCommentsStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Convert INI-style `;` comment lines to TOML `#` comments"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("; settings\nport = 80")
+    }
 }
 
 /// Strategy to add table headers if missing
@@ -346,6 +625,9 @@ impl RepairStrategy for AddTableHeadersStrategy {
 
         for line in lines {
             let trimmed = line.trim();
+            if trimmed.starts_with('[') {
+                has_table_header = true;
+            }
 
             // Check if this is a key-value pair without a table header
             if trimmed.contains('=') && !trimmed.starts_with('[') && !has_table_header {
@@ -366,4 +648,12 @@ impl RepairStrategy for AddTableHeadersStrategy {
     fn name(&self) -> &str {
         "AddTableHeadersStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Add table headers if missing"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("port = 80")
+    }
 }
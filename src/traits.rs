@@ -1,12 +1,24 @@
 //! Core traits for repair functionality
 
 use crate::error::Result;
+use crate::report::RemovedSpan;
+use std::borrow::Cow;
 
 /// Trait for repairing content of various formats
 pub trait Repair {
     /// Repair the given content and return the repaired version
     fn repair(&mut self, content: &str) -> Result<String>;
 
+    /// Repair the given content, borrowing it instead of allocating when no change is needed
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        let repaired = self.repair(content)?;
+        Ok(if repaired == content {
+            Cow::Borrowed(content)
+        } else {
+            Cow::Owned(repaired)
+        })
+    }
+
     /// Check if the content needs repair
     fn needs_repair(&self, content: &str) -> bool;
 
@@ -24,6 +36,32 @@ pub trait RepairStrategy {
 
     /// Get the name of this strategy
     fn name(&self) -> &str;
+
+    /// One-line summary of what this strategy fixes, shown by `anyrepair rules`
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// Sample input this strategy changes, shown with its output by `anyrepair rules show`
+    fn example(&self) -> Option<&str> {
+        None
+    }
+
+    /// Apply the strategy and describe what it changed (`None` if nothing changed).
+    /// Strategies can override this to give a more specific description.
+    fn apply_with_log(&self, content: &str) -> Result<(String, Option<String>)> {
+        let result = self.apply(content)?;
+        let description =
+            (result != content).then(|| format!("{} changed the content", self.name()));
+        Ok((result, description))
+    }
+
+    /// Spans of `content` that [`apply`](Self::apply) deletes outright, such as
+    /// comments or trailing junk, with byte ranges into `content`.
+    /// Only asked of strategies that changed the content.
+    fn removals(&self, _content: &str) -> Vec<RemovedSpan> {
+        Vec::new()
+    }
 }
 
 /// Trait for content validation
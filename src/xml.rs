@@ -1,8 +1,10 @@
 //! XML repair module
 
 use crate::error::Result;
+use crate::repairer_base::BaseOptions;
 use crate::traits::{Repair, RepairStrategy, Validator};
 use regex::Regex;
+use std::borrow::Cow;
 use std::sync::OnceLock;
 
 /// Cached regex patterns for XML performance optimization
@@ -16,7 +18,7 @@ struct XmlRegexCache {
 impl XmlRegexCache {
     fn new() -> Result<Self> {
         Ok(Self {
-            unclosed_tags: Regex::new(r"<(\w+)([^>]*)>")?,
+            unclosed_tags: Regex::new(r"<(/?\w+)([^>]*)>")?,
             malformed_attributes: Regex::new(r#"(\w+)=([^"'\s>]+)"#)?,
             missing_quotes: Regex::new(r#"(\w+)=([^"'\s>]+)"#)?,
             self_closing_tags: Regex::new(r"<(\w+)([^>]*)/>")?,
@@ -42,6 +44,7 @@ impl XmlRepairer {
     /// Create a new XML repairer
     pub fn new() -> Self {
         let strategies: Vec<Box<dyn RepairStrategy>> = vec![
+            Box::new(NormalizeAttributeQuotesStrategy),
             Box::new(FixUnclosedTagsStrategy),
             Box::new(FixMalformedAttributesStrategy),
             Box::new(FixInvalidCharactersStrategy),
@@ -55,6 +58,82 @@ impl XmlRepairer {
 
         Self { inner }
     }
+
+    /// Start configuring an XML repairer
+    pub fn builder() -> XmlRepairerBuilder {
+        XmlRepairerBuilder::default()
+    }
+
+    /// Create a repairer for HTML fragments when `html` is true, or a plain XML repairer otherwise.
+    ///
+    /// HTML mode leaves void elements (`<br>`, `<img>`, ...) unclosed, tolerates
+    /// boolean attributes, matches tag names case-insensitively and never adds an
+    /// XML declaration.
+    pub fn with_html_mode(html: bool) -> Self {
+        Self::with_html_options(html, false)
+    }
+
+    /// Like [`XmlRepairer::with_html_mode`], optionally lowercasing tag names in HTML mode
+    pub fn with_html_options(html: bool, lowercase_tags: bool) -> Self {
+        if !html {
+            return Self::new();
+        }
+
+        let mut strategies: Vec<Box<dyn RepairStrategy>> = vec![
+            Box::new(CloseHtmlTagsStrategy),
+            Box::new(FixMalformedAttributesStrategy),
+            Box::new(FixInvalidCharactersStrategy),
+        ];
+        if lowercase_tags {
+            strategies.push(Box::new(LowercaseTagNamesStrategy));
+        }
+
+        let validator: Box<dyn Validator> = Box::new(HtmlValidator { lowercase_tags });
+        let inner = crate::repairer_base::GenericRepairer::new(validator, strategies);
+
+        Self { inner }
+    }
+}
+
+/// Builder combining any of the options of [`XmlRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct XmlRepairerBuilder {
+    base: BaseOptions,
+    html: bool,
+    lowercase_tags: bool,
+}
+
+impl XmlRepairerBuilder {
+    /// Repair HTML fragments instead of XML (see [`XmlRepairer::with_html_mode`])
+    pub fn html_mode(mut self, html: bool) -> Self {
+        self.html = html;
+        self
+    }
+
+    /// Lowercase tag names; only applies in HTML mode
+    pub fn lowercase_tags(mut self, lowercase: bool) -> Self {
+        self.lowercase_tags = lowercase;
+        self
+    }
+
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Create the configured XML repairer
+    pub fn build(self) -> XmlRepairer {
+        let mut repairer = XmlRepairer::with_html_options(self.html, self.lowercase_tags);
+        repairer.inner.apply_options(self.base);
+        repairer
+    }
 }
 
 impl Default for XmlRepairer {
@@ -68,46 +147,20 @@ impl Repair for XmlRepairer {
         self.inner.repair(content)
     }
 
+    fn repair_cow<'a>(&mut self, content: &'a str) -> Result<Cow<'a, str>> {
+        self.inner.repair_cow(content)
+    }
+
     fn needs_repair(&self, content: &str) -> bool {
         self.inner.needs_repair(content)
     }
 
     fn confidence(&self, content: &str) -> f64 {
-        if content.trim().is_empty() {
-            return 0.0;
-        }
-
-        // Calculate confidence based on XML-like patterns
-        let mut score: f64 = 0.0;
-
-        // Check for XML declaration
-        if content.trim().starts_with("<?xml") {
-            score += 0.3;
-        }
-
-        // Check for opening tags
-        if content.contains('<') && content.contains('>') {
-            score += 0.3;
-        }
-
-        // Check for proper tag structure
-        let open_tags = content.matches('<').count();
-        let close_tags = content.matches('>').count();
-        if open_tags == close_tags {
-            score += 0.2;
+        if self.inner.validator().is_valid(content) {
+            return 1.0;
         }
-
-        // Check for attributes
-        if content.contains('=') {
-            score += 0.1;
-        }
-
-        // Check for content between tags
-        if content.contains("</") {
-            score += 0.1;
-        }
-
-        score.min(1.0)
+        // Graded by how much of the markup is well-formed, never reaching a valid score
+        0.9 * xml_completeness(content)
     }
 }
 
@@ -146,9 +199,16 @@ fn xml_structure_valid(content: &str) -> bool {
         let tag_inner = rest[start + 1..start + rel_end].trim();
         rest = &rest[start + rel_end + 1..];
 
+        // Strict parsers want the declaration double-quoted
+        if tag_inner.starts_with("?xml ") && requote_attributes(tag_inner).0 != tag_inner {
+            return false;
+        }
         if tag_inner.is_empty() || tag_inner.starts_with('?') || tag_inner.starts_with('!') {
             continue;
         }
+        if requote_attributes(tag_inner).1 {
+            return false;
+        }
 
         // Reject unquoted attribute values inside tags (e.g., <tag attr=value>)
         if tag_inner.contains('=')
@@ -160,8 +220,8 @@ fn xml_structure_valid(content: &str) -> bool {
 
         let self_closing = tag_inner.ends_with('/');
         let inner = tag_inner.trim_end_matches('/').trim();
-        if inner.starts_with('/') {
-            let name = inner[1..].split_whitespace().next().unwrap_or("");
+        if let Some(stripped) = inner.strip_prefix('/') {
+            let name = stripped.split_whitespace().next().unwrap_or("");
             match stack.pop() {
                 Some(open) if open == name => {}
                 _ => return false,
@@ -180,6 +240,312 @@ fn xml_structure_valid(content: &str) -> bool {
     stack.is_empty()
 }
 
+/// Share (0.0–1.0) of the tags in `content` that are well-formed and balanced.
+///
+/// Paired tags count once each when matched, declarations and self-closing tags
+/// count when well-formed; stray closers, unclosed elements and an unterminated
+/// tag count against. Text before the first tag halves the score.
+fn xml_completeness(content: &str) -> f64 {
+    let trimmed = content.trim();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut good = 0usize;
+    let mut bad = 0usize;
+    let mut rest = trimmed;
+
+    while let Some(start) = rest.find('<') {
+        let Some(rel_end) = rest[start..].find('>') else {
+            bad += 1;
+            break;
+        };
+        let tag_inner = rest[start + 1..start + rel_end].trim();
+        rest = &rest[start + rel_end + 1..];
+
+        let unquoted_attribute =
+            tag_inner.contains('=') && !tag_inner.contains('"') && !tag_inner.contains('\'');
+        let inner = tag_inner.trim_end_matches('/').trim();
+        let name = inner
+            .trim_start_matches('/')
+            .split_whitespace()
+            .next()
+            .unwrap_or("");
+        if unquoted_attribute || name.is_empty() {
+            bad += 1;
+        } else if tag_inner.starts_with('?')
+            || tag_inner.starts_with('!')
+            || tag_inner.ends_with('/')
+        {
+            good += 1;
+        } else if inner.starts_with('/') {
+            match stack.iter().rposition(|open| *open == name) {
+                Some(i) => {
+                    // Elements left open inside the one being closed
+                    bad += stack.len() - i - 1;
+                    stack.truncate(i);
+                    good += 2;
+                }
+                None => bad += 1,
+            }
+        } else {
+            stack.push(name);
+        }
+    }
+    bad += stack.len();
+
+    if good + bad == 0 {
+        return 0.0;
+    }
+    let score = good as f64 / (good + bad) as f64;
+    if trimmed.starts_with('<') {
+        score
+    } else {
+        score / 2.0
+    }
+}
+
+/// Rewrite every quoted attribute value in the text of a tag as double-quoted
+/// (`a='1'` -> `a="1"`), escaping `"` inside values.
+///
+/// A value opened with one quote and closed with the other (`version="1.0'`) ends
+/// at the stray quote, and an unterminated value runs to the end of the tag.
+/// Also returns whether any such mismatched or unterminated value was found.
+fn requote_attributes(inner: &str) -> (String, bool) {
+    let mut result = String::with_capacity(inner.len());
+    let mut mismatched = false;
+    let mut rest = inner;
+
+    while let Some(eq) = rest.find('=') {
+        let (before, after) = rest.split_at(eq + 1);
+        let value = after.trim_start();
+        result.push_str(before);
+        result.push_str(&after[..after.len() - value.len()]);
+
+        let Some(quote) = value.chars().next().filter(|c| matches!(c, '"' | '\'')) else {
+            rest = value;
+            continue;
+        };
+        let body = &value[1..];
+        let other = if quote == '"' { '\'' } else { '"' };
+        // Closes the value if the tag or the next attribute follows it
+        let ends_value = |i: usize| {
+            body[i + 1..]
+                .chars()
+                .next()
+                .is_none_or(|c| c.is_whitespace() || matches!(c, '>' | '/' | '?'))
+        };
+
+        let matching = body.find(quote);
+        let others_before = body[..matching.unwrap_or(body.len())]
+            .matches(other)
+            .count();
+        let end = match matching {
+            // Quotes of the other kind in between come in pairs, so they are content
+            Some(i) if others_before % 2 == 0 => Some(i),
+            _ => body
+                .char_indices()
+                .find(|&(i, c)| c == other && ends_value(i))
+                .map(|(i, _)| i)
+                .or(matching),
+        };
+        mismatched |= end != matching || end.is_none();
+
+        let end = end.unwrap_or(body.len());
+        result.push('"');
+        result.push_str(&body[..end].replace('"', "&quot;"));
+        result.push('"');
+        rest = body.get(end + 1..).unwrap_or("");
+    }
+
+    result.push_str(rest);
+    (result, mismatched)
+}
+
+/// HTML elements that never have a closing tag
+const HTML_VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+fn is_void_element(name: &str) -> bool {
+    HTML_VOID_ELEMENTS
+        .iter()
+        .any(|v| v.eq_ignore_ascii_case(name))
+}
+
+/// A single tag found while scanning markup
+struct ScannedTag<'a> {
+    /// Byte offset of the tag name within the scanned content
+    name_start: usize,
+    name: &'a str,
+    closing: bool,
+    self_closing: bool,
+    /// Raw text between `<` and `>`
+    inner: &'a str,
+}
+
+/// Scan element tags, skipping declarations, comments and processing instructions.
+/// Returns `None` if a `<` is never closed by `>`.
+fn scan_tags(content: &str) -> Option<Vec<ScannedTag<'_>>> {
+    let mut tags = Vec::new();
+    let mut offset = 0;
+    while let Some(rel_start) = content[offset..].find('<') {
+        let start = offset + rel_start;
+        let rel_end = content[start..].find('>')?;
+        let end = start + rel_end;
+        let inner = &content[start + 1..end];
+        offset = end + 1;
+
+        if inner.starts_with('?') || inner.starts_with('!') {
+            continue;
+        }
+        let closing = inner.starts_with('/');
+        let name_start = start + 1 + usize::from(closing);
+        let name_len = content[name_start..end]
+            .find(|c: char| c.is_whitespace() || c == '/')
+            .unwrap_or(end - name_start);
+        tags.push(ScannedTag {
+            name_start,
+            name: &content[name_start..name_start + name_len],
+            closing,
+            self_closing: inner.trim_end().ends_with('/'),
+            inner,
+        });
+    }
+    Some(tags)
+}
+
+/// HTML fragment validator
+///
+/// Void elements need no closing tag, boolean attributes are allowed and tag
+/// names match case-insensitively. Unquoted attribute values are still reported.
+pub struct HtmlValidator {
+    /// Report tag names that are not lowercase
+    pub lowercase_tags: bool,
+}
+
+impl Validator for HtmlValidator {
+    fn is_valid(&self, content: &str) -> bool {
+        !content.trim().is_empty() && self.validate(content).is_empty()
+    }
+
+    fn validate(&self, content: &str) -> Vec<String> {
+        if content.trim().is_empty() {
+            return vec!["Empty HTML content".to_string()];
+        }
+        let Some(tags) = scan_tags(content) else {
+            return vec!["Unterminated tag".to_string()];
+        };
+
+        let mut errors = Vec::new();
+        let mut stack: Vec<&str> = Vec::new();
+        for tag in &tags {
+            if tag.name.is_empty() {
+                errors.push(format!("Empty tag name in <{}>", tag.inner));
+                continue;
+            }
+            if self.lowercase_tags && tag.name.chars().any(|c| c.is_ascii_uppercase()) {
+                errors.push(format!("Tag name '{}' is not lowercase", tag.name));
+            }
+            if tag.inner.contains('=') && !tag.inner.contains('"') && !tag.inner.contains('\'') {
+                errors.push(format!("Unquoted attribute value in <{}>", tag.inner));
+            }
+            if tag.closing {
+                match stack.pop() {
+                    Some(open) if open.eq_ignore_ascii_case(tag.name) => {}
+                    _ => errors.push(format!("Unexpected closing tag </{}>", tag.name)),
+                }
+            } else if !tag.self_closing && !is_void_element(tag.name) {
+                stack.push(tag.name);
+            }
+        }
+        for open in stack {
+            errors.push(format!("Unclosed tag <{}>", open));
+        }
+        errors
+    }
+}
+
+/// Strategy to close unclosed HTML elements, leaving void elements alone
+struct CloseHtmlTagsStrategy;
+
+impl RepairStrategy for CloseHtmlTagsStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let Some(tags) = scan_tags(content) else {
+            return Ok(content.to_string());
+        };
+
+        let mut stack: Vec<&str> = Vec::new();
+        for tag in &tags {
+            if tag.name.is_empty() || tag.self_closing || is_void_element(tag.name) {
+                continue;
+            }
+            if tag.closing {
+                // Pop back to the matching opener; a stray closing tag is ignored
+                if let Some(pos) = stack.iter().rposition(|t| t.eq_ignore_ascii_case(tag.name)) {
+                    stack.truncate(pos);
+                }
+            } else {
+                stack.push(tag.name);
+            }
+        }
+
+        let mut result = content.to_string();
+        for tag in stack.iter().rev() {
+            result.push_str(&format!("</{tag}>"));
+        }
+        Ok(result)
+    }
+
+    fn priority(&self) -> u8 {
+        6
+    }
+
+    fn name(&self) -> &str {
+        "CloseHtmlTagsStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Close unclosed HTML elements, leaving void elements alone"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("<div><p>Hello<br></div>")
+    }
+}
+
+/// Strategy to lowercase HTML tag names (attributes and text are untouched)
+struct LowercaseTagNamesStrategy;
+
+impl RepairStrategy for LowercaseTagNamesStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let Some(tags) = scan_tags(content) else {
+            return Ok(content.to_string());
+        };
+
+        let mut result = content.to_string();
+        for tag in &tags {
+            let range = tag.name_start..tag.name_start + tag.name.len();
+            result.replace_range(range, &tag.name.to_ascii_lowercase());
+        }
+        Ok(result)
+    }
+
+    fn priority(&self) -> u8 {
+        1
+    }
+
+    fn name(&self) -> &str {
+        "LowercaseTagNamesStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Lowercase HTML tag names (attributes and text are untouched)"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(r#"<DIV Class="x">Hi</DIV>"#)
+    }
+}
+
 /// Strategy to fix unclosed tags
 struct FixUnclosedTagsStrategy;
 
@@ -226,6 +592,14 @@ impl RepairStrategy for FixUnclosedTagsStrategy {
     fn name(&self) -> &str {
         "FixUnclosedTagsStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix unclosed tags"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("<root><item>1")
+    }
 }
 
 /// Strategy to fix malformed attributes
@@ -252,6 +626,14 @@ impl RepairStrategy for FixMalformedAttributesStrategy {
     fn name(&self) -> &str {
         "FixMalformedAttributesStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix malformed attributes"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("<item id=1 name=ann>x</item>")
+    }
 }
 
 /// Strategy to fix invalid characters
@@ -299,6 +681,14 @@ impl RepairStrategy for FixInvalidCharactersStrategy {
     fn name(&self) -> &str {
         "FixInvalidCharactersStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix invalid characters"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("<a>Tom & Jerry</a>")
+    }
 }
 
 /// Strategy to fix missing quotes around attribute values
@@ -325,6 +715,60 @@ impl RepairStrategy for FixMissingQuotesStrategy {
     fn name(&self) -> &str {
         "FixMissingQuotesStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix missing quotes around attribute values"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("<item id=1>x</item>")
+    }
+}
+
+/// Strategy to double-quote the attributes of the XML declaration and of elements,
+/// fixing values opened with one quote and closed with the other
+struct NormalizeAttributeQuotesStrategy;
+
+impl RepairStrategy for NormalizeAttributeQuotesStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut result = String::with_capacity(content.len());
+        let mut rest = content;
+
+        while let Some(start) = rest.find('<') {
+            let Some(rel_end) = rest[start..].find('>') else {
+                break;
+            };
+            let inner = &rest[start + 1..start + rel_end];
+            result.push_str(&rest[..=start]);
+            // Comments, CDATA and other processing instructions are left alone
+            if inner.starts_with('!') || (inner.starts_with('?') && !inner.starts_with("?xml ")) {
+                result.push_str(inner);
+            } else {
+                result.push_str(&requote_attributes(inner).0);
+            }
+            result.push('>');
+            rest = &rest[start + rel_end + 1..];
+        }
+
+        result.push_str(rest);
+        Ok(result)
+    }
+
+    fn priority(&self) -> u8 {
+        7
+    }
+
+    fn name(&self) -> &str {
+        "NormalizeAttributeQuotesStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Double-quote the declaration and attributes, fixing mismatched quote pairs"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("<?xml version='1.0'?>\n<item id=\"1' name='ann'>x</item>")
+    }
 }
 
 /// Strategy to fix self-closing tags
@@ -351,6 +795,10 @@ impl RepairStrategy for FixSelfClosingTagsStrategy {
     fn name(&self) -> &str {
         "FixSelfClosingTagsStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix self-closing tags"
+    }
 }
 
 /// Strategy to add XML declaration
@@ -376,4 +824,12 @@ impl RepairStrategy for AddXmlDeclarationStrategy {
     fn name(&self) -> &str {
         "AddXmlDeclarationStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Add XML declaration"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("<root/>")
+    }
 }
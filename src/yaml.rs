@@ -1,6 +1,10 @@
 //! YAML repair functionality
 
 use crate::error::Result;
+use crate::json_util::json_string;
+use crate::repairer_base::{
+    BaseOptions, DEFAULT_MAX_DEPTH, restore_line_endings, swap_comment_marker,
+};
 use crate::traits::{Repair, RepairStrategy, Validator};
 use regex::Regex;
 use std::sync::OnceLock;
@@ -11,16 +15,20 @@ struct YamlRegexCache {
     missing_colons: Regex,
     list_items: Regex,
     quoted_strings: Regex,
+    ambiguous_booleans: Regex,
 }
 
 impl YamlRegexCache {
     fn new() -> Result<Self> {
         Ok(Self {
             missing_colons: Regex::new(r#"^(\s*)([a-zA-Z_][a-zA-Z0-9_]*)\s+([^:].*)$"#)?,
-            list_items: Regex::new(r#"^\s*-\s*(.+)$"#)?,
+            list_items: Regex::new(r#"^(\s*)-([^\s-].*)$"#)?,
             quoted_strings: Regex::new(
                 r#"^(\s*)([a-zA-Z_][a-zA-Z0-9_]*)\s*:\s*([^'"].*[^'"])\s*$"#,
             )?,
+            ambiguous_booleans: Regex::new(
+                r#"(?i)^(\s*(?:-\s+)*(?:[^\s#'"][^#]*?:\s+)?)(yes|no|on|off|y|n)(\s+#.*)?\s*$"#,
+            )?,
         })
     }
 }
@@ -32,11 +40,117 @@ fn get_yaml_regex_cache() -> &'static YamlRegexCache {
         .get_or_init(|| YamlRegexCache::new().expect("Failed to initialize YAML regex cache"))
 }
 
+/// True if a line opens a block scalar (`key: |`, `key: >-`, `- |`, ...)
+fn is_block_scalar_header(line: &str) -> bool {
+    let trimmed = line.trim();
+    let value = if let Some((_, value)) = trimmed.split_once(": ") {
+        value
+    } else if let Some(value) = trimmed.strip_prefix("- ") {
+        value
+    } else {
+        return false;
+    };
+    let indicator = value.split(" #").next().unwrap_or("").trim();
+    let mut chars = indicator.chars();
+    matches!(chars.next(), Some('|' | '>'))
+        && chars.all(|c| matches!(c, '-' | '+') || c.is_ascii_digit())
+}
+
+fn indent_of(line: &str) -> usize {
+    line.chars().take_while(|c| c.is_whitespace()).count()
+}
+
+/// For each line, whether it belongs to the body of a `|` / `>` block scalar.
+/// Body lines are blank or indented deeper than the header line that opened the block.
+fn block_scalar_mask(lines: &[&str]) -> Vec<bool> {
+    let mut mask = vec![false; lines.len()];
+    let mut header_indent: Option<usize> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(indent) = header_indent {
+            if line.trim().is_empty() || indent_of(line) > indent {
+                mask[i] = true;
+                continue;
+            }
+            header_indent = None;
+        }
+        if is_block_scalar_header(line) {
+            header_indent = Some(indent_of(line));
+        }
+    }
+
+    mask
+}
+
+/// Apply `fix` to every line outside block scalars.
+///
+/// Block scalar bodies are passed through verbatim, shifted by however much
+/// `fix` re-indented their header line so they stay nested under it.
+fn map_lines_preserving_blocks(content: &str, mut fix: impl FnMut(&str) -> String) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mask = block_scalar_mask(&lines);
+    let mut result = Vec::with_capacity(lines.len());
+    let mut shift: isize = 0;
+
+    for (line, in_block) in lines.iter().zip(mask) {
+        if !in_block {
+            let fixed = fix(line);
+            shift = indent_of(&fixed) as isize - indent_of(line) as isize;
+            result.push(fixed);
+        } else if line.trim().is_empty() || shift == 0 {
+            result.push(line.to_string());
+        } else if shift > 0 {
+            result.push(format!("{}{}", " ".repeat(shift as usize), line));
+        } else {
+            // Indent is counted in chars, so find the byte offset to cut at
+            let strip = (-shift as usize).min(indent_of(line));
+            let start = line
+                .char_indices()
+                .nth(strip)
+                .map_or(line.len(), |(i, _)| i);
+            result.push(line[start..].to_string());
+        }
+    }
+
+    result.join("\n")
+}
+
+/// Split a multi-document stream into the bodies between its `---` separator lines.
+/// A stream that opens with `---` yields an empty first body.
+fn split_documents(content: &str) -> Vec<&str> {
+    let mut documents = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            documents.push(&content[start..offset]);
+            start = offset + line.len();
+        }
+        offset += line.len();
+    }
+    documents.push(&content[start..]);
+
+    documents
+}
+
 /// YAML repairer that can fix common YAML issues
 ///
 /// Uses trait-based composition with GenericRepairer for better modularity
 pub struct YamlRepairer {
     pub inner: crate::repairer_base::GenericRepairer,
+    implicit_bools: ImplicitBoolPolicy,
+}
+
+/// How [`YamlRepairer`] treats bare `yes`/`no`/`on`/`off`/`y`/`n` values, which
+/// YAML 1.1 parsers read as booleans (so the country code `no` becomes `false`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImplicitBoolPolicy {
+    /// Leave the values bare (the default)
+    #[default]
+    LeaveAsIs,
+    /// Quote the values so every parser reads them as strings
+    QuoteAmbiguous,
 }
 
 impl YamlRepairer {
@@ -45,17 +159,108 @@ impl YamlRepairer {
         let strategies: Vec<Box<dyn RepairStrategy>> = vec![
             Box::new(FixIndentationStrategy),
             Box::new(AddMissingColonsStrategy),
+            Box::new(SpaceAfterKeyColonStrategy),
             Box::new(FixListFormattingStrategy),
+            Box::new(QuoteSpecialScalarsStrategy),
             Box::new(AddDocumentSeparatorStrategy),
             Box::new(FixQuotedStringsStrategy),
+            Box::new(NormalizeIndentUnitsStrategy),
             Box::new(AdvancedIndentationStrategy),
             Box::new(ComplexStructureStrategy),
+            Box::new(ConvertSemicolonCommentsStrategy),
+            Box::new(StripStatementTerminatorsStrategy),
         ];
 
         let validator: Box<dyn Validator> = Box::new(YamlValidator);
-        let inner = crate::repairer_base::GenericRepairer::new(validator, strategies);
+        let mut inner = crate::repairer_base::GenericRepairer::new(validator, strategies);
+        inner.set_max_depth(Some(DEFAULT_MAX_DEPTH));
 
-        Self { inner }
+        Self {
+            inner,
+            implicit_bools: ImplicitBoolPolicy::default(),
+        }
+    }
+
+    /// Start configuring a YAML repairer
+    pub fn builder() -> YamlRepairerBuilder {
+        YamlRepairerBuilder::default()
+    }
+
+    /// Create a YAML repairer that applies `policy` to bare `yes`/`no`/`on`/`off`
+    /// values; unlike the other repairs this also runs on otherwise valid YAML
+    pub fn with_implicit_bool_policy(policy: ImplicitBoolPolicy) -> Self {
+        Self::builder().implicit_bool_policy(policy).build()
+    }
+
+    /// Repair each `---` separated document on its own, so a broken document
+    /// in a stream (e.g. Kubernetes manifests) cannot disturb its neighbours
+    fn repair_documents(&mut self, content: &str) -> Result<String> {
+        let documents = split_documents(content);
+        if documents.len() < 2 {
+            return self.inner.repair(content);
+        }
+
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let mut repaired = Vec::with_capacity(documents.len());
+        for document in documents {
+            if document.trim().is_empty() {
+                repaired.push(String::new());
+                continue;
+            }
+            let fixed = self.inner.repair(document)?;
+            // The separators are restored below; don't let a document gain its own
+            let fixed = fixed.strip_prefix("---").unwrap_or(&fixed);
+            repaired.push(fixed.trim().to_string());
+        }
+
+        let separator = format!("{newline}---{newline}");
+        let mut result = repaired.join(&separator);
+        if result.starts_with(newline) {
+            result.drain(..newline.len());
+        }
+        if content.ends_with('\n') && !result.ends_with('\n') {
+            result.push_str(newline);
+        }
+        Ok(result)
+    }
+}
+
+/// Builder combining any of the options of [`YamlRepairer`]
+#[derive(Debug, Clone, Default)]
+pub struct YamlRepairerBuilder {
+    base: BaseOptions,
+    implicit_bools: ImplicitBoolPolicy,
+}
+
+impl YamlRepairerBuilder {
+    /// How bare `yes`/`no`/`on`/`off` values are treated
+    pub fn implicit_bool_policy(mut self, policy: ImplicitBoolPolicy) -> Self {
+        self.implicit_bools = policy;
+        self
+    }
+
+    /// Keep a leading UTF-8 BOM of the input on the repaired output (off by default)
+    pub fn preserve_bom(mut self, preserve: bool) -> Self {
+        self.base.preserve_bom = preserve;
+        self
+    }
+
+    /// Limit how deeply `{`/`[` may nest in the input (`None` disables the check)
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.base.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Create the configured YAML repairer
+    pub fn build(self) -> YamlRepairer {
+        let mut repairer = YamlRepairer::new();
+        repairer.implicit_bools = self.implicit_bools;
+        repairer.inner.apply_options(self.base);
+        repairer
     }
 }
 
@@ -67,7 +272,11 @@ impl Default for YamlRepairer {
 
 impl Repair for YamlRepairer {
     fn repair(&mut self, content: &str) -> Result<String> {
-        self.inner.repair(content)
+        let repaired = self.repair_documents(content)?;
+        Ok(match self.implicit_bools {
+            ImplicitBoolPolicy::LeaveAsIs => repaired,
+            ImplicitBoolPolicy::QuoteAmbiguous => quote_ambiguous_booleans(&repaired),
+        })
     }
 
     fn needs_repair(&self, content: &str) -> bool {
@@ -130,6 +339,18 @@ impl Repair for YamlRepairer {
     }
 }
 
+/// Quote bare `yes`/`no`/`on`/`off`/`y`/`n` values (any case) outside block scalars
+fn quote_ambiguous_booleans(content: &str) -> String {
+    let cache = get_yaml_regex_cache();
+    let quoted = map_lines_preserving_blocks(content, |line| {
+        cache
+            .ambiguous_booleans
+            .replace(line, r#"${1}"${2}"${3}"#)
+            .into_owned()
+    });
+    restore_line_endings(content, &quoted)
+}
+
 /// YAML validator
 pub struct YamlValidator;
 
@@ -152,11 +373,51 @@ fn yaml_structure_valid(content: &str) -> bool {
         return false;
     }
 
-    for line in content.lines() {
+    let lines: Vec<&str> = content.lines().collect();
+    let anchors = anchor_names(content);
+    // Indents of the enclosing lines; a dedent must return to one of them
+    let mut levels: Vec<usize> = Vec::new();
+    for (line, in_block) in lines.iter().zip(block_scalar_mask(&lines)) {
         let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
+        if in_block || trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        // YAML forbids tabs in indentation
+        if line[..line.len() - line.trim_start().len()].contains('\t') {
+            return false;
+        }
+        if trimmed == "---" || trimmed == "..." {
+            levels.clear();
             continue;
         }
+        let indent = indent_of(line);
+        let mut dedented = false;
+        while levels.last().is_some_and(|&level| level > indent) {
+            levels.pop();
+            dedented = true;
+        }
+        if levels.last() != Some(&indent) {
+            if dedented {
+                return false;
+            }
+            levels.push(indent);
+        }
+
+        // `;` comments are INI syntax and `;` terminators come from code, not YAML
+        if trimmed.starts_with(';')
+            || strip_statement_terminator(line).len() < line.trim_end().len()
+        {
+            return false;
+        }
+
+        if split_scalar_value(line)
+            .is_some_and(|(_, value, _)| needs_scalar_quotes(value, &anchors))
+            || unspaced_key_colon(line).is_some()
+            || unspaced_list_marker(trimmed)
+        {
+            return false;
+        }
 
         if !trimmed.starts_with('-')
             && !trimmed.starts_with('[')
@@ -171,51 +432,266 @@ fn yaml_structure_valid(content: &str) -> bool {
     true
 }
 
-/// Strategy to fix indentation issues
-struct FixIndentationStrategy;
+/// True if `trimmed` is a list item missing the space after its dash (`-item`);
+/// `---`, negative numbers and `-.5` are not list items
+fn unspaced_list_marker(trimmed: &str) -> bool {
+    trimmed
+        .strip_prefix('-')
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| !c.is_whitespace() && !c.is_ascii_digit() && !matches!(c, '-' | '.'))
+}
 
-impl RepairStrategy for FixIndentationStrategy {
+/// Tracks open YAML containers to re-indent lines while keeping their nesting.
+///
+/// Each entry is `(original indent, normalized indent)` of a line that opens a
+/// nested block: a `key:` with no inline value, or a list item.
+#[derive(Default)]
+struct IndentTracker {
+    stack: Vec<(usize, usize)>,
+}
+
+impl IndentTracker {
+    /// Normalized indentation for a line indented by `indent` in the input:
+    /// two spaces deeper than the nearest shallower container, or 0 at top level.
+    fn indent_for(&mut self, indent: usize) -> usize {
+        while self.stack.last().is_some_and(|(orig, _)| *orig >= indent) {
+            self.stack.pop();
+        }
+        self.stack.last().map_or(0, |(_, norm)| norm + 2)
+    }
+
+    /// Record that the line just placed opens a nested block
+    fn open(&mut self, indent: usize, normalized: usize) {
+        self.stack.push((indent, normalized));
+    }
+
+    /// Re-indent `line`, returning the normalized line
+    fn normalize(&mut self, line: &str, trimmed: &str) -> String {
+        if trimmed == "---" || trimmed == "..." {
+            self.stack.clear();
+            return trimmed.to_string();
+        }
+        let indent = indent_of(line);
+        let normalized = self.indent_for(indent);
+        if opens_block(trimmed) {
+            self.open(indent, normalized);
+        }
+        format!("{}{}", " ".repeat(normalized), trimmed)
+    }
+}
+
+/// True if children are expected below this line (`key:` without value, or a list item).
+/// A key whose value is only an anchor or tag (`defaults: &defaults`, or
+/// `defaults &defaults` before its colon is restored) also opens a block.
+fn opens_block(trimmed: &str) -> bool {
+    if trimmed.starts_with("- ") || trimmed == "-" || trimmed.ends_with(':') {
+        return true;
+    }
+    trimmed
+        .split_once(": ")
+        .or_else(|| trimmed.split_once(' '))
+        .is_some_and(|(_, value)| is_node_properties(value))
+}
+
+/// True if `value` holds only node properties: `&anchor` and/or `!tag`, optionally commented
+fn is_node_properties(value: &str) -> bool {
+    let value = value.split(" #").next().unwrap_or("").trim();
+    !value.is_empty()
+        && value
+            .split_whitespace()
+            .all(|token| token.len() > 1 && (token.starts_with('&') || token.starts_with('!')))
+}
+
+/// Strategy to convert INI-style `;` comment lines to YAML `#` comments.
+/// Runs first so other strategies don't mistake the comment for a key.
+struct ConvertSemicolonCommentsStrategy;
+
+impl RepairStrategy for ConvertSemicolonCommentsStrategy {
     fn apply(&self, content: &str) -> Result<String> {
-        let lines: Vec<&str> = content.lines().collect();
-        let mut result = Vec::<String>::new();
-        let mut indent_stack = vec![0];
+        Ok(map_lines_preserving_blocks(content, |line| {
+            swap_comment_marker(line, ';', '#').unwrap_or_else(|| line.to_string())
+        }))
+    }
 
-        for line in lines {
-            if line.trim().is_empty() {
-                result.push(line.to_string());
-                continue;
-            }
+    fn priority(&self) -> u8 {
+        7
+    }
 
-            let _current_indent = line.chars().take_while(|c| c.is_whitespace()).count();
-            let trimmed = line.trim();
+    fn name(&self) -> &str {
+        "ConvertSemicolonCommentsStrategy"
+    }
 
-            // Determine expected indentation based on context
-            let base_indent = indent_stack.last().copied().unwrap_or(0);
-            let expected_indent = if trimmed.starts_with('-') || trimmed.ends_with(':') {
-                base_indent
-            } else {
-                base_indent + 2
-            };
+    fn description(&self) -> &str {
+        "Convert INI-style `;` comment lines to YAML `#` comments"
+    }
 
-            // Fix missing colons for key-value pairs
-            let fixed_trimmed = if !trimmed.contains(':') && trimmed.contains(' ') {
-                // This looks like a key-value pair missing a colon
-                trimmed.replacen(' ', ": ", 1)
-            } else {
-                trimmed.to_string()
-            };
+    fn example(&self) -> Option<&str> {
+        Some("; settings\nname: app")
+    }
+}
 
-            // Fix indentation
-            let fixed_line = format!("{}{}", " ".repeat(expected_indent), fixed_trimmed);
-            result.push(fixed_line);
+/// Strategy to drop `;` statement terminators from the end of values (`a: 1;` -> `a: 1`)
+struct StripStatementTerminatorsStrategy;
 
-            // Update indent stack
-            if fixed_trimmed.ends_with(':') || fixed_trimmed.starts_with('-') {
-                indent_stack.push(expected_indent + 2);
-            }
+impl RepairStrategy for StripStatementTerminatorsStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        Ok(map_lines_preserving_blocks(content, |line| {
+            strip_statement_terminator(line).to_string()
+        }))
+    }
+
+    fn priority(&self) -> u8 {
+        7
+    }
+
+    fn name(&self) -> &str {
+        "StripStatementTerminatorsStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Drop `;` statement terminators from the end of values (`a: 1;` -> `a: 1`)"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("a: 1;\nb: two;")
+    }
+}
+
+/// `line` without trailing `;` terminators, if it is a `key: value` or list item
+/// whose value is not an open quoted string
+fn strip_statement_terminator(line: &str) -> &str {
+    let trimmed = line.trim_start();
+    let is_entry = trimmed.starts_with('-') || trimmed.contains(": ");
+    if !is_entry || trimmed.starts_with(['#', ';']) || trimmed.matches('"').count() % 2 == 1 {
+        return line;
+    }
+    line.trim_end().trim_end_matches(';').trim_end()
+}
+
+/// Strategy to quote plain scalars that YAML would reject or read as another
+/// type (`key: @handle` -> `key: "@handle"`)
+struct QuoteSpecialScalarsStrategy;
+
+impl RepairStrategy for QuoteSpecialScalarsStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let anchors = anchor_names(content);
+        Ok(map_lines_preserving_blocks(
+            content,
+            |line| match split_scalar_value(line) {
+                Some((before, value, after)) if needs_scalar_quotes(value, &anchors) => {
+                    format!("{}{}{}", before, json_string(value), after)
+                }
+                _ => line.to_string(),
+            },
+        ))
+    }
+
+    fn priority(&self) -> u8 {
+        3
+    }
+
+    fn name(&self) -> &str {
+        "QuoteSpecialScalarsStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Quote values starting with a reserved indicator, undefined aliases and zero-padded numbers"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("user: @handle\nref: *missing\nzip: 01234")
+    }
+}
+
+/// Split a `key: value` or `- value` line into the text before the value, the
+/// value, and whatever follows it (trailing space and a ` #` comment)
+fn split_scalar_value(line: &str) -> Option<(&str, &str, &str)> {
+    let mut rest = line.trim_start();
+    if rest.starts_with('#') || rest == "---" {
+        return None;
+    }
+    let mut is_item = false;
+    while let Some(item) = rest.strip_prefix("- ") {
+        rest = item.trim_start();
+        is_item = true;
+    }
+    if let Some(i) = rest.find(": ") {
+        rest = rest[i + 2..].trim_start();
+    } else if !is_item {
+        return None;
+    }
+
+    let start = line.len() - rest.len();
+    let end = start + rest.split(" #").next().unwrap_or("").trim_end().len();
+    Some((&line[..start], &line[start..end], &line[end..]))
+}
+
+/// Names of the `&anchor`s defined anywhere in `content`
+fn anchor_names(content: &str) -> Vec<&str> {
+    content
+        .split_whitespace()
+        .filter_map(|token| token.strip_prefix('&'))
+        .filter(|name| is_property_name(name))
+        .collect()
+}
+
+fn is_property_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '/'))
+}
+
+/// True if the plain scalar `value` would be rejected by a YAML parser or read
+/// as something other than the text written
+fn needs_scalar_quotes(value: &str, anchors: &[&str]) -> bool {
+    let (token, tail) = value.split_once(' ').unwrap_or((value, ""));
+    match value.chars().next() {
+        // Reserved indicators and directives cannot start a plain scalar
+        Some('@' | '`' | '%') => true,
+        // An alias must name an anchor and be the whole value
+        Some('*') => !tail.is_empty() || !anchors.contains(&&token[1..]),
+        Some('&') => !is_property_name(&token[1..]),
+        // A bare `!tag` leaves the node empty, so the text is lost
+        Some('!') => {
+            let tag = &token[1..];
+            tail.is_empty()
+                || !(tag.is_empty() || is_property_name(tag.strip_prefix('!').unwrap_or(tag)))
         }
+        // YAML 1.1 reads `012` as octal and YAML 1.2 as 12; either drops the zero
+        _ => {
+            let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+            digits.len() > 1
+                && digits.starts_with('0')
+                && digits.bytes().all(|b| b.is_ascii_digit())
+        }
+    }
+}
+
+/// Strategy to fix indentation issues
+struct FixIndentationStrategy;
 
-        Ok(result.join("\n"))
+impl RepairStrategy for FixIndentationStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let mut tracker = IndentTracker::default();
+
+        Ok(map_lines_preserving_blocks(content, |line| {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                return line.to_string();
+            }
+
+            // Fix missing colons for key-value pairs
+            let fixed_trimmed =
+                if !trimmed.contains(':') && trimmed.contains(' ') && !trimmed.starts_with('-') {
+                    // This looks like a key-value pair missing a colon
+                    trimmed.replacen(' ', ": ", 1)
+                } else {
+                    trimmed.to_string()
+                };
+
+            tracker.normalize(line, &fixed_trimmed)
+        }))
     }
 
     fn priority(&self) -> u8 {
@@ -225,6 +701,14 @@ impl RepairStrategy for FixIndentationStrategy {
     fn name(&self) -> &str {
         "FixIndentationStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix indentation issues"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("server:\n   port: 80\n     host: local")
+    }
 }
 
 /// Strategy to add missing colons
@@ -233,19 +717,9 @@ struct AddMissingColonsStrategy;
 impl RepairStrategy for AddMissingColonsStrategy {
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_yaml_regex_cache();
-        let lines: Vec<&str> = content.lines().collect();
-        let mut result = Vec::new();
-
-        for line in lines {
-            if cache.missing_colons.is_match(line) {
-                let fixed = cache.missing_colons.replace(line, "$1$2: $3");
-                result.push(fixed.to_string());
-            } else {
-                result.push(line.to_string());
-            }
-        }
-
-        Ok(result.join("\n"))
+        Ok(map_lines_preserving_blocks(content, |line| {
+            cache.missing_colons.replace(line, "$1$2: $3").to_string()
+        }))
     }
 
     fn priority(&self) -> u8 {
@@ -255,6 +729,73 @@ impl RepairStrategy for AddMissingColonsStrategy {
     fn name(&self) -> &str {
         "AddMissingColonsStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Add missing colons"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("name John\nage 30")
+    }
+}
+
+/// Strategy to separate a key from its value (`name:John` -> `name: John`)
+struct SpaceAfterKeyColonStrategy;
+
+impl RepairStrategy for SpaceAfterKeyColonStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        Ok(map_lines_preserving_blocks(
+            content,
+            |line| match unspaced_key_colon(line) {
+                Some(i) => format!("{}: {}", &line[..i], &line[i + 1..]),
+                None => line.to_string(),
+            },
+        ))
+    }
+
+    fn priority(&self) -> u8 {
+        6
+    }
+
+    fn name(&self) -> &str {
+        "SpaceAfterKeyColonStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Add the space YAML requires after a mapping key's colon"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some(
+            "name:John
+age:30",
+        )
+    }
+}
+
+/// Byte index of the `:` in a `key:value` line written without the space that
+/// makes it a mapping. URLs, paths and times (`http://x`, `C:\dir`, `12:30`)
+/// are plain scalars, so colons in them, or after a proper `key: `, are skipped.
+fn unspaced_key_colon(line: &str) -> Option<usize> {
+    let mut rest = line.trim_start();
+    while let Some(item) = rest.strip_prefix("- ") {
+        rest = item.trim_start();
+    }
+    let colon = rest.find(':')?;
+    let key = &rest[..colon];
+    let next = rest[colon + 1..].chars().next()?;
+    let is_key = !key.is_empty()
+        && !key.starts_with('-')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if !is_key || key.bytes().all(|b| b.is_ascii_digit()) || next.is_whitespace() {
+        return None;
+    }
+    if matches!(next, ':' | '/' | '\\') {
+        return None;
+    }
+    Some(line.len() - rest.len() + colon)
 }
 
 /// Strategy to fix list formatting
@@ -263,19 +804,9 @@ struct FixListFormattingStrategy;
 impl RepairStrategy for FixListFormattingStrategy {
     fn apply(&self, content: &str) -> Result<String> {
         let cache = get_yaml_regex_cache();
-        let lines: Vec<&str> = content.lines().collect();
-        let mut result = Vec::new();
-
-        for line in lines {
-            if cache.list_items.is_match(line) {
-                let fixed = cache.list_items.replace(line, "- $1");
-                result.push(fixed.to_string());
-            } else {
-                result.push(line.to_string());
-            }
-        }
-
-        Ok(result.join("\n"))
+        Ok(map_lines_preserving_blocks(content, |line| {
+            cache.list_items.replace(line, "$1- $2").to_string()
+        }))
     }
 
     fn priority(&self) -> u8 {
@@ -285,6 +816,14 @@ impl RepairStrategy for FixListFormattingStrategy {
     fn name(&self) -> &str {
         "FixListFormattingStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix list formatting"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("items:\n-one\n-two")
+    }
 }
 
 /// Strategy to add document separator
@@ -307,6 +846,14 @@ impl RepairStrategy for AddDocumentSeparatorStrategy {
     fn name(&self) -> &str {
         "AddDocumentSeparatorStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Add document separator"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("name: app")
+    }
 }
 
 /// Strategy to fix quoted strings
@@ -316,8 +863,9 @@ impl RepairStrategy for FixQuotedStringsStrategy {
     fn apply(&self, content: &str) -> Result<String> {
         // Convert single quotes to double quotes
         let single_quote_re = Regex::new(r"'([^']*)'")?;
-        let result = single_quote_re.replace_all(content, r#""$1""#);
-        Ok(result.to_string())
+        Ok(map_lines_preserving_blocks(content, |line| {
+            single_quote_re.replace_all(line, r#""$1""#).to_string()
+        }))
     }
 
     fn priority(&self) -> u8 {
@@ -327,58 +875,72 @@ impl RepairStrategy for FixQuotedStringsStrategy {
     fn name(&self) -> &str {
         "FixQuotedStringsStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Fix quoted strings"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("name: 'John'")
+    }
 }
 
 /// Strategy for advanced indentation detection and fixing
+///
+/// Normalizes irregular indent widths to two spaces per nesting level while
+/// keeping children nested under their `key:` or list item.
 struct AdvancedIndentationStrategy;
 
 impl RepairStrategy for AdvancedIndentationStrategy {
     fn apply(&self, content: &str) -> Result<String> {
-        let lines: Vec<&str> = content.lines().collect();
-        let mut result = Vec::new();
-        let _indent_stack: Vec<usize> = Vec::new();
-        let mut current_indent = 0;
+        let mut tracker = IndentTracker::default();
 
-        for line in lines {
-            if line.trim().is_empty() || line.starts_with('#') {
-                result.push(line.to_string());
-                continue;
+        Ok(map_lines_preserving_blocks(content, |line| {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                return line.to_string();
             }
+            tracker.normalize(line, trimmed)
+        }))
+    }
 
-            let line_indent = line.chars().take_while(|c| c.is_whitespace()).count();
-            let trimmed = line.trim();
+    fn priority(&self) -> u8 {
+        6
+    }
 
-            // Detect list items
-            if let Some(stripped) = trimmed.strip_prefix('-') {
-                // List items should be indented 2 spaces more than their parent
-                let expected_indent = current_indent + 2;
-                if line_indent != expected_indent {
-                    let fixed = format!("{}- {}", " ".repeat(expected_indent), stripped.trim());
-                    result.push(fixed);
-                    current_indent = expected_indent;
-                } else {
-                    result.push(line.to_string());
-                    current_indent = line_indent;
-                }
-            } else if trimmed.contains(':') {
-                // Key-value pairs
-                let expected_indent = current_indent;
-                if line_indent != expected_indent {
-                    let fixed = format!("{}{}", " ".repeat(expected_indent), trimmed);
-                    result.push(fixed);
-                    current_indent = expected_indent;
-                } else {
-                    result.push(line.to_string());
-                    current_indent = line_indent;
-                }
-            } else {
-                // Other content - maintain relative indentation
-                result.push(line.to_string());
-                current_indent = line_indent;
-            }
-        }
+    fn name(&self) -> &str {
+        "AdvancedIndentationStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Normalize irregular indent widths to two spaces per level"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("server:\n    port: 80\n    tls:\n        on: true")
+    }
+}
+
+/// Strategy to re-indent tabs and mixed indent widths to a uniform two-space step.
+///
+/// Leading tabs are expanded to the document's own indent unit, then levels are
+/// rebuilt from the nesting, so 4-space and 2-space blocks end up consistent.
+struct NormalizeIndentUnitsStrategy;
+
+impl RepairStrategy for NormalizeIndentUnitsStrategy {
+    fn apply(&self, content: &str) -> Result<String> {
+        let step = indent_step(content);
+        let mut tracker = IndentTracker::default();
 
-        Ok(result.join("\n"))
+        Ok(map_lines_preserving_blocks(content, |line| {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                return line.to_string();
+            }
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let expanded = indent.replace('\t', &" ".repeat(step));
+            tracker.normalize(&format!("{}{}", expanded, trimmed), trimmed)
+        }))
     }
 
     fn priority(&self) -> u8 {
@@ -386,8 +948,34 @@ impl RepairStrategy for AdvancedIndentationStrategy {
     }
 
     fn name(&self) -> &str {
-        "AdvancedIndentationStrategy"
+        "NormalizeIndentUnitsStrategy"
+    }
+
+    fn description(&self) -> &str {
+        "Re-indent tabs and mixed indent widths to a uniform two-space step"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("server:\n    host: local\n    tls:\n\t\tenabled: true\nclient:\n  retries: 3")
+    }
+}
+
+/// The document's indent unit: the first increase in space indentation from one
+/// line to the next (lines indented with tabs are ignored), or 2 if nothing is nested
+fn indent_step(content: &str) -> usize {
+    let mut previous = 0;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let indent = &line[..line.len() - line.trim_start().len()];
+        if trimmed.is_empty() || trimmed.starts_with('#') || indent.contains('\t') {
+            continue;
+        }
+        if indent.len() > previous {
+            return indent.len() - previous;
+        }
+        previous = indent.len();
     }
+    2
 }
 
 /// Strategy for handling complex nested structures
@@ -395,71 +983,38 @@ struct ComplexStructureStrategy;
 
 impl RepairStrategy for ComplexStructureStrategy {
     fn apply(&self, content: &str) -> Result<String> {
-        let lines: Vec<&str> = content.lines().collect();
-        let mut result = Vec::new();
-        let mut in_multiline_string = false;
-        let mut multiline_indent = 0;
-
-        for line in lines.iter() {
+        // Block scalar bodies (`|` / `>`) are passed through untouched
+        Ok(map_lines_preserving_blocks(content, |line| {
             if line.trim().is_empty() || line.starts_with('#') {
-                result.push(line.to_string());
-                continue;
+                return line.to_string();
             }
 
             let trimmed = line.trim();
-
-            // Handle multiline strings
-            if trimmed.starts_with('|') || trimmed.starts_with('>') {
-                in_multiline_string = true;
-                multiline_indent = line.chars().take_while(|c| c.is_whitespace()).count();
-                result.push(line.to_string());
-                continue;
-            }
-
-            if in_multiline_string {
-                let line_indent = line.chars().take_while(|c| c.is_whitespace()).count();
-                if line_indent > multiline_indent || line.trim().is_empty() {
-                    result.push(line.to_string());
-                    continue;
-                } else {
-                    in_multiline_string = false;
-                }
-            }
+            let indent = &line[..line.len() - line.trim_start().len()];
 
             // Fix nested object/array structures
-            if trimmed.starts_with('-') && trimmed.contains(':') {
+            if let Some(item) = trimmed.strip_prefix('-')
+                && trimmed.contains(':')
+            {
                 // List item with key-value pair
-                let parts: Vec<&str> = trimmed.splitn(2, ':').collect();
-                if parts.len() == 2 {
-                    let key = parts[0].trim();
-                    let value = parts[1].trim();
-                    let fixed = format!("- {}: {}", key, value);
-                    result.push(fixed);
-                } else {
-                    result.push(line.to_string());
+                match item.split_once(':') {
+                    Some((key, value)) if !value.trim().is_empty() => {
+                        format!("{}- {}: {}", indent, key.trim(), value.trim())
+                    }
+                    _ => line.to_string(),
                 }
             } else if trimmed.contains(':') && !trimmed.ends_with(':') {
                 // Key-value pair
-                let parts: Vec<&str> = trimmed.splitn(2, ':').collect();
-                if parts.len() == 2 {
-                    let key = parts[0].trim();
-                    let value = parts[1].trim();
-                    if value.is_empty() {
-                        // Key with no value - might be a parent object
-                        result.push(line.to_string());
-                    } else {
-                        let fixed = format!("{}: {}", key, value);
-                        result.push(fixed);
-                    }
-                } else {
-                    result.push(line.to_string());
+                match trimmed.split_once(':') {
+                    // Key with no value - might be a parent object
+                    Some((_, value)) if value.trim().is_empty() => line.to_string(),
+                    Some((key, value)) => format!("{}{}: {}", indent, key.trim(), value.trim()),
+                    None => line.to_string(),
                 }
             } else {
-                result.push(line.to_string());
+                line.to_string()
             }
-        }
-
-        Ok(result.join("\n"))
+        }))
     }
 
     fn priority(&self) -> u8 {
@@ -469,4 +1024,12 @@ impl RepairStrategy for ComplexStructureStrategy {
     fn name(&self) -> &str {
         "ComplexStructureStrategy"
     }
+
+    fn description(&self) -> &str {
+        "Handle complex nested structures"
+    }
+
+    fn example(&self) -> Option<&str> {
+        Some("-  name :  Ann\nage :   30")
+    }
 }
@@ -0,0 +1,233 @@
+//! End-to-end tests piping data through the `anyrepair` binary
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Run the binary with `args`, feeding `stdin`, and return (stdout, stderr)
+fn run(args: &[&str], stdin: &str) -> (String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_anyrepair"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start anyrepair");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    (
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn test_repair_reads_stdin_and_writes_only_result() {
+    let (stdout, stderr) = run(&["repair"], "{\"a\": 1, \"b\": [1, 2,],}\n");
+    assert_eq!(stdout, "{\"a\": 1, \"b\": [1, 2]}\n");
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn test_verbose_repair_prints_summary() {
+    let (stdout, stderr) = run(&["--verbose", "repair"], "{\"a\": 1, \"b\": [1, 2,],}\n");
+    assert_eq!(stdout, "{\"a\": 1, \"b\": [1, 2]}\n");
+    let summary = stderr
+        .lines()
+        .find(|line| line.starts_with("Repair completed:"))
+        .unwrap_or_else(|| panic!("no summary in {stderr:?}"));
+    assert!(summary.contains("format json"), "{summary}");
+    assert!(summary.contains("changed yes"), "{summary}");
+    assert!(summary.contains("1 strategy applied"), "{summary}");
+}
+
+#[test]
+fn test_stdin_format_overrides_detection() {
+    // Without braces this is detected as YAML and left alone
+    let input = "\"a\": 1, \"b\": 2";
+    assert_eq!(run(&["repair"], input).0, input);
+
+    let (stdout, _) = run(&["repair", "--stdin-format", "json"], input);
+    assert_eq!(stdout, "{\"a\": 1, \"b\": 2}");
+}
+
+#[test]
+fn test_stream_uses_stdin_format() {
+    let (stdout, _) = run(
+        &["stream", "--stdin-format", "json"],
+        "{\"a\": 1,\n\"b\": 2,}\n",
+    );
+    assert_eq!(stdout, "{\"a\": 1,\n\"b\": 2}\n");
+}
+
+#[test]
+fn test_stream_auto_detects_large_json() {
+    let path = std::env::temp_dir().join(format!("anyrepair-stream-{}.txt", std::process::id()));
+    let mut input = String::from("[\n");
+    for i in 0..2000 {
+        input.push_str(&format!("  {{\"id\": {}, \"name\": 'item {}',}},\n", i, i));
+    }
+    input.push_str("]\n");
+    std::fs::write(&path, &input).unwrap();
+
+    let result = Command::new(env!("CARGO_BIN_EXE_anyrepair"))
+        .args([
+            "--verbose",
+            "stream",
+            "--format",
+            "auto",
+            "--buffer-size",
+            "1024",
+            "--input",
+        ])
+        .arg(&path)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let stdout = String::from_utf8(result.stdout).unwrap();
+    let stderr = String::from_utf8(result.stderr).unwrap();
+
+    assert!(result.status.success(), "{stderr}");
+    assert!(stderr.contains("Detected format: json"), "{stderr}");
+    assert!(
+        anyrepair::create_validator("json")
+            .unwrap()
+            .is_valid(&stdout)
+    );
+    assert!(stdout.contains(r#"{"id": 1999, "name": "item 1999"}"#));
+}
+
+#[test]
+fn test_rules_list_groups_strategies_by_format() {
+    let (stdout, _) = run(&["rules", "list"], "");
+    assert!(stdout.contains("json:\n"));
+    assert!(stdout.contains("yaml:\n"));
+    assert!(stdout.contains("FixTrailingCommas"));
+    assert!(stdout.contains("AddMissingColonsStrategy"));
+
+    let (stdout, _) = run(&["rules", "list", "--format", "yml"], "");
+    assert!(stdout.starts_with("yaml:\n"));
+    assert!(!stdout.contains("FixTrailingCommas"));
+}
+
+#[test]
+fn test_rules_show_prints_details() {
+    let (stdout, _) = run(&["rules", "show", "FixTrailingCommas"], "");
+    assert_eq!(
+        stdout,
+        "FixTrailingCommas\n  Format:      json\n  Priority:    90\n  \
+         Description: Fix trailing commas\n  Example input:\n    {\"a\": [1, 2,],}\n  \
+         Example output:\n    {\"a\": [1, 2]}\n"
+    );
+}
+
+#[test]
+fn test_batch_continue_on_error_reports_failures() {
+    let root = std::env::temp_dir().join(format!("anyrepair-batch-{}", std::process::id()));
+    let (input, output) = (root.join("in"), root.join("out"));
+    std::fs::create_dir_all(&input).unwrap();
+    std::fs::write(input.join("good.json"), "{\"a\": 1,}").unwrap();
+    std::fs::write(input.join("nested.json"), "[".repeat(600)).unwrap();
+    std::fs::write(input.join("other.json"), "[1, 2,]").unwrap();
+
+    let result = Command::new(env!("CARGO_BIN_EXE_anyrepair"))
+        .args(["batch", "--jobs", "2", "--continue-on-error", "--input"])
+        .arg(&input)
+        .arg("--output")
+        .arg(&output)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(result.stdout).unwrap();
+    let stderr = String::from_utf8(result.stderr).unwrap();
+
+    assert!(!result.status.success());
+    assert_eq!(stdout, "Processed 2 files\n");
+    assert!(stderr.contains("Failed to process 1 file(s):"), "{stderr}");
+    assert!(stderr.contains("nested.json"), "{stderr}");
+    assert_eq!(
+        std::fs::read_to_string(output.join("good.json")).unwrap(),
+        "{\"a\": 1}"
+    );
+    assert_eq!(
+        std::fs::read_to_string(output.join("other.json")).unwrap(),
+        "[1, 2]"
+    );
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_convert_broken_json_file_to_yaml() {
+    let path = std::env::temp_dir().join(format!("anyrepair-convert-{}.json", std::process::id()));
+    std::fs::write(&path, "{\"name\": 'Ann', tags: [\"a\", \"b\",],}\n").unwrap();
+
+    let result = Command::new(env!("CARGO_BIN_EXE_anyrepair"))
+        .args(["convert", "--from", "json", "--to", "yaml", "--input"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(result.status.success(), "{:?}", result);
+    assert_eq!(
+        String::from_utf8(result.stdout).unwrap(),
+        "name: Ann\ntags:\n  - a\n  - b\n"
+    );
+}
+
+#[test]
+fn test_convert_csv_file_to_json_with_auto_detection() {
+    let root = std::env::temp_dir().join(format!("anyrepair-convert-{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    let (input, output) = (root.join("people.csv"), root.join("people.json"));
+    std::fs::write(&input, "name,age\nAnn,30\nBob,25\n").unwrap();
+
+    let result = Command::new(env!("CARGO_BIN_EXE_anyrepair"))
+        .args(["convert", "--to", "json", "--input"])
+        .arg(&input)
+        .arg("--output")
+        .arg(&output)
+        .output()
+        .unwrap();
+
+    assert!(result.status.success(), "{:?}", result);
+    assert_eq!(
+        std::fs::read_to_string(&output).unwrap(),
+        "[{\"name\": \"Ann\", \"age\": 30}, {\"name\": \"Bob\", \"age\": 25}]\n"
+    );
+    std::fs::remove_dir_all(&root).unwrap();
+
+    // Formats without a converter are rejected
+    let result = Command::new(env!("CARGO_BIN_EXE_anyrepair"))
+        .args(["convert", "--from", "xml", "--to", "json"])
+        .output()
+        .unwrap();
+    assert!(!result.status.success());
+}
+
+#[test]
+fn test_config_enables_plugin_strategy() {
+    let path =
+        std::env::temp_dir().join(format!("anyrepair-cli-plugins-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        "[plugins.replace]\nformat = \"json\"\nfrom = \"TODO\"\nto = \"done\"\n",
+    )
+    .unwrap();
+    let (stdout, _) = run(
+        &[
+            "--config",
+            path.to_str().unwrap(),
+            "repair",
+            "--format",
+            "json",
+        ],
+        r#"{"status": "TODO",}"#,
+    );
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(stdout.trim(), r#"{"status": "done"}"#);
+}
@@ -32,7 +32,7 @@ mod json_fuzz_tests {
         fn test_json_confidence_bounds(input in prop::string::string_regex(".*").unwrap()) {
             let repairer = json::JsonRepairer::new();
             let confidence = repairer.confidence(&input);
-            prop_assert!(confidence >= 0.0 && confidence <= 1.0);
+            prop_assert!((0.0..=1.0).contains(&confidence));
         }
 
         #[test]
@@ -76,7 +76,7 @@ mod yaml_fuzz_tests {
         fn test_yaml_confidence_bounds(input in prop::string::string_regex(".*").unwrap()) {
             let repairer = yaml::YamlRepairer::new();
             let confidence = repairer.confidence(&input);
-            prop_assert!(confidence >= 0.0 && confidence <= 1.0);
+            prop_assert!((0.0..=1.0).contains(&confidence));
         }
     }
 }
@@ -107,7 +107,7 @@ mod markdown_fuzz_tests {
         fn test_markdown_confidence_bounds(input in prop::string::string_regex(".*").unwrap()) {
             let repairer = markdown::MarkdownRepairer::new();
             let confidence = repairer.confidence(&input);
-            prop_assert!(confidence >= 0.0 && confidence <= 1.0);
+            prop_assert!((0.0..=1.0).contains(&confidence));
         }
     }
 }
@@ -138,7 +138,7 @@ mod xml_fuzz_tests {
         fn test_xml_confidence_bounds(input in prop::string::string_regex(".*").unwrap()) {
             let repairer = xml::XmlRepairer::new();
             let confidence = repairer.confidence(&input);
-            prop_assert!(confidence >= 0.0 && confidence <= 1.0);
+            prop_assert!((0.0..=1.0).contains(&confidence));
         }
     }
 }
@@ -169,7 +169,7 @@ mod toml_fuzz_tests {
         fn test_toml_confidence_bounds(input in prop::string::string_regex(".*").unwrap()) {
             let repairer = toml::TomlRepairer::new();
             let confidence = repairer.confidence(&input);
-            prop_assert!(confidence >= 0.0 && confidence <= 1.0);
+            prop_assert!((0.0..=1.0).contains(&confidence));
         }
     }
 }
@@ -200,7 +200,7 @@ mod csv_fuzz_tests {
         fn test_csv_confidence_bounds(input in prop::string::string_regex(".*").unwrap()) {
             let repairer = csv::CsvRepairer::new();
             let confidence = repairer.confidence(&input);
-            prop_assert!(confidence >= 0.0 && confidence <= 1.0);
+            prop_assert!((0.0..=1.0).contains(&confidence));
         }
     }
 }
@@ -231,7 +231,7 @@ mod ini_fuzz_tests {
         fn test_ini_confidence_bounds(input in prop::string::string_regex(".*").unwrap()) {
             let repairer = key_value::IniRepairer::new();
             let confidence = repairer.confidence(&input);
-            prop_assert!(confidence >= 0.0 && confidence <= 1.0);
+            prop_assert!((0.0..=1.0).contains(&confidence));
         }
     }
 }
@@ -342,3 +342,57 @@ mod performance_fuzz_tests {
         }
     }
 }
+
+/// Fuzz every format with syntax characters mixed with multi-byte text, which
+/// reaches the char-scanning and slicing paths plain random strings rarely do
+#[cfg(test)]
+mod all_formats_fuzz_tests {
+    use super::*;
+    use anyrepair::{SUPPORTED_FORMATS, create_repairer};
+
+    const SYNTAX_HEAVY: &str = "([-:#{}\\[\\]\"',=<>;|&*!@%`\\\\/. \\t\\n\\r]|[a-z0-9]|é|ß|中|𐀀|\u{a0}|\u{3000}|\u{feff}){0,80}";
+
+    proptest! {
+        #[test]
+        fn test_every_format_repair_never_panics(input in prop::string::string_regex(SYNTAX_HEAVY).unwrap()) {
+            for format in SUPPORTED_FORMATS {
+                let _ = create_repairer(format).unwrap().repair(&input);
+            }
+        }
+
+        #[test]
+        fn test_json_lint_and_report_never_panic(input in prop::string::string_regex(SYNTAX_HEAVY).unwrap()) {
+            let mut repairer = json::JsonRepairer::new();
+            if let Ok(findings) = repairer.lint(&input) {
+                for finding in findings {
+                    assert!(input.get(finding.byte_range).is_some());
+                }
+            }
+            if let Ok(report) = repairer.repair_report(&input) {
+                for span in report.removed {
+                    assert!(input.get(span.byte_range).is_some());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_json_lint_of_bom_only_input_does_not_panic() {
+        // Ranges used to be offset past the BOM that a BOM-only input keeps
+        for input in ["\u{feff}", " \u{feff} ", "\u{feff}\n"] {
+            let repairer = json::JsonRepairer::new();
+            for finding in repairer.lint(input).unwrap() {
+                assert!(input.get(finding.byte_range).is_some(), "{:?}", input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_yaml_block_scalar_with_multibyte_indent_does_not_panic() {
+        // The header is re-indented, and the body's non-breaking-space indent used
+        // to be sliced by char count instead of byte offset
+        let input = "k:\n\u{a0}\u{a0}\u{a0}a: |\n\u{a0}\u{a0}\u{a0}\u{a0}x\nb:c: [";
+        let result = yaml::YamlRepairer::new().repair(input).unwrap();
+        assert!(result.contains("\u{a0}\u{a0}\u{a0}x"));
+    }
+}
@@ -0,0 +1,69 @@
+//! Tests for the GeoJSON repair profile (requires `--features geojson`).
+//! Run with: `cargo test --features geojson --test geojson_tests`
+
+#![cfg(feature = "geojson")]
+
+use anyrepair::traits::Repair;
+use anyrepair::{JsonProfile, JsonRepairer};
+
+#[test]
+fn trailing_comma_feature_is_repaired_and_completed() {
+    let mut repairer = JsonRepairer::with_profile(JsonProfile::GeoJson);
+    let input =
+        r#"{"type": "feature", "geometry": {"type": "Point", "coordinates": [102.0, 0.5],},}"#;
+    let repaired = repairer.repair(input).unwrap();
+    assert_eq!(
+        repaired,
+        r#"{"type": "Feature", "geometry": {"type": "Point", "coordinates": [102.0, 0.5]}, "properties": {}}"#
+    );
+    assert!(JsonProfile::GeoJson.validate(&repaired).unwrap().is_empty());
+
+    let report = repairer.repair_report(input).unwrap();
+    assert_eq!(
+        report.applied_strategies,
+        vec!["FixTrailingCommas", "GeoJsonProfile"]
+    );
+}
+
+#[test]
+fn bare_coordinates_become_point_feature() {
+    let mut repairer = JsonRepairer::with_profile(JsonProfile::GeoJson);
+    assert_eq!(
+        repairer.repair("[-0.12, 51.5,]").unwrap(),
+        r#"{"type": "Feature", "geometry": {"type": "Point", "coordinates": [-0.12, 51.5]}, "properties": {}}"#
+    );
+    assert_eq!(
+        repairer.repair(r#"{"features": [[1, 2]]}"#).unwrap(),
+        r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "geometry": {"type": "Point", "coordinates": [1, 2]}, "properties": {}}]}"#
+    );
+}
+
+#[test]
+fn missing_type_is_flagged_when_ambiguous() {
+    let mut repairer = JsonRepairer::with_profile(JsonProfile::GeoJson);
+    // Two levels of positions could be a LineString or a MultiPoint
+    let input = r#"{"coordinates": [[0, 0], [1, 1]]}"#;
+    assert_eq!(repairer.repair(input).unwrap(), input);
+    assert_eq!(
+        JsonProfile::GeoJson.validate(input).unwrap(),
+        vec![r#"/: missing "type""#]
+    );
+
+    let collection = r#"{"type": "FeatureCollection", "features": [{"geometry": null, "properties": null}, {"type": "Polygon", "coordinates": [0, 0]}]}"#;
+    assert_eq!(
+        JsonProfile::GeoJson.validate(collection).unwrap(),
+        vec![
+            r#"/features/0: missing "type""#,
+            "/features/1/coordinates: not valid Polygon coordinates",
+        ]
+    );
+}
+
+#[test]
+fn valid_geojson_is_untouched() {
+    let mut repairer = JsonRepairer::with_profile(JsonProfile::GeoJson);
+    let input = r#"{"type": "LineString", "coordinates": [[0, 0], [1, 1]]}"#;
+    assert_eq!(repairer.repair(input).unwrap(), input);
+    assert!(JsonProfile::GeoJson.validate(input).unwrap().is_empty());
+    assert!(JsonProfile::GeoJson.validate("{").is_err());
+}
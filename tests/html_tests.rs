@@ -0,0 +1,59 @@
+//! Tests for the HTML fragment mode of the XML repairer
+
+use anyrepair::Repair;
+use anyrepair::traits::Validator;
+use anyrepair::xml::{HtmlValidator, XmlRepairer};
+
+#[test]
+fn test_html_void_element_not_closed() {
+    let mut repairer = XmlRepairer::with_html_mode(true);
+    let result = repairer.repair("<p>line one<br>line two</p>").unwrap();
+    assert_eq!(result, "<p>line one<br>line two</p>");
+    assert!(!result.contains("</br>"));
+}
+
+#[test]
+fn test_html_unclosed_div_gets_closed() {
+    let mut repairer = XmlRepairer::with_html_mode(true);
+    let result = repairer.repair("<div><img src=\"a.png\"><br>text").unwrap();
+    assert_eq!(result, "<div><img src=\"a.png\"><br>text</div>");
+}
+
+#[test]
+fn test_html_mode_skips_xml_declaration() {
+    let mut repairer = XmlRepairer::with_html_mode(true);
+    let result = repairer.repair("<ul><li>one</li>").unwrap();
+    assert!(!result.starts_with("<?xml"));
+    assert_eq!(result, "<ul><li>one</li></ul>");
+}
+
+#[test]
+fn test_html_boolean_attributes_tolerated() {
+    let v = HtmlValidator {
+        lowercase_tags: false,
+    };
+    assert!(v.is_valid("<input type=\"checkbox\" checked disabled>"));
+    assert!(v.is_valid("<DIV>mixed case</div>"));
+    assert!(!v.is_valid("<input type=checkbox>"));
+}
+
+#[test]
+fn test_html_unquoted_attribute_repaired() {
+    let mut repairer = XmlRepairer::with_html_mode(true);
+    let result = repairer.repair("<a href=index.html>home</a>").unwrap();
+    assert_eq!(result, "<a href=\"index.html\">home</a>");
+}
+
+#[test]
+fn test_html_lowercase_tags() {
+    let mut repairer = XmlRepairer::with_html_options(true, true);
+    let result = repairer.repair("<DIV CLASS=\"x\"><BR>Hi</DIV>").unwrap();
+    assert_eq!(result, "<div CLASS=\"x\"><br>Hi</div>");
+}
+
+#[test]
+fn test_xml_mode_unchanged() {
+    let mut repairer = XmlRepairer::with_html_mode(false);
+    let result = repairer.repair("<root><item>x</item>").unwrap();
+    assert!(result.starts_with("<?xml"));
+}
@@ -1,6 +1,10 @@
 //! Integration tests for the anyrepair library
 
-use anyrepair::{csv, diff, json, key_value, markdown, repair, toml, traits::Repair, xml, yaml};
+use anyrepair::{
+    csv, diff, json, key_value, markdown, repair, toml,
+    traits::{Repair, Validator},
+    xml, yaml,
+};
 
 #[test]
 fn test_library_integration() {
@@ -180,6 +184,32 @@ fn test_xml_edge_cases() {
     assert!(result2.contains("\"") || result2.contains("item"));
 }
 
+#[test]
+fn test_xml_attribute_quotes_normalized() {
+    let mut xml_repairer = xml::XmlRepairer::new();
+
+    // Single-quoted declaration
+    assert_eq!(
+        xml_repairer
+            .repair("<?xml version='1.0' encoding='UTF-8'?>\n<root/>")
+            .unwrap(),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<root/>"
+    );
+
+    // Mismatched open/close quotes, in the declaration and an attribute
+    let input = "<?xml version=\"1.0'?>\n<item id=\"1' name='ann'>x</item>";
+    let result = xml_repairer.repair(input).unwrap();
+    assert_eq!(
+        result,
+        "<?xml version=\"1.0\"?>\n<item id=\"1\" name=\"ann\">x</item>"
+    );
+    assert!(xml::XmlValidator.is_valid(&result));
+
+    // Well-formed single-quoted attributes with double quotes inside are left alone
+    let input = "<?xml version=\"1.0\"?>\n<p title='say \"hi\"'>x</p>";
+    assert_eq!(xml_repairer.repair(input).unwrap(), input);
+}
+
 #[test]
 fn test_toml_edge_cases() {
     let mut toml_repairer = toml::TomlRepairer::new();
@@ -210,6 +240,41 @@ fn test_csv_edge_cases() {
     assert!(result2.lines().count() >= 2);
 }
 
+#[test]
+fn test_structured_confidence_parses_content() {
+    let cases: [(Box<dyn Repair>, &str, &str, &str); 3] = [
+        (
+            Box::new(xml::XmlRepairer::new()),
+            "<root><item id=\"1\">a</item></root>",
+            "<root><item>a</item><item>b</root>",
+            "not < xml > at all",
+        ),
+        (
+            Box::new(toml::TomlRepairer::new()),
+            "[server]\nport = 80\nhost = \"local\"",
+            "[server]\nport = 80\nhost = local",
+            "port = ; host = ",
+        ),
+        (
+            Box::new(csv::CsvRepairer::new()),
+            "name,age\nAnn,30\nBob,25",
+            "name,age\nAnn,30\nBob,25,extra",
+            "\"unterminated,\n\"again",
+        ),
+    ];
+
+    for (repairer, valid, malformed, garbage) in &cases {
+        let (valid, malformed, garbage) = (
+            repairer.confidence(valid),
+            repairer.confidence(malformed),
+            repairer.confidence(garbage),
+        );
+        assert_eq!(valid, 1.0);
+        assert!(malformed < valid, "{malformed} >= {valid}");
+        assert!(garbage < malformed, "{garbage} >= {malformed}");
+    }
+}
+
 #[test]
 fn test_ini_edge_cases() {
     let mut ini_repairer = key_value::IniRepairer::new();
@@ -389,3 +454,158 @@ fn test_detect_format_with_confidence_api() {
 
     assert!(detect_format_with_confidence("plain text only").is_none());
 }
+
+#[test]
+fn test_builders_configure_repairers() {
+    let mut html = xml::XmlRepairer::builder()
+        .html_mode(true)
+        .lowercase_tags(true)
+        .build();
+    assert_eq!(html.repair("<Div><b>x</b>").unwrap(), "<div><b>x</b></div>");
+
+    let mut ini = key_value::IniRepairer::builder()
+        .comment_marker('#')
+        .build();
+    assert_eq!(ini.repair("[a]\n; note\nx=1").unwrap(), "[a]\n# note\nx=1");
+
+    let mut toml = toml::TomlRepairer::builder().max_depth(Some(1)).build();
+    assert!(toml.repair("a = [[1]]").is_err());
+}
+
+#[test]
+fn test_depth_limit_only_applies_to_bracket_formats() {
+    let brackets = "[".repeat(600);
+    let prose = format!("# Notes\n\n{}", brackets);
+    assert!(markdown::MarkdownRepairer::new().repair(&prose).is_ok());
+    let cell = format!("a,b\n1,{}", brackets);
+    assert!(csv::CsvRepairer::new().repair(&cell).is_ok());
+    let value = format!("[s]\nx={}", brackets);
+    assert!(key_value::IniRepairer::new().repair(&value).is_ok());
+
+    assert!(matches!(
+        json::JsonRepairer::new().repair(&brackets),
+        Err(anyrepair::error::RepairError::MaxDepthExceeded { .. })
+    ));
+    let mut markdown = markdown::MarkdownRepairer::builder()
+        .max_depth(Some(10))
+        .build();
+    assert!(markdown.repair(&prose).is_err());
+}
+
+#[test]
+fn test_csv_unterminated_quoted_field_closed_at_row_end() {
+    let mut repairer = csv::CsvRepairer::new();
+    assert_eq!(
+        repairer
+            .repair("name,city\nJohn,\"New York\nJane,Paris")
+            .unwrap(),
+        "name,city\nJohn,\"New York\"\nJane,Paris"
+    );
+    // Closing at the row end would leave one field, so the comma separates
+    assert_eq!(
+        repairer.repair("name,age\n\"John,30\nJane,25").unwrap(),
+        "name,age\nJohn,30\nJane,25"
+    );
+}
+
+#[test]
+fn test_csv_stray_quote_mid_field_escaped() {
+    let mut repairer = csv::CsvRepairer::new();
+    assert_eq!(
+        repairer
+            .repair("item,size\nTV,55\" screen\nJohn\",x")
+            .unwrap(),
+        "item,size\nTV,\"55\"\" screen\"\n\"John\"\"\",x"
+    );
+}
+
+#[test]
+fn test_csv_quoted_multiline_field_kept_as_one_record() {
+    let input = "id,note,owner\n1,\"first line\nsecond line\",ann\n2,plain,bob\n3,extra,,cy";
+    let mut repairer = csv::CsvRepairer::new();
+    let repaired = repairer.repair(input).unwrap();
+    assert_eq!(
+        repaired,
+        "id,note,owner\n1,\"first line\nsecond line\",ann\n2,plain,bob\n3,extra,cy"
+    );
+    assert!(csv::CsvValidator.is_valid(&repaired));
+}
+
+#[test]
+fn test_csv_unquoted_stray_newline_rejoined() {
+    let input = "name,age,city\nAnn,30,New\nYork\nBob,25,Rome";
+    let mut repairer = csv::CsvRepairer::new();
+    let repaired = repairer.repair(input).unwrap();
+    assert_eq!(repaired, "name,age,city\nAnn,30,\"New York\"\nBob,25,Rome");
+    assert!(csv::CsvValidator.is_valid(&repaired));
+
+    // The break can fall before any field, too
+    assert_eq!(
+        repairer.repair("name,age,city\nAnn,\n30,Paris").unwrap(),
+        "name,age,city\nAnn,30,Paris"
+    );
+}
+
+#[test]
+fn test_csv_max_field_len_stops_runaway_quoted_field() {
+    let mut input = String::from("id,name,note\n1,\"Ann,first\n");
+    for i in 2..=101 {
+        input.push_str(&format!("{i},name{i},ok\n"));
+    }
+    input.push_str("102,\"Zed\",last");
+
+    // Without a limit the open quote on row 1 runs until the quote on row 102,
+    // swallowing the rows in between into one field
+    let mut unlimited = csv::CsvRepairer::new();
+    let swallowed = unlimited.repair(&input).unwrap();
+    assert_eq!(swallowed.lines().nth(1), Some("1,\"Ann,first"));
+
+    let mut repairer = csv::CsvRepairer::with_max_field_len(256);
+    let repaired = repairer.repair(&input).unwrap();
+    let rows: Vec<&str> = repaired.lines().collect();
+    assert_eq!(rows.len(), 103);
+    assert_eq!(rows[1], "1,Ann,first");
+    assert_eq!(rows[50], "50,name50,ok");
+    assert_eq!(rows[102], "102,Zed,last");
+    assert_eq!(repairer.confidence(&repaired), 1.0);
+}
+
+#[test]
+fn test_is_valid_per_format() {
+    use anyrepair::{is_valid, is_valid_auto};
+
+    let cases = [
+        ("json", r#"{"a": 1}"#, r#"{"a": 1,}"#),
+        (
+            "yaml",
+            "name: app\nport: 80",
+            "name: app\n  port: 80\n -bad",
+        ),
+        ("markdown", "# Title\n\nText", "##Title"),
+        ("xml", "<a><b>1</b></a>", "<a><b>1</a>"),
+        ("toml", "[server]\nport = 80", "[server]\nname = web app"),
+        ("csv", "a,b\n1,2", "a,b\n1,\"2"),
+        ("ini", "[main]\nkey=value", "[main\nkey=value"),
+        ("env", "KEY=value", "KEY value"),
+        ("dotenv", "KEY=\"two words\"", "KEY=two words"),
+        ("properties", "key=value", "key value\\"),
+        (
+            "diff",
+            "--- a/f\n+++ b/f\n@@ -1 +1 @@\n-old\n+new",
+            "--- a/f\n+++ b/f\n@@ -1 +1\n-old\n+new",
+        ),
+    ];
+    for (format, valid, invalid) in cases {
+        assert!(is_valid(valid, format), "{format} should accept {valid:?}");
+        assert!(
+            !is_valid(invalid, format),
+            "{format} should reject {invalid:?}"
+        );
+    }
+    assert!(is_valid("a: 1", "YML"));
+    assert!(!is_valid("{}", "unknown"));
+
+    assert_eq!(is_valid_auto(r#"{"a": [1, 2]}"#), Some("json"));
+    assert_eq!(is_valid_auto("<root><item/></root>"), Some("xml"));
+    assert_eq!(is_valid_auto(r#"{"a": [1, 2}"#), None);
+}
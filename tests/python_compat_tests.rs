@@ -0,0 +1,46 @@
+//! Compatibility with Python's `json_repair`.
+//!
+//! Each `tests/fixtures/python_json_repair/<case>.input.txt` is repaired with
+//! [`jsonrepair`] and compared with `<case>.expected.json`, the output of
+//! `json_repair.repair_json` for the same input. Documents are compared as values
+//! (whitespace, key order and number spelling aside), so a failure is a real
+//! divergence from the Python library.
+
+use anyrepair::{jsonrepair, validate_schema};
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn matches_python_json_repair() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/python_json_repair");
+    let mut cases: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().ends_with(".input.txt"))
+        .collect();
+    cases.sort();
+    assert!(cases.len() >= 15, "only {} fixtures found", cases.len());
+
+    let mut divergences = Vec::new();
+    for input_path in &cases {
+        let name = input_path.file_name().unwrap().to_string_lossy();
+        let name = name.trim_end_matches(".input.txt");
+        let input = fs::read_to_string(input_path).unwrap();
+        let expected = fs::read_to_string(dir.join(format!("{}.expected.json", name))).unwrap();
+
+        let repaired = jsonrepair(&input).unwrap();
+        // `const` compares structurally, which is the value equality we want
+        let schema = format!(r#"{{"const": {}}}"#, expected.trim());
+        match validate_schema(&repaired, &schema) {
+            Ok(violations) if violations.is_empty() => {}
+            _ => divergences.push(format!(
+                "{}: expected {}, got {}",
+                name,
+                expected.trim(),
+                repaired
+            )),
+        }
+    }
+
+    assert!(divergences.is_empty(), "{}", divergences.join("\n"));
+}
@@ -0,0 +1,88 @@
+//! Tests for custom format detectors and repairers registered at runtime
+
+use anyrepair::{
+    FormatDetector, Repair, create_repairer, detect_format, detect_format_with_confidence,
+    register_detector, register_format, register_repairer, repair,
+};
+
+/// Made-up format: lines of `@key value`, optionally preceded by a `%%ACME` banner
+struct AcmeDetector;
+
+impl FormatDetector for AcmeDetector {
+    fn detect(&self, content: &str) -> Option<(String, f64)> {
+        content
+            .starts_with("%%ACME")
+            .then(|| ("acme".to_string(), 0.99))
+    }
+}
+
+struct AcmeRepairer;
+
+impl Repair for AcmeRepairer {
+    fn repair(&mut self, content: &str) -> anyrepair::Result<String> {
+        Ok(content.replace("@ ", "@"))
+    }
+
+    fn needs_repair(&self, content: &str) -> bool {
+        content.contains("@ ")
+    }
+
+    fn confidence(&self, _content: &str) -> f64 {
+        1.0
+    }
+}
+
+/// Detector that always matches, but with low confidence
+struct WeakDetector;
+
+impl FormatDetector for WeakDetector {
+    fn detect(&self, _content: &str) -> Option<(String, f64)> {
+        Some(("weak".to_string(), 0.1))
+    }
+}
+
+/// Detector that registers a repairer the first time it runs
+struct SelfRegisteringDetector;
+
+impl FormatDetector for SelfRegisteringDetector {
+    fn detect(&self, content: &str) -> Option<(String, f64)> {
+        register_repairer("lazy", || Box::new(AcmeRepairer));
+        content
+            .starts_with("%%LAZY")
+            .then(|| ("lazy".to_string(), 0.99))
+    }
+}
+
+#[test]
+fn test_custom_detector_wins_on_matching_input() {
+    register_format("acme", Box::new(AcmeDetector), || Box::new(AcmeRepairer));
+
+    // `key: value` lines alone would be detected as YAML
+    let input = "%%ACME\n@ name: widget";
+    assert_eq!(detect_format(input), Some("acme"));
+    let result = detect_format_with_confidence(input).unwrap();
+    assert_eq!(result.confidence, 0.99);
+
+    assert_eq!(repair(input).unwrap(), "%%ACME\n@name: widget");
+    assert!(create_repairer("ACME").is_ok());
+}
+
+#[test]
+fn test_low_scoring_detector_does_not_override_builtin() {
+    register_detector(Box::new(WeakDetector));
+
+    assert_eq!(detect_format(r#"{"key": "value"}"#), Some("json"));
+    assert_eq!(detect_format("name: John\nage: 30"), Some("yaml"));
+
+    // `weak` has no repairer, so prose is still repaired as Markdown
+    let prose = "just some notes";
+    assert_eq!(detect_format(prose), None);
+    assert_eq!(repair(prose).unwrap(), prose);
+}
+
+#[test]
+fn test_detector_may_register_formats() {
+    register_detector(Box::new(SelfRegisteringDetector));
+
+    assert_eq!(repair("%%LAZY\n@ a").unwrap(), "%%LAZY\n@a");
+}
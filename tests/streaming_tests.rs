@@ -267,6 +267,21 @@ fn test_streaming_csv_quoted_fields() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_streaming_csv_chunk_does_not_split_quoted_field() {
+    let mut input = String::from("id,note\n");
+    for i in 0..20 {
+        input.push_str(&format!("{i},\"line one\nline two of {i}\"\n"));
+    }
+
+    let reader = Cursor::new(input.clone());
+    let mut output = Vec::new();
+    let processor = StreamingRepair::with_buffer_size(64);
+    processor.process(reader, &mut output, "csv").unwrap();
+
+    assert_eq!(String::from_utf8(output).unwrap(), input);
+}
+
 #[test]
 fn test_streaming_xml_attributes() {
     let input = "<root>\n  <item id=\"1\" name=\"first\">Content</item>\n</root>";
@@ -66,3 +66,14 @@ fn strict_json_nested_structures() {
     assert!(v.is_valid(r#"{"a": {"b": {"c": [1, 2, {"d": true}]}}}"#));
     assert!(!v.is_valid(r#"{"a": {"b": {"c": [1, 2, {"d": true,}]}}}"#));
 }
+
+#[test]
+fn strict_json_depth_limit_reported_as_max_depth() {
+    let v = create_validator("json").unwrap();
+    let deep = format!("{}{}", "[".repeat(50_000), "]".repeat(50_000));
+    assert!(!v.is_valid(&deep));
+    assert_eq!(
+        v.validate(&deep),
+        vec!["Nesting depth 50000 exceeds the limit of 128"]
+    );
+}
@@ -0,0 +1,53 @@
+//! TOML repair tests that exercise the (private) TOML strategies end to end
+
+use anyrepair::Repair;
+use anyrepair::toml::{TomlRepairer, TomlValidator};
+use anyrepair::traits::Validator;
+
+#[test]
+fn test_semicolon_comment_converted_to_hash() {
+    let input = "[server]\n; listen address\nhost = \"127.0.0.1;8080\"\nport = 8080";
+    let mut repairer = TomlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+
+    assert_eq!(
+        result,
+        "[server]\n# listen address\nhost = \"127.0.0.1;8080\"\nport = 8080"
+    );
+    assert!(TomlValidator.is_valid(&result));
+}
+
+#[test]
+fn test_semicolon_inside_multiline_string_untouched() {
+    let input = "[doc]\n; about\ntext = \"\"\"\n; not a comment\n\"\"\"";
+    let mut repairer = TomlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+
+    assert!(result.contains("# about"));
+    assert!(result.contains("\n; not a comment\n"));
+}
+
+#[test]
+fn test_key_with_spaces_quoted() {
+    let input = "[server]\nmy key = 1\nsite.host name = \"example.com\"";
+    assert!(!TomlValidator.is_valid(input));
+
+    let mut repairer = TomlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+    assert_eq!(
+        result,
+        "[server]\n\"my key\" = 1\nsite.\"host name\" = \"example.com\""
+    );
+    assert!(TomlValidator.is_valid(&result));
+}
+
+#[test]
+fn test_valid_dotted_keys_untouched() {
+    let input = "[package]\nname = \"app\"\nmetadata.build-id = 7\nsite . \"host name\" = true";
+    assert!(TomlValidator.is_valid(input));
+
+    let mut repairer = TomlRepairer::new();
+    assert_eq!(repairer.repair(input).unwrap(), input);
+    // An empty segment can't be fixed by quoting and is left alone
+    assert!(!TomlValidator.is_valid("[a]\nx..y = 1"));
+}
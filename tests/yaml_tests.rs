@@ -0,0 +1,229 @@
+//! YAML repair tests that exercise the (private) YAML strategies end to end
+
+use anyrepair::Repair;
+use anyrepair::traits::Validator;
+use anyrepair::yaml::{ImplicitBoolPolicy, YamlRepairer, YamlValidator};
+
+#[test]
+fn test_literal_block_preserved_without_colon_insertion() {
+    let input =
+        "name John\ntext: |\n  This is a\n  multiline string\n\n  second paragraph\nage: 30";
+    let mut repairer = YamlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+
+    assert_eq!(
+        result,
+        "---\nname: John\ntext: |\n  This is a\n  multiline string\n\n  second paragraph\nage: 30"
+    );
+}
+
+#[test]
+fn test_folded_block_with_chomping_indicator_preserved() {
+    let input = "title Report\nsummary: >-\n  word word\n  more words here\nfooter: done";
+    let mut repairer = YamlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+
+    assert!(result.contains("title: Report"));
+    assert!(result.contains("summary: >-\n  word word\n  more words here\n"));
+    assert!(!result.contains("word: word"));
+}
+
+#[test]
+fn test_validator_accepts_block_scalar_bodies() {
+    let validator = YamlValidator;
+    assert!(validator.is_valid("text: |\n  plain words without colons\n  another line"));
+    assert!(validator.is_valid("- |\n  list item block\n  still block"));
+    assert!(!validator.is_valid("text: |\n  inside\nplain words outside"));
+}
+
+#[test]
+fn test_nested_mappings_keep_their_indentation() {
+    let input = "person:\n  name John\n  address:\n    city Paris\n    zip: 75001\nactive: true";
+    let mut repairer = YamlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+
+    assert_eq!(
+        result,
+        "---\nperson:\n  name: John\n  address:\n    city: Paris\n    zip: 75001\nactive: true"
+    );
+}
+
+#[test]
+fn test_nested_lists_round_trip_structure() {
+    let input =
+        "team:\n  lead: Ann\n  members:\n    - Bob\n    - name: Carl\n      role: dev\nbroken line";
+    let mut repairer = YamlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+
+    assert_eq!(
+        result,
+        "---\nteam:\n  lead: Ann\n  members:\n    - Bob\n    - name: Carl\n      role: dev\nbroken: line"
+    );
+    assert!(YamlValidator.is_valid(&result));
+}
+
+#[test]
+fn test_anchors_aliases_and_merge_keys_preserved() {
+    let input = "defaults: &defaults\n  adapter: postgres\n  host: localhost\ndevelopment:\n  <<: *defaults\n  database: dev_db\nservers:\n  - *defaults\n  - &extra x\nbroken line";
+    let mut repairer = YamlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+
+    assert_eq!(
+        result,
+        "---\ndefaults: &defaults\n  adapter: postgres\n  host: localhost\ndevelopment:\n  <<: *defaults\n  database: dev_db\nservers:\n  - *defaults\n  - &extra x\nbroken: line"
+    );
+}
+
+#[test]
+fn test_anchor_and_merge_key_missing_colons() {
+    let input = "defaults &defaults\n  adapter: postgres\nprod:\n  << *defaults\n  host: db";
+    let mut repairer = YamlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+
+    assert_eq!(
+        result,
+        "---\ndefaults: &defaults\n  adapter: postgres\nprod:\n  <<: *defaults\n  host: db"
+    );
+}
+
+#[test]
+fn test_semicolon_comment_converted_to_hash() {
+    let input = "; settings\nname: a;b\ntext: |\n  ; kept in block\nage: 30";
+    let mut repairer = YamlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+
+    assert_eq!(
+        result,
+        "---\n# settings\nname: a;b\ntext: |\n  ; kept in block\nage: 30"
+    );
+}
+
+#[test]
+fn test_statement_terminators_stripped() {
+    let mut repairer = YamlRepairer::new();
+    assert_eq!(repairer.repair("a: 1;").unwrap(), "---\na: 1");
+
+    let input = "name: a;b\nitems:\n  - x;\nquote: \"c;\"\ntext: |\n  kept;";
+    assert_eq!(
+        repairer.repair(input).unwrap(),
+        "---\nname: a;b\nitems:\n  - x\nquote: \"c;\"\ntext: |\n  kept;"
+    );
+}
+
+#[test]
+fn test_multi_document_stream_repaired_per_document() {
+    let first = "apiVersion: v1\nkind: ConfigMap\ndata:\n  motd: |\n    hello\n    world";
+    let input = format!("{first}\n---\napiVersion: v1\nkind Service\nmetadata:\n  name: web\n");
+    let mut repairer = YamlRepairer::new();
+    let result = repairer.repair(&input).unwrap();
+
+    assert_eq!(
+        result,
+        format!("{first}\n---\napiVersion: v1\nkind: Service\nmetadata:\n  name: web\n")
+    );
+    // A stream that opens with a separator keeps it, and valid streams are untouched
+    let stream = "---\na: 1\n---\nb: 2";
+    assert_eq!(repairer.repair(stream).unwrap(), stream);
+}
+
+#[test]
+fn test_implicit_bool_policy_quotes_ambiguous_values() {
+    let input = "country: no\nflags:\n  - Yes\n  - off # legacy\nenabled: true\nnote: say no\nraw: |\n  on\n";
+    let mut repairer = YamlRepairer::with_implicit_bool_policy(ImplicitBoolPolicy::QuoteAmbiguous);
+    assert_eq!(
+        repairer.repair(input).unwrap(),
+        "country: \"no\"\nflags:\n  - \"Yes\"\n  - \"off\" # legacy\nenabled: true\nnote: say no\nraw: |\n  on\n"
+    );
+
+    // The default leaves valid YAML alone
+    assert_eq!(
+        YamlRepairer::new().repair("country: no").unwrap(),
+        "country: no"
+    );
+    let mut repairer = YamlRepairer::with_implicit_bool_policy(ImplicitBoolPolicy::LeaveAsIs);
+    assert_eq!(repairer.repair("country: no").unwrap(), "country: no");
+}
+
+#[test]
+fn test_mixed_indent_units_normalized_to_two_spaces() {
+    // 4-space nesting under `server`, a stray 2-space key and a tab-indented block
+    let input = "server:\n    host: local\n    ports:\n      - 80\n    tls:\n\t\tenabled: true\n  retries: 3\nclient:\n  timeout: 5\n";
+    assert!(!YamlValidator.is_valid(input));
+
+    let result = YamlRepairer::new().repair(input).unwrap();
+    assert_eq!(
+        result,
+        "---\nserver:\n  host: local\n  ports:\n    - 80\n  tls:\n    enabled: true\n  retries: 3\nclient:\n  timeout: 5\n"
+    );
+    assert!(YamlValidator.is_valid(&result));
+}
+
+#[test]
+fn test_builder_applies_implicit_bool_policy_and_bom() {
+    let mut repairer = YamlRepairer::builder()
+        .implicit_bool_policy(ImplicitBoolPolicy::QuoteAmbiguous)
+        .preserve_bom(true)
+        .build();
+    assert_eq!(
+        repairer.repair("\u{feff}country: no\n").unwrap(),
+        "\u{feff}country: \"no\"\n"
+    );
+}
+
+#[test]
+fn test_special_scalars_quoted() {
+    let input = "user: @handle\nref: *missing\nnote: *bold* text\nzip: 01234\nteam:\n  - @ann\n  - 007 # agent\nbase: &base\n  size: 1\ncopy: *base\nbucket: !Ref logs";
+    let mut repairer = YamlRepairer::new();
+    let result = repairer.repair(input).unwrap();
+
+    assert_eq!(
+        result,
+        "---\nuser: \"@handle\"\nref: \"*missing\"\nnote: \"*bold* text\"\nzip: \"01234\"\nteam:\n  - \"@ann\"\n  - \"007\" # agent\nbase: &base\n  size: 1\ncopy: *base\nbucket: !Ref logs"
+    );
+    assert!(YamlValidator.is_valid(&result));
+
+    // The quoted values parse back as strings
+    let repaired = repairer
+        .repair("user: @handle\nref: *missing\nzip: 01234")
+        .unwrap();
+    assert_eq!(
+        anyrepair::convert::yaml_to_json(&repaired).unwrap(),
+        r#"{"user": "@handle", "ref": "*missing", "zip": "01234"}"#
+    );
+}
+
+#[test]
+fn test_space_added_after_key_colon() {
+    let mut repairer = YamlRepairer::new();
+    assert_eq!(
+        repairer.repair("name:John\nage:30").unwrap(),
+        "---\nname: John\nage: 30"
+    );
+
+    // Colons in URLs, times and values after a proper `key: ` are left alone
+    let input = "url: http://x\ntime: 12:30\nlinks:\n  - https://example.com\n  - 09:15";
+    assert_eq!(repairer.repair(input).unwrap(), input);
+    assert_eq!(
+        repairer.repair("site:http://x\nat:12:30").unwrap(),
+        "---\nsite: http://x\nat: 12:30"
+    );
+    assert_eq!(
+        anyrepair::convert::yaml_to_json(&repairer.repair("time:12:30").unwrap()).unwrap(),
+        r#"{"time": "12:30"}"#
+    );
+}
+
+#[test]
+fn test_list_items_with_multibyte_text() {
+    let mut repairer = YamlRepairer::new();
+    let input = "items:\n  - 日本語\n  - 中文";
+    assert_eq!(repairer.repair(input).unwrap(), input);
+
+    // A dash directly before multi-byte text is still a list item
+    let repaired = repairer.repair("items:\n  - 日本語\n  -中文\n  -é").unwrap();
+    assert_eq!(repaired, "---\nitems:\n  - 日本語\n  - 中文\n  - é");
+    assert_eq!(
+        anyrepair::convert::yaml_to_json(&repaired).unwrap(),
+        r#"{"items": ["日本語", "中文", "é"]}"#
+    );
+}